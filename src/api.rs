@@ -0,0 +1,580 @@
+use crate::core;
+use crate::core::client::Exit;
+use crate::core::event::{ClientEvent, ConnectionState, ConsoleCommand, EventBus, Position, Query};
+use crate::core::query::QueryResponse;
+use crate::core::reconnect::Reconnect;
+use crate::itti::basis::{IttiInterface, ITTI};
+use crate::itti::proxy::Proxy;
+use console::style;
+use log::{debug, info, warn};
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+
+// settings applied to the protocol state machine before the first connection
+type Configure = Box<dyn FnOnce(&mut core::client::Client) + Send>;
+
+/// The session loop has stopped, after [`Client::quit`] or when it was dropped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Closed;
+
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "client already quit")
+    }
+}
+
+impl std::error::Error for Closed {}
+
+/// The builder settings can't start a session.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BuildError {
+    /// Protocol version outside 763 (1.20.1) to 766 (1.20.5-1.20.6).
+    UnsupportedVersion(i32),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::UnsupportedVersion(protocol) => {
+                write!(f, "unsupported protocol version: {}", protocol)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Builds a [`Client`] for one server.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = mc_client::ClientBuilder::new("127.0.0.1", 25565)
+///     .username("Steve")
+///     .version(763)
+///     .build()?;
+/// let mut events = client.events();
+/// client.connect().await?;
+/// client.chat("hello").await?;
+/// while let Some(event) = events.next().await {
+///     println!("{:?}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ClientBuilder {
+    host: String,
+    port: u16,
+    username: String,
+    protocol: i32,
+    lang: String,
+    // tcp reader, writer
+    buffers: (i32, i32),
+    // command, response
+    channels: (usize, usize),
+    resolver: Option<SocketAddr>,
+    proxy: Option<Proxy>,
+    reconnect: Option<Reconnect>,
+    configure: Vec<Configure>,
+}
+
+impl ClientBuilder {
+    pub fn new(host: &str, port: u16) -> ClientBuilder {
+        ClientBuilder {
+            host: host.to_string(),
+            port,
+            username: "Steve".to_string(),
+            protocol: core::version::V1_20_1,
+            lang: "en_us".to_string(),
+            buffers: (8192, 8192),
+            channels: (64, 64),
+            resolver: None,
+            proxy: None,
+            reconnect: None,
+            configure: Vec::new(),
+        }
+    }
+
+    /// Player name, offline or of the selected yggdrasil profile.
+    pub fn username(mut self, username: &str) -> ClientBuilder {
+        self.username = username.to_string();
+        self
+    }
+
+    /// Protocol version: 763 (1.20.1) to 766 (1.20.5-1.20.6).
+    pub fn version(mut self, protocol: i32) -> ClientBuilder {
+        self.protocol = protocol;
+        self
+    }
+
+    pub fn lang(mut self, lang: &str) -> ClientBuilder {
+        self.lang = lang.to_string();
+        self
+    }
+
+    /// TCP reader and writer buffer sizes.
+    pub fn buffers(mut self, reader: i32, writer: i32) -> ClientBuilder {
+        self.buffers = (reader, writer);
+        self
+    }
+
+    /// Capacity of the command and response channels.
+    pub fn channels(mut self, command: usize, response: usize) -> ClientBuilder {
+        self.channels = (command, response);
+        self
+    }
+
+    /// DNS server instead of the system configuration.
+    pub fn resolver(mut self, resolver: Option<SocketAddr>) -> ClientBuilder {
+        self.resolver = resolver;
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Option<Proxy>) -> ClientBuilder {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Reconnect by the disconnect reason, otherwise wait for [`Client::connect`].
+    pub fn reconnect(mut self, reconnect: Option<Reconnect>) -> ClientBuilder {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Silence of the server before it counts as timed out.
+    pub fn timeout(self, timeout: Duration) -> ClientBuilder {
+        self.configure(move |client| client.set_timeout(timeout))
+    }
+
+    /// Other settings of the protocol state machine, such as the resource pack policy,
+    /// forwarding or plugin channels.
+    pub fn configure(
+        mut self,
+        configure: impl FnOnce(&mut core::client::Client) + Send + 'static,
+    ) -> ClientBuilder {
+        self.configure.push(Box::new(configure));
+        self
+    }
+
+    /// Starts the session loop, offline until [`Client::connect`].
+    pub fn build(self) -> Result<Client, BuildError> {
+        let mut itti = ITTI::new(
+            self.host.clone(),
+            self.port.to_string(),
+            self.buffers.0,
+            self.buffers.1,
+        );
+        itti.set_resolver(self.resolver);
+        itti.set_proxy(self.proxy.clone());
+        self.build_with(itti)
+    }
+
+    /// Like [`ClientBuilder::build`] over another transport, such as
+    /// [`crate::itti::memory::duplex`] in tests. Host and port are still sent in the handshake.
+    pub fn build_with<I: IttiInterface + 'static>(self, mut itti: I) -> Result<Client, BuildError> {
+        if !core::version::supported(self.protocol) {
+            return Err(BuildError::UnsupportedVersion(self.protocol));
+        }
+        let mut client = core::client::Client::new(self.username, self.protocol, self.lang);
+        for configure in self.configure {
+            configure(&mut client);
+        }
+        itti.set_address(self.host, self.port);
+
+        let (command_tx, command_rx) = mpsc::channel(self.channels.0);
+        let (response_tx, response_rx) = mpsc::channel(self.channels.1);
+        let events = EventBus::new();
+        let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+        tokio::spawn(track(events.subscribe(), snapshot.clone()));
+        let task = tokio::spawn(run(
+            client,
+            itti,
+            self.reconnect,
+            command_rx,
+            response_tx,
+            events.clone(),
+        ));
+        Ok(Client {
+            command_tx,
+            response_rx: tokio::sync::Mutex::new((response_rx, 0)),
+            events,
+            snapshot,
+            task: Mutex::new(Some(task)),
+        })
+    }
+}
+
+/// Async stream of [`ClientEvent`]s, lagged events are skipped.
+pub struct Events {
+    rx: broadcast::Receiver<ClientEvent>,
+}
+
+impl Events {
+    /// Next event, none once the client quit.
+    pub async fn next(&mut self) -> Option<ClientEvent> {
+        loop {
+            match self.rx.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(n)) => {
+                    warn!("event stream lagged behind {} events", n);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+// latest state from the events
+#[derive(Default)]
+struct Snapshot {
+    position: Option<Position>,
+    // health, food, saturation
+    health: Option<(f32, i32, f32)>,
+    state: Option<ConnectionState>,
+}
+
+/// Handle of a running session, see [`ClientBuilder`].
+pub struct Client {
+    command_tx: mpsc::Sender<ConsoleCommand>,
+    // responses, and how many are owed to requests (of dropped callers too)
+    response_rx: tokio::sync::Mutex<(mpsc::Receiver<QueryResponse>, usize)>,
+    events: EventBus,
+    snapshot: Arc<Mutex<Snapshot>>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Client {
+    pub fn builder(host: &str, port: u16) -> ClientBuilder {
+        ClientBuilder::new(host, port)
+    }
+
+    /// Events from now on, every call is an independent subscriber.
+    pub fn events(&self) -> Events {
+        Events {
+            rx: self.events.subscribe(),
+        }
+    }
+
+    pub async fn send(&self, command: ConsoleCommand) -> Result<(), Closed> {
+        self.command_tx.send(command).await.map_err(|_| Closed)
+    }
+
+    pub async fn connect(&self) -> Result<(), Closed> {
+        self.send(ConsoleCommand::Connect).await
+    }
+
+    pub async fn chat(&self, message: &str) -> Result<(), Closed> {
+        self.send(ConsoleCommand::Chat(message.to_string())).await
+    }
+
+    /// Server command, with or without `/`.
+    pub async fn command(&self, command: &str) -> Result<(), Closed> {
+        let command = command.trim_start_matches('/').to_string();
+        self.send(ConsoleCommand::Command(command)).await
+    }
+
+    pub async fn respawn(&self) -> Result<(), Closed> {
+        self.send(ConsoleCommand::Respawn).await
+    }
+
+    /// Answer of a query, none if not connected.
+    pub async fn query(&self, query: Query) -> Option<QueryResponse> {
+        match self.request(ConsoleCommand::Query(query)).await? {
+            QueryResponse::Offline => None,
+            response => Some(response),
+        }
+    }
+
+    /// Candidates to complete a server command (without `/`).
+    pub async fn complete(&self, input: &str) -> Option<Vec<String>> {
+        match self
+            .request(ConsoleCommand::Complete(input.to_string()))
+            .await?
+        {
+            QueryResponse::Completions(candidates) => Some(candidates),
+            _ => None,
+        }
+    }
+
+    // every request is answered once and in order,
+    // answers left by callers dropped while waiting are skipped
+    async fn request(&self, command: ConsoleCommand) -> Option<QueryResponse> {
+        let mut responses = self.response_rx.lock().await;
+        let (response_rx, owed) = &mut *responses;
+        self.send(command).await.ok()?;
+        *owed += 1;
+        loop {
+            let response = response_rx.recv().await?;
+            *owed -= 1;
+            if *owed == 0 {
+                return Some(response);
+            }
+            debug!("skip the response of a dropped request: {:?}", response);
+        }
+    }
+
+    /// Last position sent by the server.
+    pub fn position(&self) -> Option<Position> {
+        self.snapshot.lock().unwrap().position
+    }
+
+    /// Health, food and saturation.
+    pub fn health(&self) -> Option<(f32, i32, f32)> {
+        self.snapshot.lock().unwrap().health
+    }
+
+    pub fn state(&self) -> Option<ConnectionState> {
+        self.snapshot.lock().unwrap().state.clone()
+    }
+
+    /// Stops the session loop, waits with [`Client::wait`].
+    pub async fn quit(&self) -> Result<(), Closed> {
+        self.send(ConsoleCommand::Quit).await
+    }
+
+    /// Until the session loop stopped.
+    pub async fn wait(&self) {
+        let task = self.task.lock().unwrap().take();
+        if let Some(task) = task {
+            match task.await {
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("session loop failed: {}", e);
+                }
+            }
+        }
+    }
+}
+
+async fn track(mut rx: broadcast::Receiver<ClientEvent>, snapshot: Arc<Mutex<Snapshot>>) {
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => break,
+        };
+        let mut snapshot = snapshot.lock().unwrap();
+        match event {
+            ClientEvent::Position(position) => snapshot.position = Some(position),
+            ClientEvent::Health {
+                health,
+                food,
+                saturation,
+            } => snapshot.health = Some((health, food, saturation)),
+            ClientEvent::Connection(state) => {
+                if state == ConnectionState::Connecting {
+                    snapshot.position = None;
+                    snapshot.health = None;
+                }
+                snapshot.state = Some(state);
+            }
+            _ => {}
+        }
+    }
+}
+
+// connect on request, reconnect by the rules, until quit
+async fn run<I: IttiInterface>(
+    mut client: core::client::Client,
+    mut itti: I,
+    mut reconnect: Option<Reconnect>,
+    mut command_rx: mpsc::Receiver<ConsoleCommand>,
+    response_tx: mpsc::Sender<QueryResponse>,
+    events: EventBus,
+) {
+    let mut retry = None;
+    'outer: loop {
+        match retry.take() {
+            // auto reconnect, commands other than quit are dropped while waiting
+            Some(delay) => {
+                let deadline = tokio::time::Instant::now() + delay;
+                loop {
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(deadline) => break,
+                        command = command_rx.recv() => match command {
+                            Some(command) if command != ConsoleCommand::Quit => {
+                                debug!("Ignore command while reconnecting: {:?}", command);
+                                unanswered(&command, &response_tx).await;
+                            }
+                            _ => {
+                                debug!("quit");
+
+                                break 'outer;
+                            }
+                        },
+                    }
+                }
+            }
+            // reconnect
+            None => loop {
+                match command_rx.recv().await {
+                    Some(command) => {
+                        if command == ConsoleCommand::Quit {
+                            debug!("quit");
+
+                            break 'outer;
+                        }
+                        if command == ConsoleCommand::Connect {
+                            break;
+                        } else {
+                            warn!("Unknown command: {:?}", command);
+                            unanswered(&command, &response_tx).await;
+                        }
+                    }
+                    None => {
+                        warn!("client already quit");
+
+                        break 'outer;
+                    }
+                }
+            },
+        }
+        // clear channel
+        while command_rx.try_recv().is_ok() {}
+        // start client
+        let exit = client
+            .start(&mut itti, &mut command_rx, &response_tx, &events)
+            .await;
+        if exit == Exit::Quit {
+            debug!("quit");
+            events.publish(ClientEvent::Connection(ConnectionState::Offline));
+            itti.close().await;
+
+            break;
+        }
+        // next attempt
+        if let Some(reconnect) = &mut reconnect {
+            retry = next_attempt(reconnect, &client, &events);
+        }
+        let state = match retry {
+            Some(delay) => ConnectionState::Reconnecting(delay),
+            None => ConnectionState::Offline,
+        };
+        events.publish(ClientEvent::Connection(state));
+        // reset
+        client.reset();
+        // stop
+        itti.close().await;
+    }
+}
+
+// queries wait for an answer, offline while not connected
+async fn unanswered(command: &ConsoleCommand, response_tx: &mpsc::Sender<QueryResponse>) {
+    if let ConsoleCommand::Query(_) | ConsoleCommand::Complete(_) = command {
+        match response_tx.send(QueryResponse::Offline).await {
+            Ok(_) => {}
+            Err(e) => {
+                warn!("send response failed: {}", e);
+            }
+        }
+    }
+}
+
+// delay of the next connection by the disconnect reason, none to wait for /connect
+fn next_attempt(
+    reconnect: &mut Reconnect,
+    client: &core::client::Client,
+    events: &EventBus,
+) -> Option<Duration> {
+    if client.joined() {
+        reconnect.success();
+    }
+    let reason = client.get_disconnect_reason();
+    let message = match reconnect.next(reason.as_deref()) {
+        Some(delay) => {
+            info!(
+                "Reconnect attempt {}/{} in {:.1}s, reason: {:?}",
+                reconnect.attempt(),
+                reconnect.max_attempts(),
+                delay.as_secs_f64(),
+                reason
+            );
+            let message = format!(
+                "Reconnect attempt {}/{} in {:.1}s",
+                reconnect.attempt(),
+                reconnect.max_attempts(),
+                delay.as_secs_f64()
+            );
+            (Some(delay), message)
+        }
+        None => {
+            info!(
+                "Reconnect stopped after {} attempts, reason: {:?}",
+                reconnect.attempt(),
+                reason
+            );
+            reconnect.success();
+            (None, format!("Reconnect {}", style("stopped").red()))
+        }
+    };
+    events.publish(ClientEvent::Display(vec![message.1]));
+    message.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_offline_quit() {
+        let client = ClientBuilder::new("127.0.0.1", 25565)
+            .username("Alex")
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(client.position(), None);
+        assert_eq!(client.state(), None);
+        client.quit().await.unwrap();
+        client.wait().await;
+        assert_eq!(client.chat("hello").await, Err(Closed));
+        assert_eq!(client.query(Query::Position).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_version() {
+        let client = ClientBuilder::new("127.0.0.1", 25565).version(767).build();
+        assert_eq!(client.err(), Some(BuildError::UnsupportedVersion(767)));
+    }
+
+    #[tokio::test]
+    async fn test_quit_in_session() {
+        let (itti, mut server) = crate::itti::memory::duplex(4096);
+        let client = ClientBuilder::new("127.0.0.1", 25565)
+            .reconnect(Some(Reconnect::new(
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                10,
+                vec![],
+            )))
+            .build_with(itti)
+            .unwrap();
+        let mut events = client.events();
+        client.connect().await.unwrap();
+        // handshake sent, the session is running
+        let mut buf = [0; 1];
+        tokio::io::AsyncReadExt::read_exact(&mut server, &mut buf)
+            .await
+            .unwrap();
+        client.quit().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), client.wait())
+            .await
+            .unwrap();
+        // no reconnect after a quit
+        let mut states = vec![];
+        while let Ok(Some(event)) =
+            tokio::time::timeout(Duration::from_millis(100), events.next()).await
+        {
+            if let ClientEvent::Connection(state) = event {
+                states.push(state);
+            }
+        }
+        assert_eq!(
+            states,
+            vec![ConnectionState::Connecting, ConnectionState::Offline]
+        );
+    }
+}
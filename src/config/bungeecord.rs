@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+#[serde(default)]
+pub struct BungeeCord {
+    // legacy ip forwarding in the handshake
+    pub enabled: bool,
+    #[validate(length(min = 1))]
+    pub address: String,
+}
+
+// disabled when the section is missing
+impl Default for BungeeCord {
+    fn default() -> BungeeCord {
+        BungeeCord {
+            enabled: false,
+            address: "127.0.0.1".to_string(),
+        }
+    }
+}
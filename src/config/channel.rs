@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Default, Deserialize, Validate)]
+#[serde(default)]
+pub struct Channel {
+    // messages on these channels are logged
+    pub register: Vec<String>,
+}
@@ -1,6 +1,13 @@
 use crate::config::buffer::Buffer;
+use crate::config::bungeecord::BungeeCord;
+use crate::config::channel::Channel;
+use crate::config::forge::Forge;
 use crate::config::general::General;
 use crate::config::log::Log;
+use crate::config::reconnect::Reconnect;
+use crate::config::resource_pack::ResourcePack;
+use crate::config::tps::Tps;
+use crate::config::velocity::Velocity;
 use serde::Deserialize;
 use tokio::fs;
 use validator::Validate;
@@ -14,6 +21,27 @@ pub(crate) struct Config {
     pub buffer: Buffer,
     #[validate]
     pub log: Log,
+    #[validate]
+    #[serde(default)]
+    pub resource_pack: ResourcePack,
+    #[validate]
+    #[serde(default)]
+    pub forge: Forge,
+    #[validate]
+    #[serde(default)]
+    pub velocity: Velocity,
+    #[validate]
+    #[serde(default)]
+    pub bungeecord: BungeeCord,
+    #[validate]
+    #[serde(default)]
+    pub channel: Channel,
+    #[validate]
+    #[serde(default)]
+    pub reconnect: Reconnect,
+    #[validate]
+    #[serde(default)]
+    pub tps: Tps,
 }
 
 impl Config {
@@ -45,11 +73,8 @@ mod tests {
         let path = env::current_dir().unwrap().join("conf/config.toml");
         let config = Config::load(path.to_str().unwrap().to_string()).await;
         match config {
-            Ok(_) => assert!(true),
-            Err(e) => {
-                println!("{}", e);
-                assert!(false)
-            }
+            Ok(_) => {}
+            Err(e) => panic!("{}", e),
         }
     }
 
@@ -60,11 +85,48 @@ mod tests {
             .await
             .unwrap();
         match config.validate() {
-            Ok(_) => assert!(true),
-            Err(e) => {
-                println!("{}", e);
-                assert!(false)
+            Ok(_) => {}
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    // sections added after the first release may be left out of older configs
+    #[tokio::test]
+    async fn test_load_without_optional_sections() {
+        let path = env::current_dir().unwrap().join("conf/config.toml");
+        let toml = fs::read_to_string(path).await.unwrap();
+        let sections = [
+            "[resource_pack]",
+            "[forge]",
+            "[velocity]",
+            "[bungeecord]",
+            "[channel]",
+            "[reconnect]",
+            "[tps]",
+        ];
+        let keys = ["server.resolver", "proxy.url", "timeout"];
+        let mut kept = Vec::new();
+        let mut skip = false;
+        for line in toml.lines() {
+            if line.starts_with('[') {
+                skip = sections.contains(&line.trim());
+            }
+            let key = line.split('=').next().unwrap_or_default().trim();
+            if !skip && !keys.contains(&key) {
+                kept.push(line);
             }
         }
+        let config = toml::from_str::<Config>(&kept.join("\n")).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.resource_pack.policy, "accept");
+        assert_eq!(config.forge.marker, "");
+        assert_eq!(config.velocity.forwarding_secret, "");
+        assert!(!config.bungeecord.enabled);
+        assert!(config.channel.register.is_empty());
+        assert_eq!(config.general.server.resolver, "");
+        assert_eq!(config.general.proxy.url, "");
+        assert!(!config.reconnect.enabled);
+        assert_eq!(config.general.timeout, 30);
+        assert_eq!(config.tps.alert_threshold, 0.0);
     }
 }
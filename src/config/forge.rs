@@ -0,0 +1,26 @@
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+#[serde(default)]
+pub struct Forge {
+    // empty for vanilla servers
+    #[validate(regex(path = "MARKER"))]
+    pub marker: String,
+    pub mods: Vec<String>,
+}
+
+// vanilla when the section is missing
+impl Default for Forge {
+    fn default() -> Forge {
+        Forge {
+            marker: String::new(),
+            mods: vec!["minecraft".to_string(), "forge".to_string()],
+        }
+    }
+}
+
+lazy_static! {
+    static ref MARKER: regex::Regex = regex::Regex::new(r"^(|FML2|FML3)$").unwrap();
+}
@@ -1,4 +1,11 @@
 mod buffer;
+mod bungeecord;
+mod channel;
 pub mod factory;
+mod forge;
 mod general;
 mod log;
+mod reconnect;
+mod resource_pack;
+mod tps;
+mod velocity;
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+#[serde(default)]
+pub struct Reconnect {
+    pub enabled: bool,
+    // seconds
+    #[validate(range(min = 1))]
+    pub base_delay: u64,
+    #[validate(range(min = 1))]
+    pub max_delay: u64,
+    #[validate(range(min = 1))]
+    pub max_attempts: u32,
+    pub rules: Vec<Rule>,
+}
+
+// reconnect by hand when the section is missing
+impl Default for Reconnect {
+    fn default() -> Reconnect {
+        Reconnect {
+            enabled: false,
+            base_delay: 1,
+            max_delay: 300,
+            max_attempts: 10,
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Rule {
+    // part of the disconnect reason
+    pub reason: String,
+    pub retry: bool,
+    // seconds, backoff if absent
+    pub delay: Option<u64>,
+}
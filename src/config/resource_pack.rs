@@ -0,0 +1,26 @@
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+#[serde(default)]
+pub struct ResourcePack {
+    #[validate(regex(path = "POLICY"))]
+    pub policy: String,
+    #[validate(length(min = 1))]
+    pub cache: String,
+}
+
+// accept without downloading when the section is missing
+impl Default for ResourcePack {
+    fn default() -> ResourcePack {
+        ResourcePack {
+            policy: "accept".to_string(),
+            cache: "cache/resource_packs".to_string(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref POLICY: regex::Regex = regex::Regex::new(r"^(accept|decline|download)$").unwrap();
+}
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Default, Deserialize, Validate)]
+#[serde(default)]
+pub struct Tps {
+    // 1m tps, 0 to disable
+    #[validate(range(min = 0.0, max = 20.0))]
+    pub alert_threshold: f64,
+}
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+#[serde(default)]
+pub struct Velocity {
+    // empty to disable modern forwarding
+    pub forwarding_secret: String,
+    #[validate(length(min = 1))]
+    pub address: String,
+}
+
+// disabled when the section is missing
+impl Default for Velocity {
+    fn default() -> Velocity {
+        Velocity {
+            forwarding_secret: String::new(),
+            address: "127.0.0.1".to_string(),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use console::style;
+
+// cells of a rendered bar
+const WIDTH: usize = 20;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    // title, health, color, division, flags
+    Add(String, f32, i32, i32, u8),
+    Remove,
+    UpdateHealth(f32),
+    UpdateTitle(String),
+    // color, division
+    UpdateStyle(i32, i32),
+    UpdateFlags(u8),
+    Unknown(i32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BossBar {
+    pub title: String,
+    pub health: f32,
+    // 0: pink, 1: blue, 2: red, 3: green, 4: yellow, 5: purple, 6: white
+    pub color: i32,
+    // 0: none, 1: 6 notches, 2: 10 notches, 3: 12 notches, 4: 20 notches
+    pub division: i32,
+    // 0x01: darken sky, 0x02: dragon bar, 0x04: create fog
+    pub flags: u8,
+}
+
+impl BossBar {
+    // progress bar such as `████████░░░░ 66% Title`
+    pub fn render(&self) -> String {
+        let health = self.health.clamp(0.0, 1.0);
+        let filled = (health * WIDTH as f32).round() as usize;
+        let bar = format!("{}{}", "█".repeat(filled), "░".repeat(WIDTH - filled));
+        let bar = match self.color {
+            0 | 5 => style(bar).magenta(),
+            1 => style(bar).blue(),
+            2 => style(bar).red(),
+            3 => style(bar).green(),
+            4 => style(bar).yellow(),
+            _ => style(bar).white(),
+        };
+        format!(
+            "{} {:>3}% {}",
+            bar,
+            (health * 100.0).round() as i32,
+            self.title
+        )
+    }
+
+    // health step of the bar, to display only visible changes
+    fn step(&self) -> i32 {
+        (self.health.clamp(0.0, 1.0) * 10.0).round() as i32
+    }
+}
+
+#[derive(Default)]
+pub struct BossBars {
+    bars: Vec<(Vec<u8>, BossBar)>,
+}
+
+impl BossBars {
+    pub fn new() -> BossBars {
+        BossBars { bars: Vec::new() }
+    }
+
+    // apply an action, the bar is returned if its display changed
+    pub fn update(&mut self, uuid: Vec<u8>, action: Action) -> Option<BossBar> {
+        if let Action::Add(title, health, color, division, flags) = action {
+            let bar = BossBar {
+                title,
+                health,
+                color,
+                division,
+                flags,
+            };
+            self.bars.retain(|(id, _)| *id != uuid);
+            self.bars.push((uuid, bar.clone()));
+            return Some(bar);
+        }
+        let index = self.bars.iter().position(|(id, _)| *id == uuid)?;
+        let bar = &mut self.bars[index].1;
+        match action {
+            Action::Remove => return Some(self.bars.remove(index).1),
+            Action::UpdateHealth(health) => {
+                let step = bar.step();
+                bar.health = health;
+                if bar.step() == step {
+                    return None;
+                }
+            }
+            Action::UpdateTitle(title) => bar.title = title,
+            Action::UpdateStyle(color, division) => {
+                bar.color = color;
+                bar.division = division;
+                return None;
+            }
+            Action::UpdateFlags(flags) => {
+                bar.flags = flags;
+                return None;
+            }
+            _ => return None,
+        }
+        Some(bar.clone())
+    }
+
+    pub fn bars(&self) -> Vec<&BossBar> {
+        self.bars.iter().map(|(_, bar)| bar).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.bars.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let mut bars = BossBars::new();
+        let uuid = vec![0x01; 16];
+        let bar = bars.update(
+            uuid.clone(),
+            Action::Add("Event".to_string(), 1.0, 2, 0, 0x00),
+        );
+        assert_eq!(bar.unwrap().title, "Event");
+
+        // same step, not displayed
+        assert_eq!(bars.update(uuid.clone(), Action::UpdateHealth(0.98)), None);
+        assert_eq!(
+            bars.update(uuid.clone(), Action::UpdateHealth(0.5))
+                .unwrap()
+                .health,
+            0.5
+        );
+        bars.update(
+            uuid.clone(),
+            Action::UpdateTitle("Event: 1 min".to_string()),
+        );
+        assert_eq!(bars.bars()[0].title, "Event: 1 min");
+
+        bars.update(uuid.clone(), Action::Remove);
+        assert!(bars.bars().is_empty());
+        // unknown bar
+        assert_eq!(bars.update(uuid, Action::UpdateHealth(0.1)), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let bar = BossBar {
+            title: "Timer".to_string(),
+            health: 0.5,
+            color: 6,
+            division: 0,
+            flags: 0x00,
+        };
+        assert_eq!(
+            console::strip_ansi_codes(&bar.render()),
+            "██████████░░░░░░░░░░  50% Timer"
+        );
+    }
+}
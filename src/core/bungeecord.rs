@@ -0,0 +1,144 @@
+use tokio::time::{Duration, Instant};
+
+// proxy command, lists the servers without an argument
+pub const SERVER_COMMAND: &str = "server";
+
+// listings without an answer in time are dropped
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// output of the server command, bungeecord and velocity
+const CURRENT_SERVER: &str = "You are currently connected to ";
+const SERVER_LISTS: [&str; 2] = [
+    "You may connect to the following servers at this time: ",
+    "Available servers: ",
+];
+
+// parsed from the server command output
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    Servers(Vec<String>),
+    Server(String),
+}
+
+#[derive(Default)]
+pub struct BungeeCord {
+    servers: Vec<String>,
+    server: Option<String>,
+    // deadline of the server command output
+    listing: Option<Instant>,
+}
+
+impl BungeeCord {
+    // the server command was sent, its output follows as system chat
+    pub fn list(&mut self, now: Instant) {
+        self.listing = Some(now + TIMEOUT);
+    }
+
+    // current server and the server list from the server command output
+    pub fn output(&mut self, text: &str, now: Instant) -> Option<Response> {
+        match self.listing {
+            Some(deadline) if now < deadline => {}
+            Some(_) => {
+                self.listing = None;
+                return None;
+            }
+            None => return None,
+        }
+        let text = strip_codes(text);
+        if let Some(server) = text.strip_prefix(CURRENT_SERVER) {
+            let server = server.trim().trim_end_matches('.').to_string();
+            self.server = Some(server.clone());
+            return Some(Response::Server(server));
+        }
+        let servers = SERVER_LISTS
+            .iter()
+            .find_map(|prefix| text.strip_prefix(prefix))?;
+        self.listing = None;
+        self.servers = list(servers);
+        Some(Response::Servers(self.servers.clone()))
+    }
+
+    pub fn servers(&self) -> &[String] {
+        &self.servers
+    }
+
+    pub fn server(&self) -> Option<&String> {
+        self.server.as_ref()
+    }
+
+    // the proxy is per connection
+    pub fn reset(&mut self) {
+        *self = BungeeCord::default();
+    }
+}
+
+// legacy formatting codes such as `§6`
+fn strip_codes(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '§' => {
+                chars.next();
+            }
+            c => plain.push(c),
+        }
+    }
+    plain
+}
+
+// comma separated names
+fn list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output() {
+        let mut bungeecord = BungeeCord::default();
+        let now = Instant::now();
+        // not listing
+        assert_eq!(bungeecord.output("Available servers: lobby", now), None);
+
+        // bungeecord
+        bungeecord.list(now);
+        assert_eq!(
+            bungeecord.output("§6You are currently connected to lobby.", now),
+            Some(Response::Server("lobby".to_string()))
+        );
+        assert_eq!(
+            bungeecord.output(
+                "§6You may connect to the following servers at this time: lobby, skyblock",
+                now
+            ),
+            Some(Response::Servers(vec![
+                "lobby".to_string(),
+                "skyblock".to_string()
+            ]))
+        );
+        assert_eq!(bungeecord.server(), Some(&"lobby".to_string()));
+        assert_eq!(bungeecord.output("Available servers: lobby", now), None);
+
+        // velocity, and a listing that timed out
+        bungeecord.list(now);
+        assert_eq!(bungeecord.output("hello", now), None);
+        assert_eq!(
+            bungeecord.output("Available servers: lobby, pvp", now),
+            Some(Response::Servers(vec![
+                "lobby".to_string(),
+                "pvp".to_string()
+            ]))
+        );
+        bungeecord.list(now);
+        assert_eq!(
+            bungeecord.output("Available servers: lobby", now + Duration::from_secs(11)),
+            None
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+pub const REGISTER: &str = "minecraft:register";
+pub const UNREGISTER: &str = "minecraft:unregister";
+
+// messages kept for /channels
+const UNKNOWN_LIMIT: usize = 32;
+
+// data of a message on the channel, the optional reply is sent back on the same channel
+pub type Handler = Box<dyn FnMut(&[u8]) -> Option<Vec<u8>> + Send + Sync>;
+
+#[derive(Default)]
+pub struct Channels {
+    handlers: HashMap<String, Handler>,
+    // registered by the server
+    server: BTreeSet<String>,
+    // channel, data
+    unknown: VecDeque<(String, Vec<u8>)>,
+}
+
+impl Channels {
+    pub fn new() -> Channels {
+        Channels {
+            handlers: HashMap::new(),
+            server: BTreeSet::new(),
+            unknown: VecDeque::new(),
+        }
+    }
+
+    pub fn register(&mut self, channel: &str, handler: Handler) {
+        self.handlers.insert(channel.to_string(), handler);
+    }
+
+    // our channels, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.handlers.keys().cloned().collect::<Vec<String>>();
+        names.sort();
+        names
+    }
+
+    // data of minecraft:register
+    pub fn register_data(&self) -> Vec<u8> {
+        self.names().join("\0").into_bytes()
+    }
+
+    // minecraft:register or minecraft:unregister of the server
+    pub fn update_server(&mut self, channel: &str, data: &[u8]) {
+        let names = String::from_utf8_lossy(data)
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+        match channel {
+            REGISTER => self.server.extend(names),
+            _ => names.iter().for_each(|name| {
+                self.server.remove(name);
+            }),
+        }
+    }
+
+    // reply of the handler, messages without one are kept for /channels
+    pub fn handle(&mut self, channel: &str, data: &[u8]) -> Option<Vec<u8>> {
+        match self.handlers.get_mut(channel) {
+            Some(handler) => handler(data),
+            None => {
+                if self.unknown.len() == UNKNOWN_LIMIT {
+                    self.unknown.pop_front();
+                }
+                self.unknown.push_back((channel.to_string(), data.to_vec()));
+                None
+            }
+        }
+    }
+
+    // registered by the server, sorted
+    pub fn server(&self) -> Vec<String> {
+        self.server.iter().cloned().collect()
+    }
+
+    // oldest first
+    pub fn unknown(&self) -> Vec<(String, Vec<u8>)> {
+        self.unknown.iter().cloned().collect()
+    }
+
+    // handlers stay registered across connections
+    pub fn clear(&mut self) {
+        self.server.clear();
+        self.unknown.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_data() {
+        let mut channels = Channels::new();
+        channels.register("b:b", Box::new(|_| None));
+        channels.register("a:a", Box::new(|_| None));
+        assert_eq!(channels.register_data(), b"a:a\0b:b".to_vec());
+    }
+
+    #[test]
+    fn test_update_server() {
+        let mut channels = Channels::new();
+        channels.update_server(REGISTER, b"a:a\0b:b\0c:c");
+        channels.update_server(UNREGISTER, b"b:b");
+        assert_eq!(channels.server(), vec!["a:a", "c:c"]);
+    }
+
+    #[test]
+    fn test_handle() {
+        let mut channels = Channels::new();
+        channels.register("echo:main", Box::new(|data| Some(data.to_vec())));
+        assert_eq!(channels.handle("echo:main", &[0x01]), Some(vec![0x01]));
+        assert_eq!(channels.handle("other:main", &[0xab, 0xcd]), None);
+        assert_eq!(
+            channels.unknown(),
+            vec![("other:main".to_string(), vec![0xab, 0xcd])]
+        );
+    }
+}
@@ -1,20 +1,88 @@
+use crate::core::boss_bar::BossBars;
+use crate::core::bungeecord;
+use crate::core::bungeecord::{BungeeCord, Response};
+use crate::core::channel;
+use crate::core::channel::{Channels, Handler};
+use crate::core::command_tree;
+use crate::core::command_tree::CommandTree;
+use crate::core::dig;
+use crate::core::dig::Predictions;
+use crate::core::entity;
+use crate::core::entity::{Entity, EntityTracker};
+use crate::core::event::{ClientEvent, ConnectionState, ConsoleCommand, EventBus, Position, Query};
+use crate::core::forge;
+use crate::core::forge::Forge;
+use crate::core::inventory::Inventory;
+use crate::core::latency::Latency;
 use crate::core::msg;
-use crate::core::msg::login::{handshake, login_plugin_response, login_start};
-use crate::core::msg::play::confirm_tp;
+use crate::core::msg::configuration::{finish_configuration, known_packs};
+use crate::core::msg::login::handshake::Forwarding;
+use crate::core::msg::login::{handshake, login_acknowledged, login_plugin_response, login_start};
+use crate::core::msg::play::{
+    acknowledge_configuration, chunk_batch_received, command_suggestions_request, confirm_tp,
+    cookie_response, interact, ping_request, player_action, player_input, pong,
+    resource_pack_response, swing_arm, use_item_on,
+};
 use crate::core::parser;
+use crate::core::parser::configuration::{
+    feature_flags, known_packs as known_packs_parser, update_tags,
+};
 use crate::core::parser::login::{login_plugin_request, login_success, set_compression};
 use crate::core::parser::mapper;
-use crate::core::parser::play::{change_difficulty, server_data, sync_player_position};
-use crate::itti::basis::ITTI;
+use crate::core::parser::play::{
+    acknowledge_block_change, block_update, boss_bar as boss_bar_parser, change_difficulty,
+    clear_titles, command_suggestions, commands, cookie_request, display_objective, login_play,
+    player_info_remove, player_info_update, pong_response, remove_entities,
+    resource_pack as resource_pack_parser, server_data, set_container_slot, set_held_item,
+    spawn_entity, spawn_player, store_cookie, sync_player_position, tab_list, teleport_entity,
+    title_animation, title_text, transfer, update_entity_position, update_objectives, update_score,
+    update_teams,
+};
+use crate::core::player_list::PlayerList;
+use crate::core::query::{ListedPlayer, QueryResponse, Score};
+use crate::core::resource_pack;
+use crate::core::resource_pack::Policy;
+use crate::core::scoreboard::Scoreboard;
+use crate::core::tps::Tps;
+use crate::core::velocity;
+use crate::core::velocity::Velocity;
+use crate::core::version::{State, Version};
+use crate::itti::basis::IttiInterface;
 use crate::util;
 use console::style;
 use log::{debug, error, info, warn};
 use msg::play::{chat_command, chat_message, plugin_message, respawn};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::mpsc;
 use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::time::{Duration, Instant};
+
+// fade in, stay, fade out (ticks)
+const TITLE_TIMES: (i32, i32, i32) = (10, 70, 20);
+const ACTION_BAR_TIME: Duration = Duration::from_secs(3);
+// acknowledged chunk batches (1.20.2+)
+const CHUNKS_PER_TICK: f32 = 25.0;
+// data packs shared with the server (1.20.5+): namespace, id
+const KNOWN_PACKS: [(&str, &str); 1] = [("minecraft", "core")];
+// max cookie payload
+const COOKIE_SIZE: usize = 5120;
+// silence before the connection is dead, as the vanilla client
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// why a session ended
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Exit {
+    // quit command of the console
+    Quit,
+    // kicked, closed or timed out
+    Disconnected,
+}
 
 enum Status {
     Handshake,
     Login,
+    Configuration,
     Play,
 }
 
@@ -25,8 +93,9 @@ pub struct Client {
 
     // player
     username: String,
-    protocol_version: i32,
+    version: Version,
     uuid: Option<Vec<u8>>,
+    entity_id: Option<i32>,
     exp_bar: Option<f32>,
     level: Option<i32>,
     exp_level: Option<i32>,
@@ -48,7 +117,72 @@ pub struct Client {
 
     // time
     time: Option<(i64, i64, i64)>,
-    tps: Option<f32>,
+    tps: Tps,
+
+    // block
+    sequence: i32,
+    ack_sequence: Option<i32>,
+    digging: Option<(i32, i32, i32, Instant)>,
+    predictions: Predictions,
+    // hotbar, for the tool of /dig
+    inventory: Inventory,
+
+    // entity
+    entities: EntityTracker,
+    last_attack: Option<Instant>,
+
+    // boss bar
+    boss_bars: BossBars,
+
+    // scoreboard
+    scoreboard: Scoreboard,
+    players: PlayerList,
+    // header, footer
+    tab_list: Option<(String, String)>,
+    // plain text of the last disconnect reason
+    disconnect: Option<String>,
+
+    // watchdog
+    timeout: Duration,
+    last_packet: Option<Instant>,
+    last_keep_alive: Option<Instant>,
+    latency: Latency,
+
+    // title
+    title_times: (i32, i32, i32),
+    title: Option<(String, Instant)>,
+    subtitle: Option<String>,
+    action_bar: Option<(String, Instant)>,
+
+    // command
+    commands: Option<CommandTree>,
+    suggestion_id: i32,
+    // transaction id, text, local suggestions, deadline
+    suggestion: Option<(i32, String, Vec<String>, Instant)>,
+    suggestions: Option<Vec<String>>,
+
+    // resource pack
+    resource_pack: Policy,
+    // download results, set while listening
+    pack_tx: Option<Sender<Result<PathBuf, String>>>,
+
+    // forge handshake
+    forge: Forge,
+
+    // velocity modern forwarding
+    velocity: Velocity,
+
+    // bungeecord ip forwarding
+    forwarding: Forwarding,
+    // proxy servers from the server command
+    bungeecord: BungeeCord,
+    // plugin channels
+    channels: Channels,
+
+    // cookie
+    cookies: HashMap<String, Vec<u8>>,
+    // host, port
+    transfer: Option<(String, u16)>,
 
     // status
     status: Status,
@@ -61,8 +195,9 @@ impl Client {
             buffer: None,
             val: 0,
             username,
-            protocol_version,
+            version: Version::new(protocol_version),
             uuid: None,
+            entity_id: None,
             exp_bar: None,
             health: None,
             food: None,
@@ -78,57 +213,137 @@ impl Client {
             position: None,
             compress: false,
             time: None,
-            tps: None,
+            tps: Tps::new(0.0),
+            sequence: 0,
+            ack_sequence: None,
+            digging: None,
+            predictions: Predictions::new(),
+            inventory: Inventory::new(),
+            entities: EntityTracker::new(),
+            last_attack: None,
+            boss_bars: BossBars::new(),
+            scoreboard: Scoreboard::new(),
+            players: PlayerList::new(),
+            tab_list: None,
+            disconnect: None,
+            timeout: TIMEOUT,
+            last_packet: None,
+            last_keep_alive: None,
+            latency: Latency::default(),
+            title_times: TITLE_TIMES,
+            title: None,
+            subtitle: None,
+            action_bar: None,
+            commands: None,
+            suggestion_id: 0,
+            suggestion: None,
+            suggestions: None,
+            resource_pack: Policy::Accept,
+            pack_tx: None,
+            forge: Forge::default(),
+            velocity: Velocity::default(),
+            forwarding: Forwarding::None,
+            bungeecord: BungeeCord::default(),
+            channels: Channels::new(),
+            cookies: HashMap::new(),
+            transfer: None,
             status: Status::Handshake,
         }
     }
-    pub async fn start(
+    pub async fn start<I: IttiInterface>(
         &mut self,
-        itti: &mut ITTI,
-        command_rx: &mut Receiver<Vec<String>>,
-        response_tx: &Sender<Vec<String>>,
-        msg_tx: &Sender<Vec<String>>,
-    ) {
-        // start itti
-        match itti.build().await {
-            Ok(_) => {
-                debug!("Built ITTI");
-            }
-            Err(e) => {
-                error!("Failed to build ITTI: {}", e.to_string());
+        itti: &mut I,
+        command_rx: &mut Receiver<ConsoleCommand>,
+        response_tx: &Sender<QueryResponse>,
+        events: &EventBus,
+    ) -> Exit {
+        events.publish(ClientEvent::Connection(ConnectionState::Connecting));
+        let mut intent = handshake::LOGIN;
+        loop {
+            // start itti
+            match itti.connect().await {
+                Ok(_) => {
+                    debug!("Connected ITTI");
+                }
+                Err(e) => {
+                    error!("Failed to connect ITTI: {}", e);
+                    // reconnect rules match the real cause
+                    self.disconnect = Some(e.to_string());
+                    return Exit::Disconnected;
+                }
             }
-        }
 
-        // Send handshake
-        let handshake = handshake::new(
-            self.protocol_version,
-            itti.ip.clone(),
-            itti.port.parse::<u16>().unwrap(),
-            true,
-        );
-        match itti.send(handshake).await {
-            Ok(_) => {
-                debug!("Sent handshake");
-            }
-            Err(e) => {
-                error!("Failed to send handshake: {}", e.to_string());
+            // Send handshake
+            let (host, port) = itti.address();
+            let handshake = handshake::new(
+                self.version.protocol(),
+                self.forge.address(&host),
+                port,
+                intent,
+                &self.forwarding,
+            );
+            match self.send(itti, handshake).await {
+                Ok(_) => {
+                    debug!("Sent handshake");
+                }
+                Err(e) => {
+                    error!("Failed to send handshake: {}", e);
+                }
             }
-        }
 
-        // Send login start
-        let login_start = login_start::new(self.username.clone(), vec![]);
-        match itti.send(login_start).await {
-            Ok(_) => {
-                debug!("Sent login start");
+            // Send login start
+            let login_start = match self.version.has_configuration() {
+                // the server picks the uuid
+                true => login_start::new_with_uuid(self.username.clone(), vec![0x00; 16]),
+                false => login_start::new(self.username.clone(), vec![]),
+            };
+            match self.send(itti, login_start).await {
+                Ok(_) => {
+                    debug!("Sent login start");
+                }
+                Err(e) => {
+                    error!("Failed to send login start: {}", e);
+                }
             }
-            Err(e) => {
-                error!("Failed to send login start: {}", e.to_string());
+
+            // Start listening
+            let exit = self
+                .start_listen(itti, command_rx, response_tx, events)
+                .await;
+            // boss bars and titles belong to the session
+            events.publish(ClientEvent::StatusLine(String::new(), Duration::ZERO));
+
+            // transfer to another server
+            match self.transfer.take() {
+                Some((host, port)) => {
+                    info!("Transfer to {}:{}", host, port);
+                    itti.close().await;
+                    itti.set_address(host, port);
+                    self.reset();
+                    intent = handshake::TRANSFER;
+                }
+                None => return exit,
             }
         }
+    }
 
-        // Start listening
-        self.start_listen(itti, command_rx, response_tx, msg_tx)
-            .await;
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    // alert on the display below this tps, 0 to disable
+    pub fn set_tps_alert(&mut self, threshold: f64) {
+        self.tps = Tps::new(threshold);
+    }
+
+    // silence of the server, or of keep alives once they started
+    fn watchdog_deadline(&self) -> Instant {
+        let last = match (self.last_packet, self.last_keep_alive) {
+            (Some(packet), Some(keep_alive)) => packet.min(keep_alive),
+            (Some(packet), None) => packet,
+            _ => Instant::now(),
+        };
+        last + self.timeout
     }
 
     pub fn reset(&mut self) {
@@ -143,33 +358,155 @@ impl Client {
         self.position = None;
         self.compress = false;
         self.status = Status::Handshake;
+        self.version = Version::new(self.version.protocol());
         self.time = None;
-        self.tps = None;
+        self.tps.clear();
         self.exp_bar = None;
         self.level = None;
         self.exp_level = None;
         self.health = None;
         self.food = None;
         self.saturation = None;
+        self.sequence = 0;
+        self.ack_sequence = None;
+        self.digging = None;
+        self.predictions.clear();
+        self.inventory.clear();
+        self.entity_id = None;
+        self.entities.clear();
+        self.last_attack = None;
+        self.boss_bars.clear();
+        self.scoreboard.clear();
+        self.players.clear();
+        self.tab_list = None;
+        self.disconnect = None;
+        self.last_packet = None;
+        self.last_keep_alive = None;
+        self.latency.clear();
+        self.title_times = TITLE_TIMES;
+        self.title = None;
+        self.subtitle = None;
+        self.action_bar = None;
+        self.commands = None;
+        self.suggestion = None;
+        self.suggestions = None;
+        self.bungeecord.reset();
+        self.channels.clear();
+    }
+
+    fn state(&self) -> State {
+        match self.status {
+            Status::Handshake | Status::Login => State::Login,
+            Status::Configuration => State::Configuration,
+            Status::Play => State::Play,
+        }
+    }
+
+    // packets are built in the 763 layout
+    async fn send<I: IttiInterface>(&self, itti: &I, packet: Vec<u8>) -> std::io::Result<()> {
+        match self
+            .version
+            .serverbound(self.state(), packet, self.compress)
+        {
+            Some(packet) => itti.send(packet).await,
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "not available in this state",
+            )),
+        }
+    }
+
+    // waits for the writer, for latency
+    async fn send_written<I: IttiInterface>(
+        &self,
+        itti: &I,
+        packet: Vec<u8>,
+    ) -> std::io::Result<Instant> {
+        match self
+            .version
+            .serverbound(self.state(), packet, self.compress)
+        {
+            Some(packet) => itti.send_written(packet).await,
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "not available in this state",
+            )),
+        }
+    }
+
+    // after login success
+    async fn logged_in<I: IttiInterface>(&mut self, itti: &I) {
+        if !self.version.has_configuration() {
+            self.status = Status::Play;
+            debug!("Changing status to play");
+            return;
+        }
+        match self
+            .send(itti, login_acknowledged::new(self.compress))
+            .await
+        {
+            Ok(_) => {
+                debug!("Sent login acknowledged");
+            }
+            Err(e) => {
+                error!("Failed to send login acknowledged: {}", e);
+            }
+        }
+        self.status = Status::Configuration;
+        debug!("Changing status to configuration");
     }
 
-    async fn start_listen(
+    async fn start_listen<I: IttiInterface>(
         &mut self,
-        itti: &mut ITTI,
-        command_rx: &mut Receiver<Vec<String>>,
-        response_tx: &Sender<Vec<String>>,
-        msg_tx: &Sender<Vec<String>>,
-    ) {
+        itti: &mut I,
+        command_rx: &mut Receiver<ConsoleCommand>,
+        response_tx: &Sender<QueryResponse>,
+        events: &EventBus,
+    ) -> Exit {
+        let (pack_tx, mut pack_rx) = mpsc::channel(1);
+        self.pack_tx = Some(pack_tx);
+        self.last_packet = Some(Instant::now());
         loop {
             tokio::select! {
                 // console
-                Some(packet) = command_rx.recv() => {
-                    if packet.is_empty() {
+                Some(command) = command_rx.recv() => {
+                    if command == ConsoleCommand::Quit {
                         info!("client quit");
-                        break;
+                        return Exit::Quit;
                     }
                     // process command
-                    self.handle_command(packet, itti, response_tx).await;
+                    self.handle_command(command, itti, response_tx, events).await;
+                },
+
+                // dig
+                _ = tokio::time::sleep_until(self.dig_deadline()), if self.digging.is_some() => {
+                    self.finish_dig(itti, events).await;
+                },
+
+                // resource pack downloaded
+                Some(result) = pack_rx.recv() => {
+                    self.finish_resource_pack(result, itti, events).await;
+                },
+
+                // suggestions timeout
+                _ = tokio::time::sleep_until(self.suggestion_deadline()), if self.suggestion.is_some() => {
+                    if let Some((id, _, local, _)) = self.suggestion.take() {
+                        debug!("Command suggestions timeout: {}", id);
+                        self.suggestions = Some(local);
+                    }
+                    self.flush_suggestions(response_tx).await;
+                },
+
+                // title or action bar fades out beside the boss bars
+                _ = tokio::time::sleep_until(self.status_deadline().unwrap_or(Instant::now())), if self.status_deadline().is_some() => {
+                    self.show_status(events);
+                },
+
+                // watchdog
+                _ = tokio::time::sleep_until(self.watchdog_deadline()) => {
+                    warn!("Server timed out: no packet for {:?}", self.timeout);
+                    self.disconnect = Some("Timed out".to_string());
+                    break;
                 },
 
                 // server
@@ -178,6 +515,8 @@ impl Client {
                         info!("Server closed");
                         break;
                     }
+                    // read time of the reader, not of this loop
+                    self.last_packet = Some(itti.arrival());
 
                     // if last pkt is not complete
                     if self.val != 0 {
@@ -194,7 +533,7 @@ impl Client {
                         packet = packet[self.val as usize..].to_vec();
                         self.buffer = None;
                         self.val = 0;
-                        self.handle_packet(buffer, itti, msg_tx).await;
+                        self.handle_packet(buffer, itti, events).await;
 
                     }else if self.buffer.is_some() {
                         // not enough length for var int
@@ -217,21 +556,27 @@ impl Client {
 
                     // handle packets
                     for p in packets {
-                        self.handle_packet(p, itti, msg_tx).await;
+                        self.handle_packet(p, itti, events).await;
+                    }
+                    self.flush_suggestions(response_tx).await;
+                    if self.transfer.is_some() {
+                        break;
                     }
                 }
             }
         }
+        self.cancel_suggestion(response_tx).await;
+        Exit::Disconnected
     }
 }
 
 //  handle packet
 impl Client {
-    pub async fn handle_packet(
+    pub async fn handle_packet<I: IttiInterface>(
         &mut self,
         packet: Vec<u8>,
-        itti: &ITTI,
-        msg_tx: &Sender<Vec<String>>,
+        itti: &I,
+        events: &EventBus,
     ) {
         match self.status {
             Status::Handshake => {
@@ -246,7 +591,8 @@ impl Client {
                 }
                 let packet_id = packet[1];
                 let packet = packet[2..].to_vec();
-                self.handle_handshake_packet(packet, packet_id, itti).await;
+                self.handle_handshake_packet(packet, packet_id, itti, events)
+                    .await;
             }
             Status::Login => {
                 let (packet_len, data_len, packet_id, packet) =
@@ -264,7 +610,7 @@ impl Client {
                         );
                         return;
                     }
-                    self.handle_login_packet(packet, packet_id as u8, itti)
+                    self.handle_login_packet(packet, packet_id as u8, itti, events)
                         .await;
                 } else if packet_id == -1 {
                     // compress
@@ -284,7 +630,8 @@ impl Client {
                         // len < threshold
                         let packet_id = packet[0];
                         let packet = packet[1..].to_vec();
-                        self.handle_login_packet(packet, packet_id, itti).await;
+                        self.handle_login_packet(packet, packet_id, itti, events)
+                            .await;
                         return;
                     }
 
@@ -292,7 +639,7 @@ impl Client {
                     let packet = match util::zlib::decompress(packet) {
                         Ok(p) => p,
                         Err(e) => {
-                            warn!("Failed to decompress: {}", e.to_string());
+                            warn!("Failed to decompress: {}", e);
                             return;
                         }
                     };
@@ -307,10 +654,11 @@ impl Client {
                     }
                     let packet_id = packet[0];
                     let packet = packet[1..].to_vec();
-                    self.handle_login_packet(packet, packet_id, itti).await;
+                    self.handle_login_packet(packet, packet_id, itti, events)
+                        .await;
                 }
             }
-            Status::Play => {
+            Status::Configuration | Status::Play => {
                 let (packet_len, data_len, packet_id, packet) =
                     util::split::split_packet(packet, self.threshold.unwrap_or(-1));
 
@@ -326,7 +674,7 @@ impl Client {
                         );
                         return;
                     }
-                    self.handle_play_packet(packet, packet_id as u8, itti, msg_tx)
+                    self.dispatch_packet(packet, packet_id as u8, itti, events)
                         .await;
                 } else if packet_id == -1 {
                     // compress
@@ -346,8 +694,7 @@ impl Client {
                         // len < threshold
                         let packet_id = packet[0];
                         let packet = packet[1..].to_vec();
-                        self.handle_play_packet(packet, packet_id, itti, msg_tx)
-                            .await;
+                        self.dispatch_packet(packet, packet_id, itti, events).await;
                         return;
                     }
 
@@ -355,7 +702,7 @@ impl Client {
                     let packet = match util::zlib::decompress(packet) {
                         Ok(p) => p,
                         Err(e) => {
-                            warn!("Failed to decompress: {}", e.to_string());
+                            warn!("Failed to decompress: {}", e);
                             return;
                         }
                     };
@@ -370,16 +717,55 @@ impl Client {
                     }
                     let packet_id = packet[0];
                     let packet = packet[1..].to_vec();
-                    self.handle_play_packet(packet, packet_id, itti, msg_tx)
-                        .await;
+                    self.dispatch_packet(packet, packet_id, itti, events).await;
                 }
             }
         }
     }
 
+    // translate to the 763 layout and handle by status
+    async fn dispatch_packet<I: IttiInterface>(
+        &mut self,
+        packet: Vec<u8>,
+        packet_id: u8,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        let (id, packet) = match self.version.clientbound(self.state(), packet_id, packet) {
+            Some(p) => p,
+            None => {
+                debug!("Ignored packet: 0x{:02x}", packet_id);
+                return;
+            }
+        };
+        match self.status {
+            Status::Configuration => {
+                self.handle_configuration_packet(packet, id, itti, events)
+                    .await
+            }
+            _ => self.handle_play_packet(packet, id, itti, events).await,
+        }
+    }
+
     #[allow(unused_variables)]
-    async fn handle_handshake_packet(&mut self, packet: Vec<u8>, packet_id: u8, itti: &ITTI) {
+    async fn handle_handshake_packet<I: IttiInterface>(
+        &mut self,
+        packet: Vec<u8>,
+        packet_id: u8,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        let (packet_id, packet) = match self.version.clientbound(State::Login, packet_id, packet) {
+            Some(p) => p,
+            None => return,
+        };
         match packet_id {
+            mapper::LOGIN_DISCONNECT => {
+                // 0x00
+                let reason = parser::play::disconnect::parse(packet);
+                info!("Disconnect (login): {}", reason);
+                self.kick(util::chat::to_plain(&reason), events);
+            }
             mapper::LOGIN_SUCCESS => {
                 // 0x02
                 let (uuid, username) = login_success::parse(packet);
@@ -390,7 +776,6 @@ impl Client {
                         username, self.username
                     );
                 }
-                self.status = Status::Play;
                 info!(
                     "Logged in: {}, uuid: {:?}",
                     username,
@@ -399,7 +784,7 @@ impl Client {
                         .iter()
                         .fold(String::new(), |acc, x| acc + &format!("0x{:02x?} ", x))
                 );
-                debug!("Changing status to play");
+                self.logged_in(itti).await;
             }
             mapper::SET_COMPRESSION => {
                 // 0x03
@@ -416,28 +801,35 @@ impl Client {
             }
             mapper::LOGIN_PLUGIN_REQUEST => {
                 // 0x04
-                let (id, channel, data) = login_plugin_request::parse(packet);
-                let response = login_plugin_response::new(id, false, self.compress); // no check
-                match itti.send(response).await {
-                    Ok(_) => {
-                        debug!("Sent login plugin response");
-                    }
-                    Err(e) => {
-                        warn!("Failed to send login plugin response: {}", e.to_string());
-                    }
-                }
-                info!(
-                    "Login plugin request: id: {}, channel: {}, data: {:?}",
-                    id, channel, data
-                );
+                self.answer_login_plugin(packet, itti).await;
+            }
+            mapper::COOKIE_REQUEST => {
+                let key = cookie_request::parse(packet);
+                self.answer_cookie(key, itti).await;
             }
             _ => {}
         }
     }
 
     #[allow(unused_variables)]
-    async fn handle_login_packet(&mut self, packet: Vec<u8>, packet_id: u8, itti: &ITTI) {
+    async fn handle_login_packet<I: IttiInterface>(
+        &mut self,
+        packet: Vec<u8>,
+        packet_id: u8,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        let (packet_id, packet) = match self.version.clientbound(State::Login, packet_id, packet) {
+            Some(p) => p,
+            None => return,
+        };
         match packet_id {
+            mapper::LOGIN_DISCONNECT => {
+                // 0x00
+                let reason = parser::play::disconnect::parse(packet);
+                info!("Disconnect (login): {}", reason);
+                self.kick(util::chat::to_plain(&reason), events);
+            }
             mapper::LOGIN_SUCCESS => {
                 // 0x02
                 let (uuid, username) = login_success::parse(packet);
@@ -448,7 +840,6 @@ impl Client {
                         username, self.username
                     );
                 }
-                self.status = Status::Play;
                 info!(
                     "Logged in: {}, uuid: {:?}",
                     username,
@@ -457,36 +848,98 @@ impl Client {
                         .iter()
                         .fold(String::new(), |acc, x| acc + &format!("0x{:02x?} ", x))
                 );
-                debug!("Changing status to play");
+                self.logged_in(itti).await;
             }
             mapper::LOGIN_PLUGIN_REQUEST => {
                 // 0x04
-                let (id, channel, data) = login_plugin_request::parse(packet);
-                let response = login_plugin_response::new(id, false, self.compress); // no check
-                match itti.send(response).await {
+                self.answer_login_plugin(packet, itti).await;
+            }
+            mapper::COOKIE_REQUEST => {
+                let key = cookie_request::parse(packet);
+                self.answer_cookie(key, itti).await;
+            }
+            _ => {}
+        }
+    }
+
+    #[allow(unused_variables)]
+    async fn handle_configuration_packet<I: IttiInterface>(
+        &mut self,
+        packet: Vec<u8>,
+        packet_id: u8,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        match packet_id {
+            mapper::REGISTRY_DATA => {
+                debug!("Registry data: {} bytes", packet.len());
+            }
+            mapper::FEATURE_FLAGS => {
+                let flags = feature_flags::parse(packet);
+                info!("Feature flags: {:?}", flags);
+            }
+            mapper::UPDATE_TAGS => {
+                let registries = update_tags::parse(packet.clone());
+                debug!("Update tags: {:?}", registries);
+                self.inventory.set_tags(update_tags::items(packet));
+            }
+            mapper::FINISH_CONFIGURATION => {
+                let response = finish_configuration::new(self.compress);
+                match self.send(itti, response).await {
                     Ok(_) => {
-                        debug!("Sent login plugin response");
+                        debug!("Sent finish configuration");
                     }
                     Err(e) => {
-                        warn!("Failed to send login plugin response: {}", e.to_string());
+                        warn!("Failed to send finish configuration: {}", e);
+                    }
+                }
+                self.status = Status::Play;
+                debug!("Changing status to play");
+            }
+            mapper::KNOWN_PACKS => {
+                let packs = known_packs_parser::parse(packet);
+                debug!("Known packs: {:?}", packs);
+                let packs = packs
+                    .into_iter()
+                    .filter(|(namespace, id, _)| {
+                        KNOWN_PACKS.contains(&(namespace.as_str(), id.as_str()))
+                    })
+                    .collect();
+                match self
+                    .send(itti, known_packs::new(packs, self.compress))
+                    .await
+                {
+                    Ok(_) => {
+                        debug!("Sent known packs");
+                    }
+                    Err(e) => {
+                        warn!("Failed to send known packs: {}", e);
                     }
                 }
-                info!(
-                    "Login plugin request: id: {}, channel: {}, data: {:?}",
-                    id, channel, data
-                );
+            }
+            // same as in play
+            mapper::KEEP_LIVE
+            | mapper::PING
+            | mapper::PLUGIN_MESSAGE
+            | mapper::DISCONNECT
+            | mapper::RESOURCE_PACK
+            | mapper::COOKIE_REQUEST
+            | mapper::STORE_COOKIE
+            | mapper::TRANSFER => {
+                self.handle_play_packet(packet, packet_id, itti, events)
+                    .await;
             }
             _ => {}
         }
     }
 
     #[allow(unused_variables)]
-    async fn handle_play_packet(
+    async fn handle_play_packet<I: IttiInterface>(
         &mut self,
         packet: Vec<u8>,
         packet_id: u8,
-        itti: &ITTI,
-        msg_tx: &Sender<Vec<String>>,
+        itti: &I,
+        events: &EventBus,
     ) {
         match packet_id {
             mapper::CHANGE_DIFFICULTY => {
@@ -498,33 +951,45 @@ impl Client {
                     self.difficulty.as_ref().unwrap(),
                     lock
                 );
-                match msg_tx
-                    .send(vec![format!(
+                self.display(
+                    vec![format!(
                         "Server difficulty: {}, is locked: {}",
                         style(self.difficulty.as_ref().unwrap()).green(),
                         style(lock).green()
-                    )])
-                    .await
-                {
-                    Ok(_) => {
-                        debug!("Sent difficulty");
-                    }
-                    Err(e) => {
-                        warn!("Failed to send difficulty: {}", e.to_string());
-                    }
-                }
+                    )],
+                    events,
+                );
             }
             mapper::KEEP_LIVE => {
                 // 0x23
                 let id = parser::play::keep_live::parse(packet);
                 debug!("Keep live: {:?}", id);
+                let arrival = itti.arrival();
+                self.last_keep_alive = Some(arrival);
                 let response = msg::play::keep_live::new(id.clone(), self.compress);
-                match itti.send(response).await {
-                    Ok(_) => {
+                match self.send_written(itti, response).await {
+                    Ok(written) => {
                         debug!("Sent keep live response");
+                        self.latency.keep_alive.push(written - arrival);
                     }
                     Err(e) => {
-                        warn!("Failed to send keep live response: {}", e.to_string());
+                        warn!("Failed to send keep live response: {}", e);
+                    }
+                }
+                // measure the round trip on each keep alive
+                if matches!(self.status, Status::Play) && self.version.has_configuration() {
+                    let payload = self.latency.ping_request();
+                    match self
+                        .send_written(itti, ping_request::new(payload, self.compress))
+                        .await
+                    {
+                        Ok(written) => {
+                            debug!("Sent ping request: {}", payload);
+                            self.latency.ping_written(payload, written);
+                        }
+                        Err(e) => {
+                            warn!("Failed to send ping request: {}", e);
+                        }
                     }
                 }
             }
@@ -540,35 +1005,32 @@ impl Client {
                     self.motor.as_ref().unwrap(),
                     self.enforce_chat.as_ref().unwrap()
                 );
-                match msg_tx
-                    .send(vec![format!(
+                self.display(
+                    vec![format!(
                         "moto: {}",
                         style(self.motor.as_ref().unwrap()).white()
-                    )])
-                    .await
-                {
-                    Ok(_) => {
-                        debug!("Sent server data");
-                    }
-                    Err(e) => {
-                        warn!("Failed to send server data: {}", e.to_string());
-                    }
-                }
+                    )],
+                    events,
+                );
             }
             mapper::SYNC_PLAYER_POSITION => {
                 // 0x3c
                 let (x, y, z, yaw, pitch, is_abs, tp_id) = sync_player_position::parse(packet);
                 self.position = Some((x, y, z, yaw, pitch));
+                events.publish(ClientEvent::Position(Position {
+                    x,
+                    y,
+                    z,
+                    yaw,
+                    pitch,
+                }));
                 let response = confirm_tp::new(tp_id, self.compress);
-                match itti.send(response).await {
+                match self.send(itti, response).await {
                     Ok(_) => {
                         debug!("Sent sync player position response");
                     }
                     Err(e) => {
-                        warn!(
-                            "Failed to send sync player position response: {}",
-                            e.to_string()
-                        );
+                        warn!("Failed to send sync player position response: {}", e);
                     }
                 }
                 info!(
@@ -583,22 +1045,29 @@ impl Client {
             mapper::PLUGIN_MESSAGE => {
                 // 0x17
                 let (channel, data) = parser::play::plugin_message::parse(packet);
-                info!("Plugin message: channel- {}, data- {:?}", channel, data);
-                #[allow(clippy::single_match)]
+                info!(
+                    "Plugin message: channel- {}, data- {} bytes",
+                    channel,
+                    data.len()
+                );
                 match channel.as_str() {
                     "minecraft:brand" => {
+                        info!(
+                            "Server brand: {}",
+                            util::transfer_var::read_string(&data, &mut 0)
+                        );
                         // send brand
                         let response = plugin_message::new(
                             "minecraft:brand".to_string(),
-                            "Minecraft-Console-Client/1.20.2".to_string(),
+                            format!("Minecraft-Console-Client/{}", self.version.name()),
                             self.compress,
                         );
-                        match itti.send(response).await {
+                        match self.send(itti, response).await {
                             Ok(_) => {
                                 debug!("Sent plugin message response");
                             }
                             Err(e) => {
-                                warn!("Failed to send plugin message response: {}", e.to_string());
+                                warn!("Failed to send plugin message response: {}", e);
                             }
                         }
                         // send client information
@@ -609,35 +1078,98 @@ impl Client {
                             true,
                             self.compress,
                         );
-                        match itti.send(response).await {
+                        match self.send(itti, response).await {
                             Ok(_) => {
                                 debug!("Sent client information");
                             }
                             Err(e) => {
-                                warn!("Failed to send client information: {}", e.to_string());
+                                warn!("Failed to send client information: {}", e);
+                            }
+                        }
+                    }
+                    channel::REGISTER | channel::UNREGISTER => {
+                        self.channels.update_server(&channel, &data);
+                    }
+                    _ => {
+                        if let Some(reply) = self.channels.handle(&channel, &data) {
+                            let response =
+                                plugin_message::new_with_data(channel, reply, self.compress);
+                            match self.send(itti, response).await {
+                                Ok(_) => {
+                                    debug!("Sent plugin channel reply");
+                                }
+                                Err(e) => {
+                                    warn!("Failed to send plugin channel reply: {}", e);
+                                }
                             }
                         }
                     }
-                    _ => {}
                 }
             }
             mapper::DISCONNECT => {
                 // 0x1a
                 let reason = parser::play::disconnect::parse(packet);
                 info!("Disconnect: {}", reason);
+                self.kick(util::chat::to_plain(&reason), events);
             }
             mapper::SYSTEM_CHAT_MESSAGE => {
                 // 0x64
                 let (data, is_overlay) = parser::play::system_chat_message::parse(packet);
                 info!("System chat message: {}, overlay: {}", data, is_overlay);
-                match msg_tx.send(vec!["system chat".to_string(), data]).await {
-                    Ok(_) => {
-                        debug!("Sent system chat message");
-                    }
-                    Err(e) => {
-                        warn!("Failed to send system chat message: {}", e.to_string());
+                if is_overlay {
+                    // action bar
+                    self.set_action_bar(util::chat::to_plain(&data), events);
+                    return;
+                }
+                let message = util::chat::to_plain(&data);
+                // output of /servers
+                match self.bungeecord.output(&message, Instant::now()) {
+                    Some(Response::Servers(servers)) => {
+                        self.show_servers(&servers, events);
+                        return;
                     }
+                    Some(_) => return,
+                    None => {}
+                }
+                // `<name> text` of chat plugins, name with team color
+                let line = match message
+                    .strip_prefix('<')
+                    .and_then(|rest| rest.split_once("> "))
+                {
+                    Some((name, text)) => format!("<{}> {}", self.scoreboard.decorate(name), text),
+                    None => message.clone(),
+                };
+                events.publish(ClientEvent::Chat {
+                    sender: None,
+                    message,
+                    line,
+                });
+            }
+            mapper::PLAYER_CHAT_MESSAGE => {
+                // 0x35
+                let (_, body, unsigned, filter, chat_type, name, _) =
+                    parser::play::player_chat_message::parse(packet);
+                info!(
+                    "Player chat message: body: {}, unsigned: {:?}, filter: {}, chat type: {}, sender: {}",
+                    body, unsigned, filter, chat_type, name
+                );
+                // fully filtered
+                if filter == 1 {
+                    return;
                 }
+                let sender = util::chat::to_plain(&name);
+                // the server may replace the signed body
+                let message = match unsigned {
+                    Some(unsigned) => util::chat::to_plain(&unsigned),
+                    None => body,
+                };
+                // sender with team color
+                let line = format!("<{}> {}", self.scoreboard.decorate(&sender), message);
+                events.publish(ClientEvent::Chat {
+                    sender: Some(sender),
+                    message,
+                    line,
+                });
             }
             mapper::DISGUISED_CHAT_MESSAGE => {
                 // 0x1b
@@ -647,45 +1179,25 @@ impl Client {
                     "Disguised chat message: msg: {}, chat type: {}, chat type name: {}, has target name: {}, target name: {}",
                     msg, chat_type, chat_type_name, has_target_name, target_name
                 );
-                match msg_tx.send(vec!["disguised chat".to_string(), msg]).await {
-                    Ok(_) => {
-                        debug!("Sent disguised chat message");
-                    }
-                    Err(e) => {
-                        warn!("Failed to send disguised chat message: {}", e.to_string());
-                    }
-                }
+                let message = util::chat::to_plain(&msg);
+                events.publish(ClientEvent::Chat {
+                    sender: None,
+                    message: message.clone(),
+                    line: message,
+                });
             }
             mapper::UPDATE_TIME => {
                 // 0x5e
                 let (word_age, time_of_day) = parser::play::update_time::parse(packet);
                 let day = word_age / 24000;
-                // cal tps
-                if let Some((last_word_age, last_time_of_day, last_day)) = self.time {
-                    // 1 tick = 1/20 sec
-                    let tps = (word_age - last_word_age) as f32;
-                    match self.tps {
-                        Some(last_tps) => {
-                            self.tps = Some((last_tps + tps) / 2.0);
-                        }
-                        None => {
-                            self.tps = Some(tps);
-                        }
-                    }
-                }
-                match self.tps {
-                    Some(tps) => {
-                        debug!(
-                            "Update time: word age: {}, day: {}, time of day: {}, TPS: {:.2}",
-                            word_age, day, time_of_day, tps
-                        );
-                    }
-                    None => {
-                        debug!(
-                            "Update time: word age: {}, day: {}, time of day: {}, TPS: None",
-                            word_age, day, time_of_day
-                        );
-                    }
+                debug!(
+                    "Update time: word age: {}, day: {}, time of day: {}",
+                    word_age, day, time_of_day
+                );
+                // ticks per real second
+                if let Some(alert) = self.tps.record(word_age, Instant::now()) {
+                    warn!("{}", alert);
+                    self.display(vec![style(alert).yellow().to_string()], events);
                 }
                 self.time = Some((word_age, time_of_day, day));
             }
@@ -702,222 +1214,1855 @@ impl Client {
                     self.exp_level.as_ref().unwrap()
                 );
             }
-            mapper::SET_HEALTH => {
-                // 0x57
-                let (health, food, saturation) = parser::play::set_health::parse(packet);
-                info!(
-                    "Set health: health: {}, food: {}, saturation: {}",
-                    health, food, saturation
+            mapper::ACKNOWLEDGE_BLOCK_CHANGE => {
+                // 0x06
+                let sequence = acknowledge_block_change::parse(packet);
+                self.ack_sequence = Some(sequence);
+                debug!(
+                    "Acknowledge block change: sequence: {}, pending: {}",
+                    sequence,
+                    self.sequence - sequence
                 );
-                self.health = Some(health);
-                self.food = Some(food);
-                self.saturation = Some(saturation);
+                self.settle(sequence, events);
             }
-            _ => {}
-        }
-    }
-}
-
-//  command response
-impl Client {
-    async fn handle_command(
-        &mut self,
-        packet: Vec<String>,
-        itti: &ITTI,
-        response_tx: &Sender<Vec<String>>,
-    ) {
-        match packet[0].as_str() {
-            "respawn" => {
-                let response = self.respawn();
-                match itti.send(response).await {
-                    Ok(_) => {
-                        debug!("Sent respawn");
+            mapper::BLOCK_UPDATE => {
+                // 0x0a
+                let (position, state) = block_update::parse(packet);
+                debug!("Block update: {:?}, state: {}", position, state);
+                self.predictions.update(position, state);
+            }
+            mapper::SET_HELD_ITEM => {
+                // 0x4d
+                let slot = set_held_item::parse(packet);
+                match self.inventory.set_held(slot) {
+                    true => {
+                        debug!("Set held item: slot {}, {}", slot, self.inventory.held());
                     }
-                    Err(e) => {
-                        error!("Failed to send respawn: {}", e.to_string());
+                    false => {
+                        warn!("Invalid held slot: {}", slot);
                     }
                 }
             }
-            "position" => match response_tx.send(vec![self.get_position()]).await {
-                Ok(_) => {
-                    debug!("Sent position");
-                }
-                Err(e) => {
-                    error!("Failed to send position: {}", e.to_string());
+            mapper::SET_CONTAINER_SLOT => {
+                // 0x14
+                let (window, slot, item) = set_container_slot::parse(packet);
+                debug!(
+                    "Set container slot: window {}, slot {}, item {:?}",
+                    window, slot, item
+                );
+                self.inventory.set_slot(window, slot, item);
+            }
+            mapper::UPDATE_TAGS => {
+                // 0x6e, in configuration since 764
+                self.inventory.set_tags(update_tags::items(packet));
+            }
+            mapper::BOSS_BAR => {
+                // 0x0b
+                let (uuid, action) = boss_bar_parser::parse(packet);
+                debug!("Boss bar: {:?}", action);
+                if self.boss_bars.update(uuid, action).is_some() {
+                    self.show_status(events);
                 }
-            },
-            "server" => match response_tx.send(vec![self.get_server_data()]).await {
-                Ok(_) => {
-                    debug!("Sent server data");
+            }
+            mapper::SET_TITLE_TEXT => {
+                // 0x5f
+                let text = util::chat::to_plain(&title_text::parse(packet));
+                info!("Title: {}", text);
+                let (fade_in, stay, fade_out) = self.title_times;
+                let time = Duration::from_millis((fade_in + stay + fade_out).max(0) as u64 * 50);
+                self.title = Some((text, Instant::now() + time));
+                self.show_status(events);
+            }
+            mapper::SET_SUBTITLE_TEXT => {
+                // 0x5d
+                let text = util::chat::to_plain(&title_text::parse(packet));
+                info!("Subtitle: {}", text);
+                self.subtitle = Some(text);
+                // shown with the current title
+                if self
+                    .title
+                    .as_ref()
+                    .is_some_and(|(_, t)| *t > Instant::now())
+                {
+                    self.show_status(events);
                 }
-                Err(e) => {
-                    error!("Failed to send server data: {}", e.to_string());
+            }
+            mapper::SET_ACTION_BAR_TEXT => {
+                // 0x46
+                let text = util::chat::to_plain(&title_text::parse(packet));
+                self.set_action_bar(text, events);
+            }
+            mapper::SET_TITLES_ANIMATION => {
+                // 0x60
+                let times = title_animation::parse(packet);
+                debug!("Titles animation: {:?}", times);
+                self.title_times = times;
+            }
+            mapper::CLEAR_TITLES => {
+                // 0x0e
+                let reset = clear_titles::parse(packet);
+                debug!("Clear titles, reset: {}", reset);
+                self.title = None;
+                self.subtitle = None;
+                if reset {
+                    self.title_times = TITLE_TIMES;
                 }
-            },
-            "chat" => {
-                let response = self.chat_message(packet[1].clone());
-                match itti.send(response).await {
-                    Ok(_) => {
-                        debug!("Sent chat message: {}", packet[1]);
-                    }
-                    Err(e) => {
-                        error!("Failed to send chat message: {}", e.to_string());
+                self.show_status(events);
+            }
+            mapper::DISPLAY_OBJECTIVE => {
+                // 0x51
+                let (position, name) = display_objective::parse(packet);
+                debug!("Display objective: position: {}, name: {}", position, name);
+                self.scoreboard.display(position, name);
+            }
+            mapper::UPDATE_OBJECTIVES => {
+                // 0x58
+                let (name, objective) = update_objectives::parse(packet);
+                debug!("Update objectives: {}: {:?}", name, objective);
+                self.scoreboard.update_objective(name, objective);
+            }
+            mapper::UPDATE_SCORE => {
+                // 0x5b
+                let (entity, objective, score) = update_score::parse(packet);
+                debug!("Update score: {}: {}: {:?}", objective, entity, score);
+                self.scoreboard.update_score(entity, objective, score);
+            }
+            mapper::UPDATE_TEAMS => {
+                // 0x5a
+                let (name, action) = update_teams::parse(packet);
+                debug!("Update teams: {}: {:?}", name, action);
+                self.scoreboard.update_team(name, action);
+            }
+            mapper::PLAYER_INFO_UPDATE => {
+                // 0x3a
+                for (uuid, update) in player_info_update::parse(packet) {
+                    debug!("Player info update: {:?}", update);
+                    if let (Some(latency), true) = (update.latency, self.uuid == Some(uuid.clone()))
+                    {
+                        // measured by the server from our keep alive replies
+                        self.latency
+                            .tab_list
+                            .push(Duration::from_millis(latency.max(0) as u64));
                     }
+                    self.players.update(uuid, update);
                 }
             }
-            "command" => {
-                let response = self.chat_command(packet[1].clone());
-                match itti.send(response).await {
+            mapper::RESOURCE_PACK => {
+                // 0x40
+                let (url, hash, forced, prompt) = resource_pack_parser::parse(packet);
+                info!(
+                    "Resource pack: url: {}, hash: {}, forced: {}, prompt: {:?}",
+                    url, hash, forced, prompt
+                );
+                self.handle_resource_pack(url, hash, forced, itti, events)
+                    .await;
+            }
+            mapper::TAB_LIST => {
+                // 0x65
+                let (header, footer) = tab_list::parse(packet);
+                let (header, footer) =
+                    (util::chat::to_plain(&header), util::chat::to_plain(&footer));
+                debug!("Tab list: header: {}, footer: {}", header, footer);
+                self.tab_list = Some((header, footer));
+            }
+            mapper::PLAYER_INFO_REMOVE => {
+                // 0x39
+                let uuids = player_info_remove::parse(packet);
+                debug!("Player info remove: {}", uuids.len());
+                self.players.remove(uuids);
+            }
+            mapper::COMMANDS => {
+                // 0x10
+                match commands::parse(packet, self.version.protocol()) {
+                    Ok(tree) => {
+                        info!("Commands: {} nodes", tree.nodes.len());
+                        self.commands = Some(tree);
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse commands: {}", e);
+                        self.commands = None;
+                    }
+                }
+            }
+            mapper::COMMAND_SUGGESTIONS_RESPONSE => {
+                // 0x0f
+                let (id, start, length, matches) = command_suggestions::parse(packet);
+                debug!(
+                    "Command suggestions: id: {}, start: {}, length: {}, matches: {:?}",
+                    id, start, length, matches
+                );
+                match self.suggestion.take() {
+                    Some((pending, text, mut local, _)) if pending == id => {
+                        // replace the range of `/text` with each match
+                        let text = format!("/{}", text);
+                        let head = text.get(1..start).unwrap_or_default();
+                        let tail = text.get(start + length..).unwrap_or_default();
+                        local.extend(matches.iter().map(|m| format!("{}{}{}", head, m, tail)));
+                        local.sort();
+                        local.dedup();
+                        self.suggestions = Some(local);
+                    }
+                    pending => {
+                        debug!("Ignore command suggestions: {}", id);
+                        self.suggestion = pending;
+                    }
+                }
+            }
+            mapper::PING => {
+                let arrival = itti.arrival();
+                let id = parser::play::ping::parse(packet);
+                match self.send_written(itti, pong::new(id, self.compress)).await {
+                    Ok(written) => {
+                        debug!("Sent pong: {}", id);
+                        self.latency.pong.push(written - arrival);
+                    }
+                    Err(e) => {
+                        warn!("Failed to send pong: {}", e);
+                    }
+                }
+            }
+            mapper::PONG_RESPONSE => {
+                let payload = pong_response::parse(packet);
+                match self.latency.pong_response(payload, itti.arrival()) {
+                    Some(elapsed) => {
+                        debug!("Pong response: {}, {:?}", payload, elapsed);
+                    }
+                    None => {
+                        debug!("Ignore pong response: {}", payload);
+                    }
+                }
+            }
+            mapper::CHUNK_BATCH_FINISHED => {
+                let response = chunk_batch_received::new(CHUNKS_PER_TICK, self.compress);
+                match self.send(itti, response).await {
                     Ok(_) => {
-                        debug!("Sent chat command: {}", packet[1]);
+                        debug!("Sent chunk batch received");
                     }
                     Err(e) => {
-                        error!("Failed to send chat command: {}", e.to_string());
+                        warn!("Failed to send chunk batch received: {}", e);
                     }
                 }
             }
-            "time" => match response_tx.send(vec![self.get_time()]).await {
-                Ok(_) => {
-                    debug!("Sent time");
+            mapper::COOKIE_REQUEST => {
+                let key = cookie_request::parse(packet);
+                self.answer_cookie(key, itti).await;
+            }
+            mapper::STORE_COOKIE => {
+                let (key, payload) = store_cookie::parse(packet);
+                self.store_cookie(key, payload);
+            }
+            mapper::TRANSFER => {
+                let (host, port) = transfer::parse(packet);
+                self.display(
+                    vec![format!(
+                        "Transferring to {}",
+                        style(format!("{}:{}", host, port)).cyan()
+                    )],
+                    events,
+                );
+                self.transfer = Some((host, port));
+            }
+            mapper::START_CONFIGURATION => {
+                let response = acknowledge_configuration::new(self.compress);
+                match self.send(itti, response).await {
+                    Ok(_) => {
+                        debug!("Sent acknowledge configuration");
+                    }
+                    Err(e) => {
+                        warn!("Failed to send acknowledge configuration: {}", e);
+                    }
                 }
-                Err(e) => {
-                    error!("Failed to send time: {}", e.to_string());
+                // the world is sent again after configuration
+                self.entities.clear();
+                self.boss_bars.clear();
+                self.show_status(events);
+                self.scoreboard.clear();
+                self.players.clear();
+                self.tab_list = None;
+                self.status = Status::Configuration;
+                info!("Server requested configuration");
+            }
+            mapper::LOGIN_PLAY => {
+                // 0x28
+                let entity_id = login_play::parse(packet);
+                self.entity_id = Some(entity_id);
+                info!("Login (play): entity id: {}", entity_id);
+                events.publish(ClientEvent::Connection(ConnectionState::Joined));
+                self.register_channels(itti).await;
+            }
+            mapper::SPAWN_ENTITY => {
+                // 0x01
+                let (id, uuid, kind, x, y, z) = spawn_entity::parse(packet);
+                let kind = entity::type_name(self.version.protocol(), kind);
+                debug!(
+                    "Spawn entity: id: {}, type: {}, x: {}, y: {}, z: {}",
+                    id, kind, x, y, z
+                );
+                self.entities.spawn(id, uuid, kind, x, y, z);
+            }
+            mapper::SPAWN_PLAYER => {
+                // 0x03
+                let (id, uuid, x, y, z) = spawn_player::parse(packet);
+                debug!("Spawn player: id: {}, x: {}, y: {}, z: {}", id, x, y, z);
+                self.entities.spawn(id, uuid, "player".to_string(), x, y, z);
+            }
+            mapper::REMOVE_ENTITIES => {
+                // 0x3e
+                let ids = remove_entities::parse(packet);
+                debug!("Remove entities: {:?}", ids);
+                self.entities.remove(ids);
+            }
+            mapper::UPDATE_ENTITY_POSITION | mapper::UPDATE_ENTITY_POSITION_AND_ROTATION => {
+                // 0x2b, 0x2c
+                let (id, dx, dy, dz) = update_entity_position::parse(packet);
+                self.entities.relative_move(id, dx, dy, dz);
+            }
+            mapper::TELEPORT_ENTITY => {
+                // 0x68
+                let (id, x, y, z) = teleport_entity::parse(packet);
+                self.entities.teleport(id, x, y, z);
+            }
+            mapper::SET_HEALTH => {
+                // 0x57
+                let (health, food, saturation) = parser::play::set_health::parse(packet);
+                info!(
+                    "Set health: health: {}, food: {}, saturation: {}",
+                    health, food, saturation
+                );
+                self.health = Some(health);
+                self.food = Some(food);
+                self.saturation = Some(saturation);
+                events.publish(ClientEvent::Health {
+                    health,
+                    food,
+                    saturation,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+//  command response
+impl Client {
+    async fn handle_command<I: IttiInterface>(
+        &mut self,
+        command: ConsoleCommand,
+        itti: &I,
+        response_tx: &Sender<QueryResponse>,
+        events: &EventBus,
+    ) {
+        match command {
+            ConsoleCommand::Respawn => {
+                let response = self.respawn();
+                match self.send(itti, response).await {
+                    Ok(_) => {
+                        debug!("Sent respawn");
+                    }
+                    Err(e) => {
+                        error!("Failed to send respawn: {}", e);
+                    }
+                }
+            }
+            ConsoleCommand::Chat(message) => {
+                let response = self.chat_message(message.clone());
+                match self.send(itti, response).await {
+                    Ok(_) => {
+                        debug!("Sent chat message: {}", message);
+                    }
+                    Err(e) => {
+                        error!("Failed to send chat message: {}", e);
+                    }
+                }
+            }
+            ConsoleCommand::Command(command) => {
+                // validate with the command tree
+                if let Some(tree) = &self.commands {
+                    if let Err(e) = tree.validate(&command) {
+                        info!("Invalid command: {}: {}", command, e);
+                        self.display(vec![format!("{}: /{}", style(e).red(), command)], events);
+                        return;
+                    }
                 }
+                let response = self.chat_command(command.clone());
+                match self.send(itti, response).await {
+                    Ok(_) => {
+                        debug!("Sent chat command: {}", command);
+                    }
+                    Err(e) => {
+                        error!("Failed to send chat command: {}", e);
+                    }
+                }
+            }
+            ConsoleCommand::Dig { x, y, z, block } => {
+                self.start_dig(x, y, z, block, itti, events).await;
+            }
+            ConsoleCommand::CancelDig => {
+                self.cancel_dig(itti, events).await;
+            }
+            ConsoleCommand::Place { x, y, z, face } => {
+                if face > 5 {
+                    warn!("Invalid place face: {}", face);
+                    return;
+                }
+                self.place(x, y, z, face, itti).await;
+            }
+            ConsoleCommand::Attack(target) => {
+                self.attack(target, itti, events).await;
+            }
+            ConsoleCommand::Use(target) | ConsoleCommand::Mount(target) => {
+                self.use_entity(target, itti, events).await;
+            }
+            ConsoleCommand::Dismount => {
+                self.dismount(itti).await;
+            }
+            ConsoleCommand::Servers => {
+                self.bungeecord.list(Instant::now());
+                self.proxy_command(bungeecord::SERVER_COMMAND.to_string(), itti)
+                    .await;
+            }
+            ConsoleCommand::Switch(server) => {
+                self.switch_server(server, itti, events).await;
+            }
+            ConsoleCommand::Complete(text) => {
+                self.complete(text, itti, response_tx).await;
+            }
+            ConsoleCommand::Query(query) => {
+                // answers stay in the order of the requests
+                self.cancel_suggestion(response_tx).await;
+                match response_tx.send(self.query(query)).await {
+                    Ok(_) => {
+                        debug!("Sent {:?}", query);
+                    }
+                    Err(e) => {
+                        error!("Failed to send {:?}: {}", query, e);
+                    }
+                }
+            }
+            command => {
+                warn!("Unknown command: {:?}", command);
+            }
+        }
+    }
+    #[allow(unused_variables)]
+    #[allow(unused)]
+    pub fn respawn(&self) -> Vec<u8> {
+        respawn::new(self.compress)
+    }
+
+    #[allow(unused_variables)]
+    #[allow(unused)]
+    pub fn chat_message(&self, msg: String) -> Vec<u8> {
+        chat_message::new(msg, self.compress)
+    }
+
+    #[allow(unused_variables)]
+    #[allow(unused)]
+    pub fn chat_command(&self, command: String) -> Vec<u8> {
+        chat_command::new(command, self.compress)
+    }
+
+    // answer of a console query
+    pub fn query(&self, query: Query) -> QueryResponse {
+        match query {
+            Query::Position => {
+                QueryResponse::Position(self.position.map(|(x, y, z, yaw, pitch)| Position {
+                    x,
+                    y,
+                    z,
+                    yaw,
+                    pitch,
+                }))
+            }
+            Query::Server => QueryResponse::Server {
+                data: match (self.motor.clone(), self.icon.is_some(), self.enforce_chat) {
+                    (Some(motor), true, Some(enforce_chat)) => Some((motor, enforce_chat)),
+                    _ => None,
+                },
+                last_packet: self.last_packet.map(|instant| instant.elapsed()),
+                last_keep_alive: self.last_keep_alive.map(|instant| instant.elapsed()),
+                tab_list: self.get_tab_list(),
             },
-            "tps" => match response_tx.send(vec![self.get_tps()]).await {
-                Ok(_) => {
-                    debug!("Sent tps");
+            Query::Time => QueryResponse::Time(self.time),
+            Query::Tps => QueryResponse::Tps(self.tps.averages()),
+            Query::Exp => QueryResponse::Exp(match (self.exp_bar, self.level, self.exp_level) {
+                (Some(exp_bar), Some(level), Some(exp_level)) => Some((exp_bar, level, exp_level)),
+                _ => None,
+            }),
+            Query::Health => {
+                QueryResponse::Health(match (self.health, self.food, self.saturation) {
+                    (Some(health), Some(food), Some(saturation)) => {
+                        Some((health, food, saturation))
+                    }
+                    _ => None,
+                })
+            }
+            Query::Entities => QueryResponse::Entities(self.position.map(|(x, y, z, _, _)| {
+                self.entities
+                    .around(x, y, z, 10)
+                    .into_iter()
+                    .filter(|e| Some(e.id) != self.entity_id)
+                    .map(|e| (e.clone(), e.distance(x, y, z)))
+                    .collect()
+            })),
+            Query::BossBars => {
+                QueryResponse::BossBars(self.boss_bars.bars().into_iter().cloned().collect())
+            }
+            Query::Scoreboard => {
+                QueryResponse::Scoreboard(self.scoreboard.sidebar(&self.username).map(
+                    |(title, scores)| {
+                        let scores = scores
+                            .into_iter()
+                            .map(|(name, score)| Score {
+                                display: self.scoreboard.decorate(&name),
+                                name,
+                                score,
+                            })
+                            .collect();
+                        (title, scores)
+                    },
+                ))
+            }
+            Query::Players => QueryResponse::Players {
+                tab_list: self.get_tab_list(),
+                players: self
+                    .players
+                    .listed()
+                    .into_iter()
+                    .map(|player| ListedPlayer {
+                        name: player.name.clone(),
+                        display: match &player.display_name {
+                            Some(display_name) => display_name.clone(),
+                            None => self.scoreboard.decorate(&player.name),
+                        },
+                        gamemode: player.gamemode,
+                        latency: player.latency,
+                    })
+                    .collect(),
+            },
+            Query::Channels => QueryResponse::Channels {
+                registered: self.channels.names(),
+                server: self.channels.server(),
+                unknown: self.channels.unknown(),
+            },
+            Query::Latency => QueryResponse::Latency(self.latency.windows()),
+        }
+    }
+
+    // disconnected by the server
+    fn kick(&mut self, reason: String, events: &EventBus) {
+        events.publish(ClientEvent::Kick(reason.clone()));
+        self.disconnect = Some(reason);
+    }
+
+    // plain reason of the last disconnect: kick, time out or connect error
+    pub fn get_disconnect_reason(&self) -> Option<String> {
+        self.disconnect.clone()
+    }
+
+    // reached the play state in this connection
+    pub fn joined(&self) -> bool {
+        self.entity_id.is_some()
+    }
+
+    // tab list header and footer as plain text
+    pub fn get_tab_list(&self) -> Option<(String, String)> {
+        self.tab_list.clone()
+    }
+}
+
+//  resource pack
+impl Client {
+    pub fn set_resource_pack(&mut self, policy: Policy) {
+        self.resource_pack = policy;
+    }
+
+    async fn send_resource_pack_response<I: IttiInterface>(&self, result: i32, itti: &I) {
+        let response = resource_pack_response::new(result, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent resource pack response: {}", result);
+            }
+            Err(e) => {
+                error!("Failed to send resource pack response: {}", e);
+            }
+        }
+    }
+
+    async fn handle_resource_pack<I: IttiInterface>(
+        &mut self,
+        url: String,
+        hash: String,
+        forced: bool,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        match self.resource_pack.clone() {
+            Policy::Accept => {
+                self.send_resource_pack_response(resource_pack::ACCEPTED, itti)
+                    .await;
+                self.send_resource_pack_response(resource_pack::SUCCESSFULLY_LOADED, itti)
+                    .await;
+            }
+            Policy::Decline => {
+                if forced {
+                    warn!("Declined a required resource pack, the server may kick");
                 }
+                self.send_resource_pack_response(resource_pack::DECLINED, itti)
+                    .await;
+                self.display(
+                    vec![format!(
+                        "Resource pack {}: {}",
+                        style("declined").red(),
+                        url
+                    )],
+                    events,
+                );
+            }
+            Policy::Download(cache) => {
+                self.send_resource_pack_response(resource_pack::ACCEPTED, itti)
+                    .await;
+                let pack_tx = match &self.pack_tx {
+                    Some(pack_tx) => pack_tx.clone(),
+                    None => return,
+                };
+                // keep handling packets while downloading
+                tokio::spawn(async move {
+                    let result = resource_pack::download(&url, &hash, &cache).await;
+                    if pack_tx.send(result).await.is_err() {
+                        debug!("client already quit");
+                    }
+                });
+            }
+        }
+    }
+
+    async fn finish_resource_pack<I: IttiInterface>(
+        &mut self,
+        result: Result<PathBuf, String>,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        match result {
+            Ok(path) => {
+                info!("Resource pack loaded: {}", path.display());
+                self.send_resource_pack_response(resource_pack::SUCCESSFULLY_LOADED, itti)
+                    .await;
+                self.display(
+                    vec![format!(
+                        "Resource pack {}: {}",
+                        style("loaded").green(),
+                        path.display()
+                    )],
+                    events,
+                );
+            }
+            Err(e) => {
+                warn!("Resource pack failed: {}", e);
+                self.send_resource_pack_response(resource_pack::FAILED_DOWNLOAD, itti)
+                    .await;
+                self.display(vec![style(e).red().to_string()], events);
+            }
+        }
+    }
+}
+
+//  forge
+impl Client {
+    pub fn set_forge(&mut self, forge: Forge) {
+        self.forge = forge;
+    }
+
+    pub fn set_velocity(&mut self, velocity: Velocity) {
+        self.velocity = velocity;
+    }
+
+    pub fn set_forwarding(&mut self, forwarding: Forwarding) {
+        self.forwarding = forwarding;
+    }
+
+    async fn answer_login_plugin<I: IttiInterface>(&self, packet: Vec<u8>, itti: &I) {
+        let (id, channel, data) = login_plugin_request::parse(packet);
+        info!(
+            "Login plugin request: id: {}, channel: {}, data: {} bytes",
+            id,
+            channel,
+            data.len()
+        );
+        let response = match channel.as_str() {
+            forge::LOGIN_WRAPPER if self.forge.enabled() => match self.forge.respond(&data) {
+                Ok(reply) => login_plugin_response::new_with_data(id, reply, self.compress),
                 Err(e) => {
-                    error!("Failed to send tps: {}", e.to_string());
+                    warn!("Failed to answer forge handshake: {}", e);
+                    login_plugin_response::new(id, false, self.compress)
                 }
             },
-            "exp" => match response_tx.send(vec![self.get_exp()]).await {
+            velocity::CHANNEL if self.velocity.enabled() => login_plugin_response::new_with_data(
+                id,
+                self.velocity.respond(&self.username),
+                self.compress,
+            ),
+            _ => login_plugin_response::new(id, false, self.compress), // no check
+        };
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent login plugin response");
+            }
+            Err(e) => {
+                warn!("Failed to send login plugin response: {}", e);
+            }
+        }
+    }
+}
+
+//  bungeecord
+impl Client {
+    // current server in green
+    fn show_servers(&self, servers: &[String], events: &EventBus) {
+        let current = self.bungeecord.server().cloned().unwrap_or_default();
+        let names = servers
+            .iter()
+            .map(|s| match *s == current {
+                true => style(s).green().to_string(),
+                false => s.clone(),
+            })
+            .collect::<Vec<String>>();
+        self.display(vec![format!("Servers: {}", names.join(", "))], events);
+    }
+
+    async fn proxy_command<I: IttiInterface>(&self, command: String, itti: &I) {
+        let response = self.chat_command(command.clone());
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent proxy command: {}", command);
+            }
+            Err(e) => {
+                warn!("Failed to send proxy command: {}", e);
+            }
+        }
+    }
+
+    async fn switch_server<I: IttiInterface>(
+        &mut self,
+        server: String,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        // the server list is unknown before /servers
+        let servers = self.bungeecord.servers();
+        if !servers.is_empty() && !servers.contains(&server) {
+            self.display(
+                vec![format!("{}: {}", style("Unknown server").red(), server)],
+                events,
+            );
+            return;
+        }
+        self.display(
+            vec![format!("Switching to {}", style(&server).cyan())],
+            events,
+        );
+        self.proxy_command(format!("{} {}", bungeecord::SERVER_COMMAND, server), itti)
+            .await;
+    }
+}
+
+//  plugin channel
+impl Client {
+    pub fn register_channel(&mut self, channel: &str, handler: Handler) {
+        self.channels.register(channel, handler);
+    }
+
+    async fn register_channels<I: IttiInterface>(&self, itti: &I) {
+        if self.channels.names().is_empty() {
+            return;
+        }
+        let register = plugin_message::new_with_data(
+            channel::REGISTER.to_string(),
+            self.channels.register_data(),
+            self.compress,
+        );
+        match self.send(itti, register).await {
+            Ok(_) => {
+                debug!("Sent channel register: {:?}", self.channels.names());
+            }
+            Err(e) => {
+                warn!("Failed to send channel register: {}", e);
+            }
+        }
+    }
+}
+
+//  cookie
+impl Client {
+    fn store_cookie(&mut self, key: String, payload: Vec<u8>) {
+        if payload.len() > COOKIE_SIZE {
+            warn!("Cookie too large: {}, {} bytes", key, payload.len());
+            return;
+        }
+        debug!("Store cookie: {}, {} bytes", key, payload.len());
+        self.cookies.insert(key, payload);
+    }
+
+    async fn answer_cookie<I: IttiInterface>(&self, key: String, itti: &I) {
+        let payload = self.cookies.get(&key).cloned();
+        debug!("Cookie request: {}, known: {}", key, payload.is_some());
+        let response = cookie_response::new(key, payload, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent cookie response");
+            }
+            Err(e) => {
+                warn!("Failed to send cookie response: {}", e);
+            }
+        }
+    }
+}
+
+//  title
+impl Client {
+    fn set_action_bar(&mut self, text: String, events: &EventBus) {
+        debug!("Action bar: {}", text);
+        self.action_bar = Some((text, Instant::now() + ACTION_BAR_TIME));
+        self.show_status(events);
+    }
+
+    // the next title or action bar to fade out, while boss bars hold the status line
+    fn status_deadline(&self) -> Option<Instant> {
+        if self.boss_bars.bars().is_empty() {
+            return None;
+        }
+        let now = Instant::now();
+        [
+            self.title.as_ref().map(|(_, t)| *t),
+            self.action_bar.as_ref().map(|(_, t)| *t),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|t| *t > now)
+        .min()
+    }
+
+    // boss bars, title, subtitle and action bar beside the prompt,
+    // until they fade out or the boss bars are removed
+    fn show_status(&mut self, events: &EventBus) {
+        let now = Instant::now();
+        let mut parts = self
+            .boss_bars
+            .bars()
+            .iter()
+            .map(|bar| bar.render())
+            .collect::<Vec<String>>();
+        let mut until = now;
+        if let Some((title, t)) = self.title.as_ref().filter(|(_, t)| *t > now) {
+            parts.push(style(title).yellow().bold().to_string());
+            if let Some(subtitle) = &self.subtitle {
+                parts.push(subtitle.clone());
+            }
+            until = until.max(*t);
+        }
+        if let Some((action_bar, t)) = self.action_bar.as_ref().filter(|(_, t)| *t > now) {
+            parts.push(style(action_bar).green().to_string());
+            until = until.max(*t);
+        }
+        let duration = match self.boss_bars.bars().is_empty() {
+            true => until - now,
+            false => Duration::MAX,
+        };
+        events.publish(ClientEvent::StatusLine(parts.join(" | "), duration));
+    }
+}
+
+//  command
+impl Client {
+    fn suggestion_deadline(&self) -> Instant {
+        match self.suggestion {
+            Some((_, _, _, deadline)) => deadline,
+            None => Instant::now(),
+        }
+    }
+
+    // complete a command (without `/`), asking the server if the tree says so
+    async fn complete<I: IttiInterface>(
+        &mut self,
+        text: String,
+        itti: &I,
+        response_tx: &Sender<QueryResponse>,
+    ) {
+        // one answer per request, in order, the one in flight is cancelled
+        self.cancel_suggestion(response_tx).await;
+        let (local, ask_server) = match &self.commands {
+            Some(tree) => tree.complete(&text),
+            None => (vec![], true),
+        };
+        if ask_server {
+            self.suggestion_id += 1;
+            let request = command_suggestions_request::new(
+                self.suggestion_id,
+                format!("/{}", text),
+                self.compress,
+            );
+            match self.send(itti, request).await {
                 Ok(_) => {
-                    debug!("Sent exp");
+                    debug!("Sent command suggestions request: {}", text);
+                    let deadline = Instant::now() + command_tree::SUGGESTION_TIMEOUT;
+                    self.suggestion = Some((self.suggestion_id, text, local, deadline));
+                    return;
                 }
                 Err(e) => {
-                    error!("Failed to send exp: {}", e.to_string());
+                    warn!("Failed to send command suggestions request: {}", e);
                 }
-            },
-            "health" => match response_tx.send(vec![self.get_health()]).await {
+            }
+        }
+        self.suggestions = Some(local);
+        self.flush_suggestions(response_tx).await;
+    }
+
+    // answer the request in flight with the local candidates, its late response is ignored
+    async fn cancel_suggestion(&mut self, response_tx: &Sender<QueryResponse>) {
+        if let Some((id, _, local, _)) = self.suggestion.take() {
+            debug!("Cancel command suggestions: {}", id);
+            self.suggestions = Some(local);
+            self.flush_suggestions(response_tx).await;
+        }
+    }
+
+    async fn flush_suggestions(&mut self, response_tx: &Sender<QueryResponse>) {
+        if let Some(suggestions) = self.suggestions.take() {
+            match response_tx
+                .send(QueryResponse::Completions(suggestions))
+                .await
+            {
                 Ok(_) => {
-                    debug!("Sent health");
+                    debug!("Sent command suggestions");
                 }
                 Err(e) => {
-                    error!("Failed to send health: {}", e.to_string());
+                    error!("Failed to send command suggestions: {}", e);
                 }
-            },
-            _ => {
-                warn!("Unknown command: {}", packet[0]);
             }
         }
     }
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn get_position(&self) -> String {
-        match self.position {
-            Some((x, y, z, yaw, pitch)) => {
-                format!(
-                    "x: {}, y: {}, z: {}, yaw: {}, pitch: {}",
-                    style(x).green(),
-                    style(y).green(),
-                    style(z).green(),
-                    style(yaw).cyan(),
-                    style(pitch).cyan()
-                )
+}
+
+//  block
+impl Client {
+    fn dig_deadline(&self) -> Instant {
+        match self.digging {
+            Some((_, _, _, deadline)) => deadline,
+            None => Instant::now(),
+        }
+    }
+
+    fn next_sequence(&mut self) -> i32 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    async fn start_dig<I: IttiInterface>(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        block: String,
+        itti: &I,
+        events: &EventBus,
+    ) {
+        // cancel last
+        if self.digging.is_some() {
+            self.cancel_dig(itti, events).await;
+        }
+        if !dig::is_known_block(&block) {
+            warn!(
+                "Unknown block: {}, estimate as {}",
+                block,
+                dig::DEFAULT_BLOCK
+            );
+        }
+        let tool = self.inventory.held();
+        let time = match dig::estimate(&block, &tool) {
+            Some(time) => time,
+            None => {
+                info!("Block {} at ({}, {}, {}) is unbreakable", block, x, y, z);
+                self.display(
+                    vec![format!(
+                        "{} is {}",
+                        style(block).yellow(),
+                        style("unbreakable").red()
+                    )],
+                    events,
+                );
+                return;
+            }
+        };
+
+        // start
+        let sequence = self.next_sequence();
+        let response = player_action::new(0, x, y, z, 1, sequence, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!(
+                    "Sent start digging: ({}, {}, {}), sequence: {}",
+                    x, y, z, sequence
+                );
+            }
+            Err(e) => {
+                error!("Failed to send start digging: {}", e);
+                return;
+            }
+        }
+        match self.send(itti, swing_arm::new(self.compress)).await {
+            Ok(_) => {
+                debug!("Sent swing arm");
+            }
+            Err(e) => {
+                warn!("Failed to send swing arm: {}", e);
             }
-            _ => style("No position").red().to_string(),
         }
+        info!(
+            "Digging {} at ({}, {}, {}) with {}, estimated: {:?}",
+            block, x, y, z, tool, time
+        );
+
+        // instant break
+        if time.is_zero() {
+            self.predictions.predict(sequence, (x, y, z), true);
+            self.display(
+                vec![format!(
+                    "Dig ({}, {}, {}) {}",
+                    x,
+                    y,
+                    z,
+                    style("finished").green()
+                )],
+                events,
+            );
+            return;
+        }
+        self.digging = Some((x, y, z, Instant::now() + time));
+        self.display(
+            vec![format!(
+                "Digging {} at ({}, {}, {}), estimated: {}",
+                style(block).yellow(),
+                x,
+                y,
+                z,
+                style(format!("{:.2}s", time.as_secs_f32())).cyan()
+            )],
+            events,
+        );
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn get_server_data(&self) -> String {
-        match (self.motor.clone(), self.icon.clone(), self.enforce_chat) {
-            (Some(motor), Some(icon), Some(enforce_chat)) => {
-                format!(
-                    "motor: {}, enforce chat: {}",
-                    style(motor).white(),
-                    style(enforce_chat).red()
-                )
+    async fn finish_dig<I: IttiInterface>(&mut self, itti: &I, events: &EventBus) {
+        let (x, y, z, _) = match self.digging.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let sequence = self.next_sequence();
+        let response = player_action::new(2, x, y, z, 1, sequence, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!(
+                    "Sent finish digging: ({}, {}, {}), sequence: {}",
+                    x, y, z, sequence
+                );
+                self.predictions.predict(sequence, (x, y, z), true);
+            }
+            Err(e) => {
+                error!("Failed to send finish digging: {}", e);
+                return;
+            }
+        }
+        match self.send(itti, swing_arm::new(self.compress)).await {
+            Ok(_) => {
+                debug!("Sent swing arm");
+            }
+            Err(e) => {
+                warn!("Failed to send swing arm: {}", e);
             }
-            _ => style("No server data").red().to_string(),
         }
+        info!("Dig ({}, {}, {}) finished", x, y, z);
+        self.display(
+            vec![format!(
+                "Dig ({}, {}, {}) {}",
+                x,
+                y,
+                z,
+                style("finished").green()
+            )],
+            events,
+        );
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn respawn(&self) -> Vec<u8> {
-        respawn::new(self.compress)
+    async fn cancel_dig<I: IttiInterface>(&mut self, itti: &I, events: &EventBus) {
+        let (x, y, z, _) = match self.digging.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let sequence = self.next_sequence();
+        let response = player_action::new(1, x, y, z, 1, sequence, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!(
+                    "Sent cancel digging: ({}, {}, {}), sequence: {}",
+                    x, y, z, sequence
+                );
+            }
+            Err(e) => {
+                error!("Failed to send cancel digging: {}", e);
+                return;
+            }
+        }
+        info!("Dig ({}, {}, {}) cancelled", x, y, z);
+        self.display(
+            vec![format!(
+                "Dig ({}, {}, {}) {}",
+                x,
+                y,
+                z,
+                style("cancelled").yellow()
+            )],
+            events,
+        );
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn chat_message(&self, msg: String) -> Vec<u8> {
-        chat_message::new(msg, self.compress)
+    async fn place<I: IttiInterface>(&mut self, x: i32, y: i32, z: i32, face: u8, itti: &I) {
+        // cursor at the center of the clicked face
+        let cursor = match face {
+            0 => (0.5, 0.0, 0.5),
+            1 => (0.5, 1.0, 0.5),
+            2 => (0.5, 0.5, 0.0),
+            3 => (0.5, 0.5, 1.0),
+            4 => (0.0, 0.5, 0.5),
+            _ => (1.0, 0.5, 0.5),
+        };
+        let sequence = self.next_sequence();
+        let response = use_item_on::new(x, y, z, face, cursor, sequence, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!(
+                    "Sent use item on: ({}, {}, {}), face: {}, sequence: {}",
+                    x, y, z, face, sequence
+                );
+                // the block is placed against the face
+                let (dx, dy, dz) = match face {
+                    0 => (0, -1, 0),
+                    1 => (0, 1, 0),
+                    2 => (0, 0, -1),
+                    3 => (0, 0, 1),
+                    4 => (-1, 0, 0),
+                    _ => (1, 0, 0),
+                };
+                self.predictions
+                    .predict(sequence, (x + dx, y + dy, z + dz), false);
+            }
+            Err(e) => {
+                error!("Failed to send use item on: {}", e);
+                return;
+            }
+        }
+        match self.send(itti, swing_arm::new(self.compress)).await {
+            Ok(_) => {
+                debug!("Sent swing arm");
+            }
+            Err(e) => {
+                warn!("Failed to send swing arm: {}", e);
+            }
+        }
+        info!("Place on ({}, {}, {}), face: {}", x, y, z, face);
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn chat_command(&self, command: String) -> Vec<u8> {
-        chat_command::new(command, self.compress)
+    // block changes acknowledged by the server, rolled back if a block update undid them
+    fn settle(&mut self, sequence: i32, events: &EventBus) {
+        for prediction in self.predictions.acknowledge(sequence) {
+            let (x, y, z) = prediction.position;
+            let action = match prediction.air {
+                true => "Dig",
+                false => "Place",
+            };
+            let result = match prediction.confirmed() {
+                true => style("confirmed").green(),
+                false => style("rolled back").red(),
+            };
+            info!(
+                "{} ({}, {}, {}) {}, sequence: {}",
+                action, x, y, z, result, prediction.sequence
+            );
+            self.display(
+                vec![format!("{} ({}, {}, {}) {}", action, x, y, z, result)],
+                events,
+            );
+        }
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn get_time(&self) -> String {
-        match self.time {
-            Some((word_age, time_of_day, day)) => {
-                format!(
-                    "word age: {}, day: {}, time of this day: {}",
-                    style(word_age).white(),
-                    style(day).cyan(),
-                    style(time_of_day).green()
-                )
+    fn display(&self, msg: Vec<String>, events: &EventBus) {
+        events.publish(ClientEvent::Display(msg));
+    }
+}
+
+//  entity
+impl Client {
+    // entity id or the nearest entity of a type, within reach
+    fn target(&self, target: &str) -> Result<Entity, String> {
+        let (x, y, z, _, _) = match self.position {
+            Some(position) => position,
+            None => return Err("No position".to_string()),
+        };
+        let entity = match target.parse::<i32>() {
+            Ok(id) => self.entities.get(id),
+            Err(_) => self.entities.nearest(Some(target), x, y, z),
+        };
+        match entity {
+            Some(entity) if Some(entity.id) == self.entity_id => {
+                Err("Can not target yourself".to_string())
             }
-            _ => style("No time").red().to_string(),
+            Some(entity) if entity.distance(x, y, z) > entity::REACH => Err(format!(
+                "{}({}) is out of reach: {:.2}",
+                entity.kind,
+                entity.id,
+                entity.distance(x, y, z)
+            )),
+            Some(entity) => Ok(entity.clone()),
+            None => Err(format!("No entity: {}", target)),
         }
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn get_tps(&self) -> String {
-        match self.tps {
-            Some(tps) => format!("TPS: {:.2}", style(tps).green()),
-            _ => style("No TPS").red().to_string(),
+    async fn attack<I: IttiInterface>(&mut self, target: String, itti: &I, events: &EventBus) {
+        // cooldown
+        if let Some(last_attack) = self.last_attack {
+            if last_attack.elapsed() < entity::ATTACK_COOLDOWN {
+                debug!("Attack is cooling down");
+                self.display(
+                    vec![style("Attack is cooling down").yellow().to_string()],
+                    events,
+                );
+                return;
+            }
+        }
+        let entity = match self.target(&target) {
+            Ok(entity) => entity,
+            Err(e) => {
+                info!("Attack failed: {}", e);
+                self.display(vec![style(e).red().to_string()], events);
+                return;
+            }
+        };
+        let response = interact::new(entity.id, 1, None, false, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent attack: {}", entity.id);
+            }
+            Err(e) => {
+                error!("Failed to send attack: {}", e);
+                return;
+            }
+        }
+        match self.send(itti, swing_arm::new(self.compress)).await {
+            Ok(_) => {
+                debug!("Sent swing arm");
+            }
+            Err(e) => {
+                warn!("Failed to send swing arm: {}", e);
+            }
         }
+        self.last_attack = Some(Instant::now());
+        info!("Attack {}({})", entity.kind, entity.id);
+        self.display(
+            vec![format!(
+                "Attack {}({})",
+                style(entity.kind).yellow(),
+                entity.id
+            )],
+            events,
+        );
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn get_exp(&self) -> String {
-        match (self.exp_bar, self.level, self.exp_level) {
-            (Some(exp_bar), Some(level), Some(exp_level)) => {
-                format!(
-                    "exp bar: {:.2}, level: {}, exp: {}",
-                    style(exp_bar).white(),
-                    style(level).green(),
-                    style(exp_level).white()
-                )
+    // right click, also used to mount
+    async fn use_entity<I: IttiInterface>(&mut self, target: String, itti: &I, events: &EventBus) {
+        let entity = match self.target(&target) {
+            Ok(entity) => entity,
+            Err(e) => {
+                info!("Use failed: {}", e);
+                self.display(vec![style(e).red().to_string()], events);
+                return;
+            }
+        };
+        // interact at, then interact
+        let response = interact::new(entity.id, 2, Some((0.0, 0.5, 0.0)), false, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent interact at: {}", entity.id);
+            }
+            Err(e) => {
+                error!("Failed to send interact at: {}", e);
+                return;
+            }
+        }
+        let response = interact::new(entity.id, 0, None, false, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent interact: {}", entity.id);
+            }
+            Err(e) => {
+                error!("Failed to send interact: {}", e);
+                return;
             }
-            _ => style("No exp").red().to_string(),
         }
+        info!("Use {}({})", entity.kind, entity.id);
+        self.display(
+            vec![format!(
+                "Use {}({})",
+                style(entity.kind).yellow(),
+                entity.id
+            )],
+            events,
+        );
     }
 
-    #[allow(unused_variables)]
-    #[allow(unused)]
-    pub fn get_health(&self) -> String {
-        match (self.health, self.food, self.saturation) {
-            (Some(health), Some(food), Some(saturation)) => {
-                format!(
-                    "health: {}, food: {}, saturation: {}",
-                    style(health).red(),
-                    style(food).green(),
-                    style(saturation).yellow()
-                )
-            }
-            _ => style("No health").red().to_string(),
+    async fn dismount<I: IttiInterface>(&mut self, itti: &I) {
+        // the server stops riding on its next tick and releases the sneak itself
+        let response = player_input::new(0.0, 0.0, 0x02, self.compress);
+        match self.send(itti, response).await {
+            Ok(_) => {
+                debug!("Sent player input: unmount");
+            }
+            Err(e) => {
+                error!("Failed to send player input: {}", e);
+                return;
+            }
+        }
+        info!("Dismount");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event::ClientEvent;
+    use crate::core::scoreboard;
+    use crate::itti::basis::ITTI;
+    use crate::itti::memory;
+    use crate::util::transfer_var::uint2var_int;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+    use tokio::sync::broadcast;
+    use tokio::task::JoinHandle;
+
+    // scripted server end of the memory stream
+    struct Server {
+        stream: DuplexStream,
+        compress: bool,
+    }
+
+    impl Server {
+        async fn write(&mut self, id: u8, payload: &[u8]) {
+            let mut data = match self.compress {
+                // below the threshold, not compressed
+                true => vec![0x00, id],
+                false => vec![id],
+            };
+            data.extend_from_slice(payload);
+            let frame = [uint2var_int(vec![data.len() as i32]), data].concat();
+            self.stream.write_all(&frame).await.unwrap();
+        }
+
+        async fn read(&mut self) -> (u8, Vec<u8>) {
+            let mut len = 0;
+            for i in 0..3 {
+                let b = self.stream.read_u8().await.unwrap();
+                len |= ((b & 0x7F) as usize) << (7 * i);
+                if b & 0x80 == 0 {
+                    break;
+                }
+            }
+            let mut data = vec![0; len];
+            self.stream.read_exact(&mut data).await.unwrap();
+            if self.compress {
+                assert_eq!(data.remove(0), 0x00);
+            }
+            (data[0], data[1..].to_vec())
+        }
+
+        // skips other packets
+        async fn expect(&mut self, id: u8) -> Vec<u8> {
+            loop {
+                let (packet_id, payload) = self.read().await;
+                if packet_id == id {
+                    return payload;
+                }
+            }
+        }
+    }
+
+    fn string(s: &str) -> Vec<u8> {
+        [vec![s.len() as u8], s.as_bytes().to_vec()].concat()
+    }
+
+    async fn next_event(
+        events: &mut broadcast::Receiver<ClientEvent>,
+        f: impl Fn(&ClientEvent) -> bool,
+    ) -> ClientEvent {
+        loop {
+            let event = events.recv().await.unwrap();
+            if f(&event) {
+                return event;
+            }
+        }
+    }
+
+    // handshake, login start, login success and login (play) of 763
+    async fn join(server: &mut Server) {
+        server.expect(0x00).await;
+        server.expect(0x00).await;
+        let login_success = [vec![0x01; 16], string("Steve"), vec![0x00]].concat();
+        server.write(0x02, &login_success).await;
+        server.write(0x28, &[0x00, 0x00, 0x01, 0x5E, 0x00]).await;
+    }
+
+    // client of 763 on a memory stream, the other ends for the test
+    struct Harness {
+        server: Server,
+        events: broadcast::Receiver<ClientEvent>,
+        command_tx: mpsc::Sender<ConsoleCommand>,
+        response_rx: mpsc::Receiver<QueryResponse>,
+        task: JoinHandle<(Client, Exit)>,
+    }
+
+    fn start() -> Harness {
+        let (mut itti, stream) = memory::duplex(4096);
+        itti.set_address("mc.example.com".to_string(), 25565);
+        let bus = EventBus::new();
+        let events = bus.subscribe();
+        let (command_tx, mut command_rx) = mpsc::channel(8);
+        let (response_tx, response_rx) = mpsc::channel(8);
+        let mut client = Client::new("Steve".to_string(), 763, "en_us".to_string());
+        let task = tokio::spawn(async move {
+            let exit = client
+                .start(&mut itti, &mut command_rx, &response_tx, &bus)
+                .await;
+            (client, exit)
+        });
+        Harness {
+            server: Server {
+                stream,
+                compress: false,
+            },
+            events,
+            command_tx,
+            response_rx,
+            task,
+        }
+    }
+
+    async fn login_play_chat_kick(compress: bool) {
+        let Harness {
+            mut server,
+            mut events,
+            command_tx,
+            task,
+            ..
+        } = start();
+
+        // handshake: 763, mc.example.com, 25565, login
+        let handshake = server.expect(0x00).await;
+        assert_eq!(
+            handshake,
+            [
+                vec![0xFB, 0x05],
+                string("mc.example.com"),
+                vec![0x63, 0xDD, 0x02]
+            ]
+            .concat()
+        );
+        let login_start = server.expect(0x00).await;
+        assert_eq!(login_start[..6], string("Steve"));
+
+        if compress {
+            server.write(0x03, &[0x80, 0x02]).await;
+            server.compress = true;
+        }
+        // login success, play
+        let login_success = [vec![0x01; 16], string("Steve"), vec![0x00]].concat();
+        server.write(0x02, &login_success).await;
+        server.write(0x28, &[0x00, 0x00, 0x01, 0x5E, 0x00]).await;
+        next_event(&mut events, |e| {
+            *e == ClientEvent::Connection(ConnectionState::Joined)
+        })
+        .await;
+
+        // keep alive
+        let id = [0x00, 0x00, 0x00, 0x00, 0x07, 0x1B, 0x44, 0xF3];
+        server.write(0x23, &id).await;
+        assert_eq!(server.expect(0x12).await, id);
+
+        // chat both ways
+        server
+            .write(0x64, &[string("{\"text\":\"hello\"}"), vec![0x00]].concat())
+            .await;
+        let chat = next_event(&mut events, |e| matches!(e, ClientEvent::Chat { .. })).await;
+        assert_eq!(
+            chat,
+            ClientEvent::Chat {
+                sender: None,
+                message: "hello".to_string(),
+                line: "hello".to_string(),
+            }
+        );
+        command_tx
+            .send(ConsoleCommand::Chat("hi".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(server.expect(0x05).await[..3], string("hi"));
+
+        // kick
+        server.write(0x1A, &string("{\"text\":\"Kicked\"}")).await;
+        let kick = next_event(&mut events, |e| matches!(e, ClientEvent::Kick(_))).await;
+        assert_eq!(kick, ClientEvent::Kick("Kicked".to_string()));
+        drop(server);
+        let (client, exit) = task.await.unwrap();
+        assert_eq!(exit, Exit::Disconnected);
+        assert!(client.joined());
+        assert_eq!(client.get_disconnect_reason(), Some("Kicked".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_login_play_chat_kick() {
+        login_play_chat_kick(false).await;
+    }
+
+    #[tokio::test]
+    async fn test_login_play_chat_kick_compressed() {
+        login_play_chat_kick(true).await;
+    }
+
+    #[tokio::test]
+    async fn test_quit() {
+        let Harness {
+            mut server,
+            command_tx,
+            task,
+            ..
+        } = start();
+        server.expect(0x00).await;
+        command_tx.send(ConsoleCommand::Quit).await.unwrap();
+        assert_eq!(task.await.unwrap().1, Exit::Quit);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_completions() {
+        let Harness {
+            mut server,
+            mut events,
+            command_tx,
+            mut response_rx,
+            task,
+        } = start();
+        join(&mut server).await;
+        next_event(&mut events, |e| {
+            *e == ClientEvent::Connection(ConnectionState::Joined)
+        })
+        .await;
+
+        // no command tree, both ask the server
+        for text in ["tp ", "tp S"] {
+            command_tx
+                .send(ConsoleCommand::Complete(text.to_string()))
+                .await
+                .unwrap();
         }
+        assert_eq!(server.expect(0x09).await[..1], [0x01]);
+        assert_eq!(server.expect(0x09).await[..1], [0x02]);
+        // the first is cancelled by the second
+        assert_eq!(
+            response_rx.recv().await,
+            Some(QueryResponse::Completions(vec![]))
+        );
+
+        // the late response of the first is ignored
+        let matches = |id: u8, length: u8| {
+            [vec![id, 0x04, length, 0x01], string("Steve"), vec![0x00]].concat()
+        };
+        server.write(0x0F, &matches(0x01, 0x00)).await;
+        server.write(0x0F, &matches(0x02, 0x01)).await;
+        assert_eq!(
+            response_rx.recv().await,
+            Some(QueryResponse::Completions(vec!["tp Steve".to_string()]))
+        );
+        assert!(response_rx.try_recv().is_err());
+
+        command_tx.send(ConsoleCommand::Quit).await.unwrap();
+        assert_eq!(task.await.unwrap().1, Exit::Quit);
+    }
+
+    #[tokio::test]
+    async fn test_connect_failed() {
+        // a port nobody listens on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut itti = ITTI::new("127.0.0.1".to_string(), port.to_string(), 1024, 1024);
+        let (_command_tx, mut command_rx) = mpsc::channel(8);
+        let (response_tx, _response_rx) = mpsc::channel(8);
+        let mut client = Client::new("Steve".to_string(), 763, "en_us".to_string());
+        let exit = client
+            .start(&mut itti, &mut command_rx, &response_tx, &EventBus::new())
+            .await;
+        assert_eq!(exit, Exit::Disconnected);
+        assert!(!client.joined());
+        assert!(client.get_disconnect_reason().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_held_tool_and_place() {
+        let Harness {
+            mut server,
+            mut events,
+            command_tx,
+            task,
+            ..
+        } = start();
+        join(&mut server).await;
+
+        // pickaxes 802, 807, ..., a wooden pickaxe in the held slot 2
+        let tags = [
+            vec![0x01],
+            string("minecraft:item"),
+            vec![0x01],
+            string("minecraft:pickaxes"),
+            vec![0x06],
+            [802, 807, 812, 817, 822, 827]
+                .iter()
+                .flat_map(|id| uint2var_int(vec![*id]))
+                .collect(),
+        ]
+        .concat();
+        server.write(0x6E, &tags).await;
+        let slot = [
+            vec![0x00, 0x01, 0x00, 38, 0x01],
+            uint2var_int(vec![802]),
+            vec![0x01, 0x00],
+        ];
+        server.write(0x14, &slot.concat()).await;
+        server.write(0x4D, &[0x02]).await;
+        // handled once the keep alive is answered
+        server.write(0x23, &[0x00; 8]).await;
+        server.expect(0x12).await;
+        command_tx
+            .send(ConsoleCommand::Dig {
+                x: 0,
+                y: 64,
+                z: 0,
+                block: "stone".to_string(),
+            })
+            .await
+            .unwrap();
+        let digging = next_event(&mut events, |e| matches!(e, ClientEvent::Display(_))).await;
+        assert_eq!(
+            digging,
+            ClientEvent::Display(vec![format!(
+                "Digging {} at (0, 64, 0), estimated: {}",
+                style("stone").yellow(),
+                style("1.15s").cyan()
+            )])
+        );
+        command_tx.send(ConsoleCommand::CancelDig).await.unwrap();
+        let cancelled = next_event(&mut events, |e| matches!(e, ClientEvent::Display(_))).await;
+        assert_eq!(
+            cancelled,
+            ClientEvent::Display(vec![format!(
+                "Dig (0, 64, 0) {}",
+                style("cancelled").yellow()
+            )])
+        );
+
+        // placed on the east face, the server sets air back
+        command_tx
+            .send(ConsoleCommand::Place {
+                x: 1,
+                y: 64,
+                z: -2,
+                face: 5,
+            })
+            .await
+            .unwrap();
+        let use_item_on = server.expect(0x31).await;
+        assert_eq!(use_item_on[9], 0x05);
+        let sequence = *use_item_on.last().unwrap();
+        let position = util::transfer_var::position2long(2, 64, -2);
+        server
+            .write(
+                0x0A,
+                &[position.to_be_bytes().to_vec(), vec![0x00]].concat(),
+            )
+            .await;
+        server.write(0x06, &[sequence]).await;
+        // a frame of three bytes waits for more data
+        server.write(0x23, &[0x00; 8]).await;
+        let settled = next_event(&mut events, |e| matches!(e, ClientEvent::Display(_))).await;
+        assert_eq!(
+            settled,
+            ClientEvent::Display(vec![format!(
+                "Place (2, 64, -2) {}",
+                style("rolled back").red()
+            )])
+        );
+
+        command_tx.send(ConsoleCommand::Quit).await.unwrap();
+        assert_eq!(task.await.unwrap().1, Exit::Quit);
+    }
+
+    #[tokio::test]
+    async fn test_chat_team_prefix() {
+        let Harness {
+            mut server,
+            mut events,
+            command_tx,
+            task,
+            ..
+        } = start();
+        join(&mut server).await;
+
+        // team "red" with prefix "[R] " and member Al
+        let team = [
+            string("red"),
+            vec![0x00],
+            string("{\"text\":\"\"}"),
+            vec![0x00],
+            string("always"),
+            string("always"),
+            vec![0x0c],
+            string("{\"text\":\"[R] \"}"),
+            string("{\"text\":\"\"}"),
+            vec![0x01],
+            string("Al"),
+        ]
+        .concat();
+        server.write(0x5A, &team).await;
+
+        // player chat: Al says "hi", unsigned, chat type 0
+        let chat = [
+            vec![0x11; 16],
+            vec![0x00, 0x00],
+            string("hi"),
+            vec![0x00; 16],
+            vec![0x00, 0x00, 0x00, 0x00],
+            string("{\"text\":\"Al\"}"),
+            vec![0x00],
+        ]
+        .concat();
+        server.write(0x35, &chat).await;
+        let chat = next_event(&mut events, |e| matches!(e, ClientEvent::Chat { .. })).await;
+        assert_eq!(
+            chat,
+            ClientEvent::Chat {
+                sender: Some("Al".to_string()),
+                message: "hi".to_string(),
+                line: format!("<[R] {}> hi", scoreboard::paint("Al", 0x0c)),
+            }
+        );
+
+        // system chat of a chat plugin
+        server
+            .write(
+                0x64,
+                &[string("{\"text\":\"<Al> yo\"}"), vec![0x00]].concat(),
+            )
+            .await;
+        let chat = next_event(&mut events, |e| matches!(e, ClientEvent::Chat { .. })).await;
+        assert_eq!(
+            chat,
+            ClientEvent::Chat {
+                sender: None,
+                message: "<Al> yo".to_string(),
+                line: format!("<[R] {}> yo", scoreboard::paint("Al", 0x0c)),
+            }
+        );
+
+        command_tx.send(ConsoleCommand::Quit).await.unwrap();
+        assert_eq!(task.await.unwrap().1, Exit::Quit);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_servers() {
+        let Harness {
+            mut server,
+            mut events,
+            command_tx,
+            task,
+            ..
+        } = start();
+        join(&mut server).await;
+
+        // /servers runs the proxy command and reads its output
+        command_tx.send(ConsoleCommand::Servers).await.unwrap();
+        assert_eq!(server.expect(0x04).await[..7], string("server"));
+        for line in [
+            "You are currently connected to lobby.",
+            "Available servers: lobby, pvp",
+        ] {
+            let json = format!("{{\"text\":\"{}\"}}", line);
+            server
+                .write(0x64, &[string(&json), vec![0x00]].concat())
+                .await;
+        }
+        let servers = next_event(&mut events, |e| matches!(e, ClientEvent::Display(_))).await;
+        assert_eq!(
+            servers,
+            ClientEvent::Display(vec![format!("Servers: {}, pvp", style("lobby").green())])
+        );
+
+        // /switch runs /server <name>, unknown servers are refused
+        command_tx
+            .send(ConsoleCommand::Switch("skyblock".to_string()))
+            .await
+            .unwrap();
+        command_tx
+            .send(ConsoleCommand::Switch("pvp".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(server.expect(0x04).await[..11], string("server pvp"));
+
+        command_tx.send(ConsoleCommand::Quit).await.unwrap();
+        assert_eq!(task.await.unwrap().1, Exit::Quit);
+    }
+
+    #[tokio::test]
+    async fn test_boss_bar_status_line() {
+        let Harness {
+            mut server,
+            mut events,
+            command_tx,
+            task,
+            ..
+        } = start();
+        join(&mut server).await;
+
+        // add "Event" at full health, then remove it
+        let add = [
+            vec![0x12; 16],
+            vec![0x00],
+            string("{\"text\":\"Event\"}"),
+            vec![0x3f, 0x80, 0x00, 0x00, 0x02, 0x00, 0x00],
+        ]
+        .concat();
+        server.write(0x0B, &add).await;
+        let status = next_event(&mut events, |e| matches!(e, ClientEvent::StatusLine(..))).await;
+        match status {
+            ClientEvent::StatusLine(text, duration) => {
+                assert!(text.contains("100% Event"));
+                assert_eq!(duration, Duration::MAX);
+            }
+            _ => unreachable!(),
+        }
+        server
+            .write(0x0B, &[vec![0x12; 16], vec![0x01]].concat())
+            .await;
+        let status = next_event(&mut events, |e| matches!(e, ClientEvent::StatusLine(..))).await;
+        assert_eq!(
+            status,
+            ClientEvent::StatusLine(String::new(), Duration::ZERO)
+        );
+
+        command_tx.send(ConsoleCommand::Quit).await.unwrap();
+        assert_eq!(task.await.unwrap().1, Exit::Quit);
     }
 }
@@ -0,0 +1,479 @@
+use crate::core::version::{V1_20_3, V1_20_5};
+use tokio::time::Duration;
+
+// command argument parser registry (1.20.1 and 1.20.2)
+const PARSERS: [&str; 49] = [
+    "brigadier:bool",
+    "brigadier:float",
+    "brigadier:double",
+    "brigadier:integer",
+    "brigadier:long",
+    "brigadier:string",
+    "minecraft:entity",
+    "minecraft:game_profile",
+    "minecraft:block_pos",
+    "minecraft:column_pos",
+    "minecraft:vec3",
+    "minecraft:vec2",
+    "minecraft:block_state",
+    "minecraft:block_predicate",
+    "minecraft:item_stack",
+    "minecraft:item_predicate",
+    "minecraft:color",
+    "minecraft:component",
+    "minecraft:message",
+    "minecraft:nbt_compound_tag",
+    "minecraft:nbt_tag",
+    "minecraft:nbt_path",
+    "minecraft:objective",
+    "minecraft:objective_criteria",
+    "minecraft:operation",
+    "minecraft:particle",
+    "minecraft:angle",
+    "minecraft:rotation",
+    "minecraft:scoreboard_slot",
+    "minecraft:score_holder",
+    "minecraft:swizzle",
+    "minecraft:team",
+    "minecraft:item_slot",
+    "minecraft:resource_location",
+    "minecraft:function",
+    "minecraft:entity_anchor",
+    "minecraft:int_range",
+    "minecraft:float_range",
+    "minecraft:dimension",
+    "minecraft:gamemode",
+    "minecraft:time",
+    "minecraft:resource_or_tag",
+    "minecraft:resource_or_tag_key",
+    "minecraft:resource",
+    "minecraft:resource_key",
+    "minecraft:template_mirror",
+    "minecraft:template_rotation",
+    "minecraft:heightmap",
+    "minecraft:uuid",
+];
+
+pub const ASK_SERVER: &str = "minecraft:ask_server";
+// wait for server suggestions before falling back to local ones
+pub const SUGGESTION_TIMEOUT: Duration = Duration::from_millis(500);
+
+// added by later versions: since, name, inserted after
+const ADDED_PARSERS: [(i32, &str, &str); 5] = [
+    (V1_20_3, "minecraft:style", "minecraft:component"),
+    (V1_20_5, "minecraft:item_slots", "minecraft:item_slot"),
+    (V1_20_5, "minecraft:loot_table", "minecraft:heightmap"),
+    (V1_20_5, "minecraft:loot_predicate", "minecraft:loot_table"),
+    (
+        V1_20_5,
+        "minecraft:loot_modifier",
+        "minecraft:loot_predicate",
+    ),
+];
+
+// parser registry of the protocol version
+fn parsers(protocol: i32) -> Vec<&'static str> {
+    let mut parsers = PARSERS.to_vec();
+    for (since, name, after) in ADDED_PARSERS {
+        if protocol < since {
+            continue;
+        }
+        if let Some(index) = parsers.iter().position(|p| *p == after) {
+            parsers.insert(index + 1, name);
+        }
+    }
+    parsers
+}
+
+// None for an id outside the registry
+pub fn parser_name(protocol: i32, id: i32) -> Option<String> {
+    parsers(protocol)
+        .get(usize::try_from(id).ok()?)
+        .map(|name| name.to_string())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Parser {
+    Bool,
+    Float(Option<f32>, Option<f32>),
+    Double(Option<f64>, Option<f64>),
+    Integer(Option<i32>, Option<i32>),
+    Long(Option<i64>, Option<i64>),
+    // 0: single word, 1: quotable phrase, 2: greedy phrase
+    String(i32),
+    // flags: 0x01 single, 0x02 players only
+    Entity(u8),
+    // flags: 0x01 multiple
+    ScoreHolder(u8),
+    // minimum ticks
+    Time(i32),
+    // parser name, registry
+    Resource(String, String),
+    Other(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NodeKind {
+    Root,
+    Literal,
+    Argument(Parser),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub kind: NodeKind,
+    pub name: String,
+    pub executable: bool,
+    pub children: Vec<usize>,
+    pub redirect: Option<usize>,
+    pub suggestions: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommandTree {
+    pub nodes: Vec<Node>,
+    pub root: usize,
+}
+
+impl Parser {
+    // number of tokens taken by the argument, None if the input is invalid
+    fn accept(&self, tokens: &[&str]) -> Option<usize> {
+        let token = tokens[0];
+        match self {
+            Parser::Bool => matches!(token, "true" | "false").then_some(1),
+            Parser::Float(min, max) => token
+                .parse::<f32>()
+                .ok()
+                .filter(|v| in_range(*v, *min, *max))
+                .map(|_| 1),
+            Parser::Double(min, max) => token
+                .parse::<f64>()
+                .ok()
+                .filter(|v| in_range(*v, *min, *max))
+                .map(|_| 1),
+            Parser::Integer(min, max) => token
+                .parse::<i32>()
+                .ok()
+                .filter(|v| in_range(*v, *min, *max))
+                .map(|_| 1),
+            Parser::Long(min, max) => token
+                .parse::<i64>()
+                .ok()
+                .filter(|v| in_range(*v, *min, *max))
+                .map(|_| 1),
+            Parser::String(2) => Some(tokens.len()),
+            Parser::String(1) if token.starts_with('"') => tokens
+                .iter()
+                .enumerate()
+                .position(|(i, t)| t.ends_with('"') && (i > 0 || t.len() > 1))
+                .map(|i| i + 1),
+            Parser::String(_) => Some(1),
+            Parser::Entity(flags) => selector(tokens, flags & 0x02 != 0),
+            Parser::ScoreHolder(_) => {
+                if token == "*" {
+                    Some(1)
+                } else {
+                    selector(tokens, false)
+                }
+            }
+            Parser::Time(min) => {
+                let (number, unit) = match token.strip_suffix(['d', 's', 't']) {
+                    Some(number) => (number, &token[number.len()..]),
+                    None => (token, "t"),
+                };
+                let ticks = number.parse::<f32>().ok()?
+                    * match unit {
+                        "d" => 24000.0,
+                        "s" => 20.0,
+                        _ => 1.0,
+                    };
+                (ticks >= *min as f32).then_some(1)
+            }
+            Parser::Resource(_, _) => Some(1),
+            Parser::Other(name) => match name.as_str() {
+                "minecraft:block_pos" | "minecraft:vec3" => (tokens.len() >= 3).then_some(3),
+                "minecraft:column_pos" | "minecraft:vec2" | "minecraft:rotation" => {
+                    (tokens.len() >= 2).then_some(2)
+                }
+                "minecraft:message"
+                | "minecraft:component"
+                | "minecraft:style"
+                | "minecraft:nbt_compound_tag"
+                | "minecraft:nbt_tag" => Some(tokens.len()),
+                // states, components and nbt may contain spaces
+                "minecraft:block_state"
+                | "minecraft:block_predicate"
+                | "minecraft:item_stack"
+                | "minecraft:item_predicate"
+                | "minecraft:nbt_path" => balanced(tokens),
+                _ => Some(1),
+            },
+        }
+    }
+}
+
+fn in_range<T: PartialOrd>(v: T, min: Option<T>, max: Option<T>) -> bool {
+    min.map_or(true, |min| v >= min) && max.map_or(true, |max| v <= max)
+}
+
+// player name, uuid or target selector such as `@e[type=cow, limit=1]`
+fn selector(tokens: &[&str], players_only: bool) -> Option<usize> {
+    let token = tokens[0];
+    if !token.starts_with('@') {
+        return Some(1);
+    }
+    let variable = token.get(1..2)?;
+    if !matches!(variable, "p" | "a" | "r" | "s" | "e") || (players_only && variable == "e") {
+        return None;
+    }
+    balanced(tokens)
+}
+
+// tokens up to the one closing all `[` and `{`, None if left open
+fn balanced(tokens: &[&str]) -> Option<usize> {
+    let mut depth = 0;
+    for (i, t) in tokens.iter().enumerate() {
+        depth += t.matches(['[', '{']).count() as i32 - t.matches([']', '}']).count() as i32;
+        if depth <= 0 {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+impl CommandTree {
+    pub fn new(nodes: Vec<Node>, root: usize) -> CommandTree {
+        CommandTree { nodes, root }
+    }
+
+    // children of a node, following redirects
+    fn children(&self, node: usize) -> &Vec<usize> {
+        match self.nodes[node].redirect {
+            Some(redirect) if self.nodes[node].children.is_empty() => {
+                &self.nodes[redirect].children
+            }
+            _ => &self.nodes[node].children,
+        }
+    }
+
+    // walk the tree, error with the number of tokens left at the failure
+    fn walk(&self, node: usize, tokens: &[&str]) -> Result<(), (usize, String)> {
+        if tokens.is_empty() {
+            return match self.nodes[node].executable {
+                true => Ok(()),
+                false => Err((0, "Incomplete command".to_string())),
+            };
+        }
+        let mut error = (
+            tokens.len(),
+            format!("Unknown or incorrect argument: {}", tokens[0]),
+        );
+        for child in self.children(node) {
+            let taken = match &self.nodes[*child].kind {
+                NodeKind::Literal if self.nodes[*child].name == tokens[0] => Some(1),
+                NodeKind::Argument(parser) => parser.accept(tokens),
+                _ => None,
+            };
+            let taken = match taken {
+                Some(taken) if taken <= tokens.len() => taken,
+                _ => continue,
+            };
+            match self.walk(*child, &tokens[taken..]) {
+                Ok(_) => return Ok(()),
+                Err(e) if e.0 < error.0 => error = e,
+                Err(_) => {}
+            }
+        }
+        Err(error)
+    }
+
+    // nodes reached after all tokens are taken
+    fn reach(&self, node: usize, tokens: &[&str], reached: &mut Vec<usize>) {
+        if tokens.is_empty() {
+            reached.push(node);
+            return;
+        }
+        for child in self.children(node) {
+            let taken = match &self.nodes[*child].kind {
+                NodeKind::Literal if self.nodes[*child].name == tokens[0] => Some(1),
+                NodeKind::Argument(parser) => parser.accept(tokens),
+                _ => None,
+            };
+            match taken {
+                Some(taken) if taken <= tokens.len() => {
+                    self.reach(*child, &tokens[taken..], reached)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // check a command (without `/`) before sending
+    pub fn validate(&self, command: &str) -> Result<(), String> {
+        let tokens = command.split_whitespace().collect::<Vec<&str>>();
+        if tokens.is_empty() {
+            return Err("Empty command".to_string());
+        }
+        self.walk(self.root, &tokens).map_err(|(_, e)| e)
+    }
+
+    // completed inputs, and whether the server should be asked for suggestions
+    pub fn complete(&self, input: &str) -> (Vec<String>, bool) {
+        let (done, partial) = match input.rfind(' ') {
+            Some(i) => (&input[..i + 1], &input[i + 1..]),
+            None => ("", input),
+        };
+        let tokens = done.split_whitespace().collect::<Vec<&str>>();
+        let mut reached = Vec::new();
+        self.reach(self.root, &tokens, &mut reached);
+
+        let mut candidates = Vec::new();
+        let mut ask_server = false;
+        for node in reached {
+            for child in self.children(node) {
+                let child = &self.nodes[*child];
+                if child.suggestions.as_deref() == Some(ASK_SERVER) {
+                    ask_server = true;
+                }
+                let names = match &child.kind {
+                    NodeKind::Literal => vec![child.name.as_str()],
+                    NodeKind::Argument(Parser::Bool) => vec!["true", "false"],
+                    _ => vec![],
+                };
+                names
+                    .into_iter()
+                    .filter(|name| name.starts_with(partial))
+                    .for_each(|name| candidates.push(format!("{}{}", done, name)));
+            }
+        }
+        candidates.sort();
+        candidates.dedup();
+        (candidates, ask_server)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::version::{V1_20_1, V1_20_2};
+
+    fn node(kind: NodeKind, name: &str, executable: bool, children: Vec<usize>) -> Node {
+        Node {
+            kind,
+            name: name.to_string(),
+            executable,
+            children,
+            redirect: None,
+            suggestions: None,
+        }
+    }
+
+    // tp <targets> <location>, time set <time>, gamemode <survival|creative>, execute run ...
+    fn tree() -> CommandTree {
+        let mut nodes = vec![
+            node(NodeKind::Root, "", false, vec![1, 4, 7, 9]),
+            node(NodeKind::Literal, "tp", false, vec![2]),
+            node(
+                NodeKind::Argument(Parser::Entity(0x00)),
+                "targets",
+                false,
+                vec![3],
+            ),
+            node(
+                NodeKind::Argument(Parser::Other("minecraft:vec3".to_string())),
+                "location",
+                true,
+                vec![],
+            ),
+            node(NodeKind::Literal, "time", false, vec![5]),
+            node(NodeKind::Literal, "set", false, vec![6]),
+            node(NodeKind::Argument(Parser::Time(0)), "time", true, vec![]),
+            node(NodeKind::Literal, "gamemode", false, vec![8]),
+            node(
+                NodeKind::Argument(Parser::Integer(Some(0), Some(3))),
+                "mode",
+                true,
+                vec![],
+            ),
+            node(NodeKind::Literal, "execute", false, vec![10]),
+            node(NodeKind::Literal, "run", false, vec![]),
+        ];
+        nodes[10].redirect = Some(0);
+        nodes[2].suggestions = Some(ASK_SERVER.to_string());
+        CommandTree::new(nodes, 0)
+    }
+
+    #[test]
+    fn test_parser_name() {
+        let name = |protocol, id| parser_name(protocol, id).unwrap_or_default();
+        assert_eq!(name(V1_20_1, 29), "minecraft:score_holder");
+        assert_eq!(name(V1_20_1, 47), "minecraft:heightmap");
+        assert_eq!(name(V1_20_1, 48), "minecraft:uuid");
+        assert_eq!(parser_name(V1_20_1, 49), None);
+        assert_eq!(name(V1_20_2, 48), "minecraft:uuid");
+        assert_eq!(name(V1_20_3, 18), "minecraft:style");
+        assert_eq!(name(V1_20_3, 30), "minecraft:score_holder");
+        assert_eq!(name(V1_20_3, 41), "minecraft:time");
+        assert_eq!(name(V1_20_3, 49), "minecraft:uuid");
+        assert_eq!(name(V1_20_5, 34), "minecraft:item_slots");
+        assert_eq!(name(V1_20_5, 42), "minecraft:time");
+        assert_eq!(name(V1_20_5, 50), "minecraft:loot_table");
+        assert_eq!(name(V1_20_5, 53), "minecraft:uuid");
+    }
+
+    #[test]
+    fn test_validate() {
+        let tree = tree();
+        assert!(tree.validate("tp @e[type=cow, limit=1] 1 64 -2").is_ok());
+        assert!(tree.validate("time set 1d").is_ok());
+        assert!(tree.validate("execute run gamemode 1").is_ok());
+        assert_eq!(
+            tree.validate("gamemode 5"),
+            Err("Unknown or incorrect argument: 5".to_string())
+        );
+        assert_eq!(
+            tree.validate("tp Steve 1 64"),
+            Err("Unknown or incorrect argument: 1".to_string())
+        );
+        assert_eq!(
+            tree.validate("time set"),
+            Err("Incomplete command".to_string())
+        );
+        assert_eq!(
+            tree.validate("kill"),
+            Err("Unknown or incorrect argument: kill".to_string())
+        );
+    }
+
+    #[test]
+    fn test_accept_balanced() {
+        let block = Parser::Other("minecraft:block_state".to_string());
+        assert_eq!(block.accept(&["stone", "replace"]), Some(1));
+        assert_eq!(
+            block.accept(&["oak_stairs[facing=north,", "half=top]", "replace"]),
+            Some(2)
+        );
+        assert_eq!(block.accept(&["chest[facing=east]{Items:", "[]}"]), Some(2));
+        assert_eq!(block.accept(&["stone[", "facing=north"]), None);
+        let item = Parser::Other("minecraft:item_stack".to_string());
+        assert_eq!(item.accept(&["diamond_sword{Damage:", "5}", "1"]), Some(2));
+    }
+
+    #[test]
+    fn test_complete() {
+        let tree = tree();
+        assert_eq!(
+            tree.complete("t"),
+            (vec!["time".to_string(), "tp".to_string()], false)
+        );
+        assert_eq!(
+            tree.complete("time s"),
+            (vec!["time set".to_string()], false)
+        );
+        assert_eq!(
+            tree.complete("execute run ga"),
+            (vec!["execute run gamemode".to_string()], false)
+        );
+        assert_eq!(tree.complete("tp "), (vec![], true));
+    }
+}
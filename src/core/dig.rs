@@ -0,0 +1,231 @@
+use tokio::time::Duration;
+
+// block name, hardness, preferred tool, minimum tier to harvest (-1: any)
+const BLOCKS: [(&str, f32, &str, i32); 40] = [
+    ("stone", 1.5, "pickaxe", 0),
+    ("granite", 1.5, "pickaxe", 0),
+    ("diorite", 1.5, "pickaxe", 0),
+    ("andesite", 1.5, "pickaxe", 0),
+    ("deepslate", 3.0, "pickaxe", 0),
+    ("cobblestone", 2.0, "pickaxe", 0),
+    ("cobbled_deepslate", 3.5, "pickaxe", 0),
+    ("sandstone", 0.8, "pickaxe", 0),
+    ("bricks", 2.0, "pickaxe", 0),
+    ("terracotta", 1.25, "pickaxe", 0),
+    ("netherrack", 0.4, "pickaxe", 0),
+    ("end_stone", 3.0, "pickaxe", 0),
+    ("coal_ore", 3.0, "pickaxe", 0),
+    ("iron_ore", 3.0, "pickaxe", 1),
+    ("copper_ore", 3.0, "pickaxe", 1),
+    ("lapis_ore", 3.0, "pickaxe", 1),
+    ("gold_ore", 3.0, "pickaxe", 2),
+    ("redstone_ore", 3.0, "pickaxe", 2),
+    ("diamond_ore", 3.0, "pickaxe", 2),
+    ("emerald_ore", 3.0, "pickaxe", 2),
+    ("obsidian", 50.0, "pickaxe", 3),
+    ("ice", 0.5, "pickaxe", -1),
+    ("dirt", 0.5, "shovel", -1),
+    ("grass_block", 0.6, "shovel", -1),
+    ("sand", 0.5, "shovel", -1),
+    ("gravel", 0.6, "shovel", -1),
+    ("clay", 0.6, "shovel", -1),
+    ("snow_block", 0.2, "shovel", 0),
+    ("soul_sand", 0.5, "shovel", -1),
+    ("oak_log", 2.0, "axe", -1),
+    ("oak_planks", 2.0, "axe", -1),
+    ("crafting_table", 2.5, "axe", -1),
+    ("chest", 2.5, "axe", -1),
+    ("oak_leaves", 0.2, "hoe", -1),
+    ("white_wool", 0.8, "shears", -1),
+    ("glass", 0.3, "", -1),
+    ("torch", 0.0, "", -1),
+    ("short_grass", 0.0, "", -1),
+    ("tall_grass", 0.0, "", -1),
+    ("bedrock", -1.0, "", -1),
+];
+
+// tool material, speed, tier
+const MATERIALS: [(&str, f32, i32); 6] = [
+    ("wooden", 2.0, 0),
+    ("golden", 12.0, 0),
+    ("stone", 4.0, 1),
+    ("iron", 6.0, 2),
+    ("diamond", 8.0, 3),
+    ("netherite", 9.0, 4),
+];
+
+// blocks shears are faster on, speed
+const SHEARS: [(&str, f32); 2] = [("oak_leaves", 15.0), ("white_wool", 5.0)];
+
+pub const DEFAULT_BLOCK: &str = "stone";
+pub const DEFAULT_TOOL: &str = "hand";
+
+// (tool kind, speed, tier) of an item such as `diamond_pickaxe`
+fn tool(name: &str) -> (String, f32, i32) {
+    if name == "shears" {
+        return ("shears".to_string(), 2.0, -1);
+    }
+    match name.split_once('_') {
+        Some((material, kind)) => match MATERIALS.iter().find(|(m, _, _)| *m == material) {
+            Some((_, speed, tier)) => (kind.to_string(), *speed, *tier),
+            None => (String::new(), 1.0, -1),
+        },
+        None => (String::new(), 1.0, -1),
+    }
+}
+
+pub fn is_known_block(block: &str) -> bool {
+    BLOCKS.iter().any(|(b, _, _, _)| *b == block)
+}
+
+// estimate dig time (None: unbreakable)
+pub fn estimate(block: &str, held: &str) -> Option<Duration> {
+    let (hardness, preferred, min_tier) = match BLOCKS.iter().find(|(b, _, _, _)| *b == block) {
+        Some((_, hardness, preferred, min_tier)) => (*hardness, *preferred, *min_tier),
+        None => (1.5, "pickaxe", 0),
+    };
+    if hardness < 0.0 {
+        return None;
+    }
+    if hardness == 0.0 {
+        return Some(Duration::ZERO);
+    }
+
+    let (kind, speed, tier) = tool(held);
+    let shears = SHEARS.iter().find(|(b, _)| kind == "shears" && *b == block);
+    let correct = (!preferred.is_empty() && kind == preferred) || shears.is_some();
+    let speed = match shears {
+        Some((_, speed)) => *speed,
+        None if correct => speed,
+        None => 1.0,
+    };
+    let harvest = min_tier < 0 || (correct && tier >= min_tier);
+
+    // ticks until the damage reaches 1.0 (speed / hardness / 30 per tick)
+    let ticks = hardness * if harvest { 30.0 } else { 100.0 } / speed;
+    if ticks <= 1.0 {
+        return Some(Duration::ZERO);
+    }
+    Some(Duration::from_millis(ticks.ceil() as u64 * 50))
+}
+
+// block change predicted by a sequenced action, until the server acknowledges it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prediction {
+    pub sequence: i32,
+    pub position: (i32, i32, i32),
+    // air after digging, a block after placing
+    pub air: bool,
+    // block state of the last block update at the position
+    pub state: Option<i32>,
+}
+
+impl Prediction {
+    // without a block update the predicted change stands
+    pub fn confirmed(&self) -> bool {
+        match self.state {
+            Some(state) => (state == 0) == self.air,
+            None => true,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Predictions {
+    pending: Vec<Prediction>,
+}
+
+impl Predictions {
+    pub fn new() -> Predictions {
+        Predictions::default()
+    }
+
+    pub fn predict(&mut self, sequence: i32, position: (i32, i32, i32), air: bool) {
+        self.pending.push(Prediction {
+            sequence,
+            position,
+            air,
+            state: None,
+        });
+    }
+
+    pub fn update(&mut self, position: (i32, i32, i32), state: i32) {
+        for prediction in self.pending.iter_mut() {
+            if prediction.position == position {
+                prediction.state = Some(state);
+            }
+        }
+    }
+
+    // predictions up to the acknowledged sequence
+    pub fn acknowledge(&mut self, sequence: i32) -> Vec<Prediction> {
+        let (settled, pending) = self.pending.drain(..).partition(|p| p.sequence <= sequence);
+        self.pending = pending;
+        settled
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate() {
+        // stone with hand: 1.5 * 5 = 7.5s
+        assert_eq!(estimate("stone", "hand"), Some(Duration::from_millis(7500)));
+        // stone with wooden pickaxe: 1.5 * 1.5 / 2 = 1.15s
+        assert_eq!(
+            estimate("stone", "wooden_pickaxe"),
+            Some(Duration::from_millis(1150))
+        );
+        // dirt with hand: 0.75s
+        assert_eq!(estimate("dirt", "hand"), Some(Duration::from_millis(750)));
+        // obsidian with iron pickaxe can not be harvested: 50 * 5 / 6
+        assert_eq!(
+            estimate("obsidian", "iron_pickaxe"),
+            Some(Duration::from_millis(41700))
+        );
+        // instant
+        assert_eq!(estimate("torch", "hand"), Some(Duration::ZERO));
+        assert_eq!(
+            estimate("snow_block", "golden_shovel"),
+            Some(Duration::ZERO)
+        );
+        // wool with shears: 0.8 * 1.5 / 5 = 0.25s, leaves are instant
+        assert_eq!(
+            estimate("white_wool", "shears"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(estimate("oak_leaves", "shears"), Some(Duration::ZERO));
+        assert_eq!(
+            estimate("white_wool", "hand"),
+            Some(Duration::from_millis(1200))
+        );
+        // unbreakable
+        assert_eq!(estimate("bedrock", "netherite_pickaxe"), None);
+    }
+
+    #[test]
+    fn test_predictions() {
+        let mut predictions = Predictions::new();
+        predictions.predict(1, (0, 64, 0), true);
+        predictions.predict(2, (1, 64, 0), false);
+        predictions.predict(3, (2, 64, 0), true);
+        // the dug block is back, the placed block is there
+        predictions.update((0, 64, 0), 1);
+        predictions.update((1, 64, 0), 9);
+        let settled = predictions.acknowledge(2);
+        assert_eq!(settled.len(), 2);
+        assert!(!settled[0].confirmed());
+        assert!(settled[1].confirmed());
+        // no block update
+        let settled = predictions.acknowledge(3);
+        assert_eq!(settled.len(), 1);
+        assert!(settled[0].confirmed());
+        assert!(predictions.acknowledge(4).is_empty());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::core::version::{V1_20_3, V1_20_5};
+use std::collections::HashMap;
+use tokio::time::Duration;
+
+// entity type registry (1.20.1 and 1.20.2)
+const ENTITY_TYPES: [&str; 124] = [
+    "allay",
+    "area_effect_cloud",
+    "armor_stand",
+    "arrow",
+    "axolotl",
+    "bat",
+    "bee",
+    "blaze",
+    "block_display",
+    "boat",
+    "camel",
+    "cat",
+    "cave_spider",
+    "chest_boat",
+    "chest_minecart",
+    "chicken",
+    "cod",
+    "command_block_minecart",
+    "cow",
+    "creeper",
+    "dolphin",
+    "donkey",
+    "dragon_fireball",
+    "drowned",
+    "egg",
+    "elder_guardian",
+    "end_crystal",
+    "ender_dragon",
+    "ender_pearl",
+    "enderman",
+    "endermite",
+    "evoker",
+    "evoker_fangs",
+    "experience_bottle",
+    "experience_orb",
+    "eye_of_ender",
+    "falling_block",
+    "firework_rocket",
+    "fox",
+    "frog",
+    "furnace_minecart",
+    "ghast",
+    "giant",
+    "glow_item_frame",
+    "glow_squid",
+    "goat",
+    "guardian",
+    "hoglin",
+    "hopper_minecart",
+    "horse",
+    "husk",
+    "illusioner",
+    "interaction",
+    "iron_golem",
+    "item",
+    "item_display",
+    "item_frame",
+    "fireball",
+    "leash_knot",
+    "lightning_bolt",
+    "llama",
+    "llama_spit",
+    "magma_cube",
+    "marker",
+    "minecart",
+    "mooshroom",
+    "mule",
+    "ocelot",
+    "painting",
+    "panda",
+    "parrot",
+    "phantom",
+    "pig",
+    "piglin",
+    "piglin_brute",
+    "pillager",
+    "polar_bear",
+    "potion",
+    "pufferfish",
+    "rabbit",
+    "ravager",
+    "salmon",
+    "sheep",
+    "shulker",
+    "shulker_bullet",
+    "silverfish",
+    "skeleton",
+    "skeleton_horse",
+    "slime",
+    "small_fireball",
+    "sniffer",
+    "snow_golem",
+    "snowball",
+    "spawner_minecart",
+    "spectral_arrow",
+    "spider",
+    "squid",
+    "stray",
+    "strider",
+    "tadpole",
+    "text_display",
+    "tnt",
+    "tnt_minecart",
+    "trader_llama",
+    "trident",
+    "tropical_fish",
+    "turtle",
+    "vex",
+    "villager",
+    "vindicator",
+    "wandering_trader",
+    "warden",
+    "witch",
+    "wither",
+    "wither_skeleton",
+    "wither_skull",
+    "wolf",
+    "zoglin",
+    "zombie",
+    "zombie_horse",
+    "zombie_villager",
+    "zombified_piglin",
+    "player",
+    "fishing_bobber",
+];
+
+// added by later versions: since, name, inserted after
+const ADDED_TYPES: [(i32, &str, &str); 6] = [
+    (V1_20_3, "breeze", "boat"),
+    (V1_20_3, "wind_charge", "warden"),
+    (V1_20_5, "armadillo", "area_effect_cloud"),
+    (V1_20_5, "bogged", "boat"),
+    (V1_20_5, "breeze_wind_charge", "breeze"),
+    (V1_20_5, "ominous_item_spawner", "ocelot"),
+];
+
+// survival reach
+pub const REACH: f64 = 3.0;
+// full attack strength with a sword (attack speed 1.6)
+pub const ATTACK_COOLDOWN: Duration = Duration::from_millis(625);
+
+// entity type registry of the protocol version
+fn types(protocol: i32) -> Vec<&'static str> {
+    let mut types = ENTITY_TYPES.to_vec();
+    for (since, name, after) in ADDED_TYPES {
+        if protocol < since {
+            continue;
+        }
+        if let Some(index) = types.iter().position(|t| *t == after) {
+            types.insert(index + 1, name);
+        }
+    }
+    types
+}
+
+pub fn type_name(protocol: i32, kind: i32) -> String {
+    match types(protocol).get(kind as usize) {
+        Some(name) => name.to_string(),
+        None => format!("unknown({})", kind),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entity {
+    pub id: i32,
+    pub uuid: Vec<u8>,
+    pub kind: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Entity {
+    pub fn distance(&self, x: f64, y: f64, z: f64) -> f64 {
+        ((self.x - x).powi(2) + (self.y - y).powi(2) + (self.z - z).powi(2)).sqrt()
+    }
+}
+
+#[derive(Default)]
+pub struct EntityTracker {
+    entities: HashMap<i32, Entity>,
+}
+
+impl EntityTracker {
+    pub fn new() -> EntityTracker {
+        EntityTracker {
+            entities: HashMap::new(),
+        }
+    }
+
+    pub fn spawn(&mut self, id: i32, uuid: Vec<u8>, kind: String, x: f64, y: f64, z: f64) {
+        self.entities.insert(
+            id,
+            Entity {
+                id,
+                uuid,
+                kind,
+                x,
+                y,
+                z,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, ids: Vec<i32>) {
+        for id in ids {
+            self.entities.remove(&id);
+        }
+    }
+
+    pub fn relative_move(&mut self, id: i32, dx: f64, dy: f64, dz: f64) {
+        if let Some(entity) = self.entities.get_mut(&id) {
+            entity.x += dx;
+            entity.y += dy;
+            entity.z += dz;
+        }
+    }
+
+    pub fn teleport(&mut self, id: i32, x: f64, y: f64, z: f64) {
+        if let Some(entity) = self.entities.get_mut(&id) {
+            entity.x = x;
+            entity.y = y;
+            entity.z = z;
+        }
+    }
+
+    pub fn get(&self, id: i32) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    // nearest entity of a type, `None` kind matches any type
+    pub fn nearest(&self, kind: Option<&str>, x: f64, y: f64, z: f64) -> Option<&Entity> {
+        self.entities
+            .values()
+            .filter(|e| kind.map_or(true, |k| e.kind == k))
+            .min_by(|a, b| a.distance(x, y, z).total_cmp(&b.distance(x, y, z)))
+    }
+
+    // entities sorted by distance
+    pub fn around(&self, x: f64, y: f64, z: f64, n: usize) -> Vec<&Entity> {
+        let mut entities = self.entities.values().collect::<Vec<&Entity>>();
+        entities.sort_by(|a, b| a.distance(x, y, z).total_cmp(&b.distance(x, y, z)));
+        entities.truncate(n);
+        entities
+    }
+
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name(763, 118), "zombie");
+        assert_eq!(type_name(763, 122), "player");
+        assert_eq!(type_name(764, 9), "boat");
+        assert_eq!(type_name(763, 500), "unknown(500)");
+        // breeze and wind charge since 765
+        assert_eq!(type_name(765, 10), "breeze");
+        assert_eq!(type_name(765, 11), "camel");
+        assert_eq!(type_name(765, 124), "player");
+        // armadillo, bogged, breeze wind charge and ominous item spawner since 766
+        assert_eq!(type_name(766, 2), "armadillo");
+        assert_eq!(type_name(766, 11), "bogged");
+        assert_eq!(type_name(766, 13), "breeze_wind_charge");
+        assert_eq!(type_name(766, 14), "camel");
+        assert_eq!(type_name(766, 129), "fishing_bobber");
+    }
+
+    #[test]
+    fn test_tracker() {
+        let mut tracker = EntityTracker::new();
+        tracker.spawn(1, vec![], "zombie".to_string(), 10.0, 64.0, 0.0);
+        tracker.spawn(2, vec![], "zombie".to_string(), 2.0, 64.0, 0.0);
+        tracker.spawn(3, vec![], "cow".to_string(), 1.0, 64.0, 0.0);
+
+        assert_eq!(
+            tracker.nearest(Some("zombie"), 0.0, 64.0, 0.0).unwrap().id,
+            2
+        );
+        assert_eq!(tracker.nearest(None, 0.0, 64.0, 0.0).unwrap().id, 3);
+
+        tracker.relative_move(2, 20.0, 0.0, 0.0);
+        assert_eq!(
+            tracker.nearest(Some("zombie"), 0.0, 64.0, 0.0).unwrap().id,
+            1
+        );
+
+        tracker.teleport(1, 30.0, 64.0, 0.0);
+        tracker.remove(vec![3]);
+        let around = tracker.around(0.0, 64.0, 0.0, 5);
+        assert_eq!(
+            around.iter().map(|e| e.id).collect::<Vec<i32>>(),
+            vec![2, 1]
+        );
+        assert!(tracker.get(3).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+use log::debug;
+use tokio::sync::broadcast;
+use tokio::time::Duration;
+
+// events kept for a slow subscriber before it lags
+const CAPACITY: usize = 256;
+
+// console -> client
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Quit,
+    Connect,
+    Respawn,
+    // chat message, server command without `/`
+    Chat(String),
+    Command(String),
+    // input to complete, answered with the candidates
+    Complete(String),
+    Dig {
+        x: i32,
+        y: i32,
+        z: i32,
+        block: String,
+    },
+    CancelDig,
+    Place {
+        x: i32,
+        y: i32,
+        z: i32,
+        face: u8,
+    },
+    // entity id or type
+    Attack(String),
+    Use(String),
+    Mount(String),
+    Dismount,
+    // through the proxy server command
+    Servers,
+    Switch(String),
+    // answered on the response channel
+    Query(Query),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Query {
+    Position,
+    Server,
+    Time,
+    Tps,
+    Exp,
+    Health,
+    Entities,
+    BossBars,
+    Scoreboard,
+    Players,
+    Channels,
+    Latency,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectionState {
+    // waiting for /connect
+    Offline,
+    Connecting,
+    Joined,
+    // auto reconnect after the delay
+    Reconnecting(Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+// client -> subscribers
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientEvent {
+    // lines for the console
+    Display(Vec<String>),
+    // text beside the prompt for a while (`Duration::MAX`: until the next one), empty to clear
+    StatusLine(String, Duration),
+    // plain text, line is styled for the console
+    Chat {
+        sender: Option<String>,
+        message: String,
+        line: String,
+    },
+    Health {
+        health: f32,
+        food: i32,
+        saturation: f32,
+    },
+    Position(Position),
+    // plain disconnect reason of the server
+    Kick(String),
+    Connection(ConnectionState),
+}
+
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<ClientEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> EventBus {
+        EventBus::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> EventBus {
+        let (tx, _) = broadcast::channel(CAPACITY);
+        EventBus { tx }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ClientEvent> {
+        self.tx.subscribe()
+    }
+
+    // dropped if nobody listens
+    pub fn publish(&self, event: ClientEvent) {
+        match self.tx.send(event) {
+            Ok(n) => {
+                debug!("Published event to {} subscribers", n);
+            }
+            Err(e) => {
+                debug!("No subscriber for event: {:?}", e.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribers() {
+        let bus = EventBus::new();
+        // nobody listens yet
+        bus.publish(ClientEvent::Connection(ConnectionState::Offline));
+        let mut console = bus.subscribe();
+        let mut logger = bus.subscribe();
+        bus.publish(ClientEvent::Kick("Server closed".to_string()));
+        for rx in [&mut console, &mut logger] {
+            assert_eq!(
+                rx.recv().await.unwrap(),
+                ClientEvent::Kick("Server closed".to_string())
+            );
+            assert!(rx.try_recv().is_err());
+        }
+    }
+}
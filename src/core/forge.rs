@@ -0,0 +1,190 @@
+use crate::util::transfer_var::{read_string, read_var_int, uint2var_int, write_string};
+
+// login plugin channel wrapping the forge handshake
+pub const LOGIN_WRAPPER: &str = "fml:loginwrapper";
+const HANDSHAKE: &str = "fml:handshake";
+
+// handshake messages
+const MOD_LIST: i32 = 1;
+const MOD_LIST_REPLY: i32 = 2;
+const SERVER_REGISTRY: i32 = 3;
+const CONFIG_DATA: i32 = 4;
+const MOD_DATA: i32 = 5;
+const CHANNEL_MISMATCH: i32 = 6;
+const ACKNOWLEDGE: i32 = 99;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Marker {
+    // vanilla
+    None,
+    // 1.13 - 1.17
+    Fml2,
+    // 1.18+
+    Fml3,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModList {
+    pub mods: Vec<String>,
+    // name, version
+    pub channels: Vec<(String, String)>,
+    pub registries: Vec<String>,
+}
+
+pub struct Forge {
+    marker: Marker,
+    mods: Vec<String>,
+}
+
+impl Default for Forge {
+    fn default() -> Forge {
+        Forge {
+            marker: Marker::None,
+            mods: Vec::new(),
+        }
+    }
+}
+
+impl Forge {
+    pub fn new(marker: &str, mods: Vec<String>) -> Forge {
+        let marker = match marker {
+            "FML2" => Marker::Fml2,
+            "FML3" => Marker::Fml3,
+            _ => Marker::None,
+        };
+        Forge { marker, mods }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.marker != Marker::None
+    }
+
+    // server address in the handshake, marked for forge servers
+    pub fn address(&self, host: &str) -> String {
+        match self.marker {
+            Marker::None => host.to_string(),
+            Marker::Fml2 => format!("{}\0FML2\0", host),
+            Marker::Fml3 => format!("{}\0FML3\0", host),
+        }
+    }
+
+    // login wrapper data of the response
+    pub fn respond(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        let mut index = 0;
+        let channel = read_string(data, &mut index);
+        if channel != HANDSHAKE {
+            return Err(format!("unknown channel: {}", channel));
+        }
+        let len = read_var_int(data, &mut index) as usize;
+        let message = &data[index..(index + len).min(data.len())];
+        let mut index = 0;
+        let reply = match read_var_int(message, &mut index) {
+            MOD_LIST => {
+                let mod_list = self.parse_mod_list(&message[index..]);
+                self.mod_list_reply(mod_list)
+            }
+            SERVER_REGISTRY | CONFIG_DATA | MOD_DATA => uint2var_int(vec![ACKNOWLEDGE]),
+            CHANNEL_MISMATCH => return Err("channel mismatch".to_string()),
+            id => return Err(format!("unknown message: {}", id)),
+        };
+        Ok(wrap(reply))
+    }
+
+    fn parse_mod_list(&self, pkt: &[u8]) -> ModList {
+        let mut index = 0;
+        let mut mod_list = ModList::default();
+        for _ in 0..read_var_int(pkt, &mut index) {
+            mod_list.mods.push(read_string(pkt, &mut index));
+        }
+        for _ in 0..read_var_int(pkt, &mut index) {
+            let name = read_string(pkt, &mut index);
+            let version = read_string(pkt, &mut index);
+            mod_list.channels.push((name, version));
+        }
+        for _ in 0..read_var_int(pkt, &mut index) {
+            mod_list.registries.push(read_string(pkt, &mut index));
+        }
+        // fml3 data pack registries are not answered
+        mod_list
+    }
+
+    // our mods, the channels of the server, no registry snapshots
+    fn mod_list_reply(&self, mod_list: ModList) -> Vec<u8> {
+        let mut reply = uint2var_int(vec![MOD_LIST_REPLY]);
+        reply.extend(uint2var_int(vec![self.mods.len() as i32]));
+        self.mods.iter().for_each(|m| reply.extend(write_string(m)));
+        reply.extend(uint2var_int(vec![mod_list.channels.len() as i32]));
+        for (name, version) in mod_list.channels.iter() {
+            reply.extend(write_string(name));
+            reply.extend(write_string(version));
+        }
+        reply.extend(uint2var_int(vec![0]));
+        reply
+    }
+}
+
+// handshake channel and the message length
+fn wrap(message: Vec<u8>) -> Vec<u8> {
+    [
+        write_string(HANDSHAKE),
+        uint2var_int(vec![message.len() as i32]),
+        message,
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        assert_eq!(Forge::default().address("a.b"), "a.b");
+        assert_eq!(Forge::new("FML3", vec![]).address("a.b"), "a.b\0FML3\0");
+    }
+
+    #[test]
+    fn test_mod_list() {
+        let forge = Forge::new("FML3", vec!["minecraft".to_string(), "forge".to_string()]);
+        // mods: forge; channels: forge:tier_sorting 1.0; registries: minecraft:item
+        let mut message = uint2var_int(vec![MOD_LIST]);
+        message.extend([vec![0x01], write_string("forge")].concat());
+        message.extend(
+            [
+                vec![0x01],
+                write_string("forge:tier_sorting"),
+                write_string("1.0"),
+            ]
+            .concat(),
+        );
+        message.extend([vec![0x01], write_string("minecraft:item")].concat());
+        // data pack registries
+        message.push(0x00);
+        let reply = forge.respond(&wrap(message)).unwrap();
+
+        let mut expected = uint2var_int(vec![MOD_LIST_REPLY]);
+        expected.extend([vec![0x02], write_string("minecraft"), write_string("forge")].concat());
+        expected.extend(
+            [
+                vec![0x01],
+                write_string("forge:tier_sorting"),
+                write_string("1.0"),
+            ]
+            .concat(),
+        );
+        expected.push(0x00);
+        assert_eq!(reply, wrap(expected));
+    }
+
+    #[test]
+    fn test_acknowledge() {
+        let forge = Forge::new("FML2", vec![]);
+        let mut message = uint2var_int(vec![SERVER_REGISTRY]);
+        message.extend([write_string("minecraft:item"), vec![0x00]].concat());
+        assert_eq!(
+            forge.respond(&wrap(message)),
+            Ok(wrap(vec![ACKNOWLEDGE as u8]))
+        );
+        assert!(forge.respond(&wrap(vec![CHANNEL_MISMATCH as u8])).is_err());
+    }
+}
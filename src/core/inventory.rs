@@ -0,0 +1,136 @@
+use crate::core::dig;
+use std::collections::HashMap;
+
+// window id of the player inventory and its hotbar slots
+const PLAYER_WINDOW: i8 = 0;
+const HOTBAR_START: i16 = 36;
+const HOTBAR_SIZE: usize = 9;
+// item tag, tool kind
+const TOOL_TAGS: [(&str, &str); 5] = [
+    ("swords", "sword"),
+    ("shovels", "shovel"),
+    ("pickaxes", "pickaxe"),
+    ("axes", "axe"),
+    ("hoes", "hoe"),
+];
+// tools are registered in this order, so the ids of a tag are too
+const TOOL_MATERIALS: [&str; 6] = ["wooden", "stone", "golden", "iron", "diamond", "netherite"];
+// shears are in no item tag, they are registered this many items after the netherite hoe
+// (stick, armor, signs, buckets, dyes, beds, ... filled map; 1.20.1 - 1.20.6)
+const SHEARS_AFTER_HOE: i32 = 136;
+
+// hotbar items, and tool names from the item tags of the server
+#[derive(Default)]
+pub struct Inventory {
+    held: usize,
+    hotbar: [Option<i32>; HOTBAR_SIZE],
+    // item id -> tool such as `diamond_pickaxe`
+    tools: HashMap<i32, String>,
+}
+
+impl Inventory {
+    pub fn new() -> Inventory {
+        Inventory::default()
+    }
+
+    pub fn set_held(&mut self, slot: u8) -> bool {
+        if slot as usize >= HOTBAR_SIZE {
+            return false;
+        }
+        self.held = slot as usize;
+        true
+    }
+
+    // slots of other windows or outside the hotbar are ignored
+    pub fn set_slot(&mut self, window: i8, slot: i16, item: Option<i32>) {
+        if window != PLAYER_WINDOW {
+            return;
+        }
+        if let Some(index) = slot.checked_sub(HOTBAR_START) {
+            if (0..HOTBAR_SIZE as i16).contains(&index) {
+                self.hotbar[index as usize] = item;
+            }
+        }
+    }
+
+    // tags of the item registry, a tag changed by a data pack is skipped
+    pub fn set_tags(&mut self, tags: Vec<(String, Vec<i32>)>) {
+        self.tools.clear();
+        for (tag, mut ids) in tags {
+            let tag = tag.trim_start_matches("minecraft:");
+            let kind = match TOOL_TAGS.iter().find(|(t, _)| *t == tag) {
+                Some((_, kind)) => kind,
+                None => continue,
+            };
+            if ids.len() != TOOL_MATERIALS.len() {
+                continue;
+            }
+            ids.sort();
+            if *kind == "hoe" {
+                self.tools
+                    .insert(ids[ids.len() - 1] + SHEARS_AFTER_HOE, "shears".to_string());
+            }
+            for (id, material) in ids.into_iter().zip(TOOL_MATERIALS) {
+                self.tools.insert(id, format!("{}_{}", material, kind));
+            }
+        }
+    }
+
+    // tool in the main hand, `hand` for an empty slot or other items
+    pub fn held(&self) -> String {
+        self.hotbar[self.held]
+            .and_then(|id| self.tools.get(&id).cloned())
+            .unwrap_or(dig::DEFAULT_TOOL.to_string())
+    }
+
+    pub fn clear(&mut self) {
+        self.held = 0;
+        self.hotbar = [None; HOTBAR_SIZE];
+        self.tools.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_held() {
+        let mut inventory = Inventory::new();
+        inventory.set_tags(vec![
+            (
+                "minecraft:pickaxes".to_string(),
+                vec![822, 812, 807, 827, 817, 802],
+            ),
+            ("minecraft:logs".to_string(), vec![1, 2]),
+        ]);
+        assert_eq!(inventory.held(), "hand");
+
+        inventory.set_slot(0, 36, Some(817));
+        assert_eq!(inventory.held(), "iron_pickaxe");
+        inventory.set_slot(0, 40, Some(827));
+        assert!(inventory.set_held(4));
+        assert_eq!(inventory.held(), "netherite_pickaxe");
+        assert!(!inventory.set_held(9));
+
+        // not a tool
+        inventory.set_slot(0, 40, Some(1));
+        assert_eq!(inventory.held(), "hand");
+        // other windows
+        inventory.set_slot(1, 40, Some(802));
+        assert_eq!(inventory.held(), "hand");
+        inventory.set_slot(0, 40, Some(802));
+        assert_eq!(inventory.held(), "wooden_pickaxe");
+
+        // shears after the netherite hoe
+        inventory.set_tags(vec![(
+            "minecraft:hoes".to_string(),
+            vec![804, 809, 814, 819, 824, 829],
+        )]);
+        inventory.set_slot(0, 40, Some(965));
+        assert_eq!(inventory.held(), "shears");
+
+        inventory.clear();
+        assert_eq!(inventory.held(), "hand");
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+use tokio::time::{Duration, Instant};
+
+// samples kept per source
+const WINDOW: usize = 60;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+}
+
+// a window as answered to /latency
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatencyWindow {
+    pub name: String,
+    pub stats: Option<Stats>,
+    pub sparkline: String,
+}
+
+#[derive(Default)]
+pub struct Window {
+    samples: VecDeque<Duration>,
+}
+
+impl Window {
+    pub fn push(&mut self, sample: Duration) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn stats(&self) -> Option<Stats> {
+        let mut sorted = self.samples.iter().copied().collect::<Vec<Duration>>();
+        sorted.sort();
+        let (min, max) = (*sorted.first()?, *sorted.last()?);
+        let avg = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+        // nearest rank
+        let rank = (sorted.len() * 95).div_ceil(100);
+        Some(Stats {
+            min,
+            avg,
+            max,
+            p95: sorted[rank - 1],
+        })
+    }
+
+    // oldest first, scaled between min and max
+    pub fn sparkline(&self) -> String {
+        let (min, max) = match self.stats() {
+            Some(stats) => (stats.min, stats.max),
+            None => return String::new(),
+        };
+        let range = (max - min).as_secs_f64();
+        self.samples
+            .iter()
+            .map(|sample| match range > 0.0 {
+                true => {
+                    let level = (*sample - min).as_secs_f64() / range * (BARS.len() - 1) as f64;
+                    BARS[level.round() as usize]
+                }
+                false => BARS[0],
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Latency {
+    // keep alive arrival to our reply written
+    pub keep_alive: Window,
+    // ping arrival to our pong written
+    pub pong: Window,
+    // ping request written to pong response read (1.20.2+)
+    pub round_trip: Window,
+    // our latency in the tab list, measured by the server
+    pub tab_list: Window,
+    // payload and write time of the ping request in flight
+    pending: Option<(i64, Instant)>,
+    next_id: i64,
+}
+
+impl Latency {
+    // payload of the next ping request, replaces the one in flight
+    pub fn ping_request(&mut self) -> i64 {
+        self.next_id += 1;
+        self.pending = Some((self.next_id, Instant::now()));
+        self.next_id
+    }
+
+    // the writer wrote the request
+    pub fn ping_written(&mut self, id: i64, written: Instant) {
+        if let Some((pending, sent)) = &mut self.pending {
+            if *pending == id {
+                *sent = written;
+            }
+        }
+    }
+
+    // responses to an older request are ignored
+    pub fn pong_response(&mut self, id: i64, arrival: Instant) -> Option<Duration> {
+        match self.pending {
+            Some((pending, sent)) if pending == id => {
+                self.pending = None;
+                let elapsed = arrival.saturating_duration_since(sent);
+                self.round_trip.push(elapsed);
+                Some(elapsed)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn windows(&self) -> Vec<LatencyWindow> {
+        [
+            ("Keep alive", &self.keep_alive),
+            ("Pong", &self.pong),
+            ("Round trip", &self.round_trip),
+            ("Tab list", &self.tab_list),
+        ]
+        .iter()
+        .map(|(name, window)| LatencyWindow {
+            name: name.to_string(),
+            stats: window.stats(),
+            sparkline: window.sparkline(),
+        })
+        .collect()
+    }
+
+    // samples are per connection
+    pub fn clear(&mut self) {
+        *self = Latency::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(millis: &[u64]) -> Window {
+        let mut window = Window::default();
+        for m in millis {
+            window.push(Duration::from_millis(*m));
+        }
+        window
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Window::default().stats(), None);
+        let window = window(&(1..=100).collect::<Vec<u64>>());
+        assert_eq!(
+            window.stats(),
+            Some(Stats {
+                // first samples are dropped
+                min: Duration::from_millis(41),
+                avg: Duration::from_micros(70500),
+                max: Duration::from_millis(100),
+                p95: Duration::from_millis(97),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(window(&[10, 80, 45, 10]).sparkline(), "▁█▅▁");
+        assert_eq!(window(&[5, 5]).sparkline(), "▁▁");
+    }
+
+    #[test]
+    fn test_pong_response() {
+        let mut latency = Latency::default();
+        let old = latency.ping_request();
+        let id = latency.ping_request();
+        let written = Instant::now();
+        latency.ping_written(id, written);
+        let arrival = written + Duration::from_millis(40);
+        assert_eq!(latency.pong_response(old, arrival), None);
+        assert_eq!(
+            latency.pong_response(id, arrival),
+            Some(Duration::from_millis(40))
+        );
+        assert_eq!(latency.pong_response(id, arrival), None);
+        let windows = latency.windows();
+        assert_eq!(windows[2].name, "Round trip");
+        assert!(windows[2].stats.is_some());
+        assert_eq!(windows[0].stats, None);
+    }
+}
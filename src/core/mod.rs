@@ -1,4 +1,21 @@
+pub mod boss_bar;
+pub mod bungeecord;
+pub mod channel;
 pub mod client;
-pub mod console;
+pub mod command_tree;
+pub mod dig;
+pub mod entity;
+pub mod event;
+pub mod forge;
+pub mod inventory;
+pub mod latency;
 pub mod msg;
 mod parser;
+pub mod player_list;
+pub mod query;
+pub mod reconnect;
+pub mod resource_pack;
+pub mod scoreboard;
+pub mod tps;
+pub mod velocity;
+pub mod version;
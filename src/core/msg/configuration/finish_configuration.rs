@@ -0,0 +1,31 @@
+use crate::core::msg::mapper;
+
+// acknowledges finish configuration, enters the play state
+pub fn new(compress: bool) -> Vec<u8> {
+    let mut finish_configuration_pkt: Vec<u8> = Vec::new();
+    if compress {
+        finish_configuration_pkt.push(0x00);
+    }
+    finish_configuration_pkt.push(mapper::FINISH_CONFIGURATION);
+    finish_configuration_pkt = [
+        vec![finish_configuration_pkt.len() as u8],
+        finish_configuration_pkt,
+    ]
+    .concat();
+    finish_configuration_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        assert_eq!(new(true), vec![0x02, 0x00, 0x71]);
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(new(false), vec![0x01, 0x71]);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::core::msg::mapper;
+use crate::util::transfer_var;
+
+// packs known by the client: namespace, id, version
+pub fn new(packs: Vec<(String, String, String)>, compress: bool) -> Vec<u8> {
+    let mut known_packs_pkt: Vec<u8> = Vec::new();
+    if compress {
+        known_packs_pkt.push(0x00);
+    }
+    known_packs_pkt.push(mapper::KNOWN_PACKS);
+    known_packs_pkt.extend(transfer_var::uint2var_int(vec![packs.len() as i32]));
+    for (namespace, id, version) in packs {
+        for field in [namespace, id, version] {
+            known_packs_pkt.extend(transfer_var::uint2var_int(vec![field.len() as i32]));
+            known_packs_pkt.extend(field.as_bytes());
+        }
+    }
+    known_packs_pkt = [vec![known_packs_pkt.len() as u8], known_packs_pkt].concat();
+    known_packs_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let packs = vec![(
+            "minecraft".to_string(),
+            "core".to_string(),
+            "1.20.6".to_string(),
+        )];
+        assert_eq!(
+            new(packs, false),
+            vec![
+                0x18, 0x74, 0x01, 0x09, 0x6d, 0x69, 0x6e, 0x65, 0x63, 0x72, 0x61, 0x66, 0x74, 0x04,
+                0x63, 0x6f, 0x72, 0x65, 0x06, 0x31, 0x2e, 0x32, 0x30, 0x2e, 0x36,
+            ]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod finish_configuration;
+pub mod known_packs;
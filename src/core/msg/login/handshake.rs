@@ -1,18 +1,59 @@
 use crate::core::msg::mapper;
 use crate::util::transfer_var;
 
-pub fn new(protocol_version: i32, ip: String, port: u16, login: bool) -> Vec<u8> {
+// next state (1: status)
+pub const LOGIN: i32 = 2;
+// 1.20.5+, after a transfer packet
+pub const TRANSFER: i32 = 3;
+
+pub enum Forwarding {
+    None,
+    // bungeecord legacy ip forwarding, uuid without dashes, properties as json
+    BungeeCord {
+        client_ip: String,
+        uuid: String,
+        properties: String,
+    },
+}
+
+impl Forwarding {
+    // host\0clientIp\0uuid\0propertiesJson
+    fn address(&self, host: String) -> String {
+        match self {
+            Forwarding::None => host,
+            Forwarding::BungeeCord {
+                client_ip,
+                uuid,
+                properties,
+            } => match properties.is_empty() {
+                true => format!("{}\0{}\0{}", host, client_ip, uuid),
+                false => format!("{}\0{}\0{}\0{}", host, client_ip, uuid, properties),
+            },
+        }
+    }
+}
+
+pub fn new(
+    protocol_version: i32,
+    ip: String,
+    port: u16,
+    intent: i32,
+    forwarding: &Forwarding,
+) -> Vec<u8> {
+    let ip = forwarding.address(ip);
     let mut handshake_pkt: Vec<u8> = Vec::new();
     handshake_pkt.push(mapper::HANDSHAKE);
     handshake_pkt.append(&mut transfer_var::uint2var_int(vec![protocol_version]));
     handshake_pkt.append(&mut transfer_var::uint2var_int(vec![ip.len() as i32]));
     handshake_pkt.append(&mut ip.as_bytes().to_vec());
     handshake_pkt.append(&mut port.to_be_bytes().to_vec());
-    handshake_pkt.push(match login {
-        true => 0x02,
-        false => 0x01,
-    });
-    [vec![handshake_pkt.len() as u8], handshake_pkt].concat()
+    handshake_pkt.push(intent as u8);
+    // forwarded addresses may exceed 127 bytes
+    [
+        transfer_var::uint2var_int(vec![handshake_pkt.len() as i32]),
+        handshake_pkt,
+    ]
+    .concat()
 }
 
 #[cfg(test)]
@@ -24,13 +65,32 @@ mod tests {
         let protocol_version: i32 = 763;
         let ip: String = "127.0.0.1".to_string();
         let port: u16 = 25565;
-        let login: bool = true;
 
         // 1000fb05093132372e302e302e3163dd02
         let expected: Vec<u8> = vec![
             0x10, 0x00, 0xFB, 0x05, 0x09, 0x31, 0x32, 0x37, 0x2E, 0x30, 0x2E, 0x30, 0x2E, 0x31,
             0x63, 0xDD, 0x02,
         ];
-        assert_eq!(new(protocol_version, ip, port, login), expected);
+        assert_eq!(
+            new(protocol_version, ip, port, LOGIN, &Forwarding::None),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_new_bungeecord() {
+        let forwarding = Forwarding::BungeeCord {
+            client_ip: "127.0.0.1".to_string(),
+            uuid: "b50ad385829d3141a2167e7d7539ba7f".to_string(),
+            properties: r#"[{"name":"textures","value":"e30="}]"#.repeat(3),
+        };
+        let address = forwarding.address("a.b".to_string());
+        assert_eq!(address.split('\0').count(), 4);
+        assert!(address.starts_with("a.b\x00127.0.0.1\x00b50ad385829d3141a2167e7d7539ba7f\x00["));
+
+        // a301 00 fb05 9b01 ...
+        let pkt = new(763, "a.b".to_string(), 25565, LOGIN, &forwarding);
+        assert_eq!(pkt[..6], [0xA3, 0x01, 0x00, 0xFB, 0x05, 0x9B]);
+        assert_eq!(pkt.len(), 2 + 0xA3);
     }
 }
@@ -0,0 +1,31 @@
+use crate::core::msg::mapper;
+
+// 1.20.2+, enters the configuration state
+pub fn new(compress: bool) -> Vec<u8> {
+    let mut login_acknowledged_pkt: Vec<u8> = Vec::new();
+    if compress {
+        login_acknowledged_pkt.push(0x00);
+    }
+    login_acknowledged_pkt.push(mapper::LOGIN_ACKNOWLEDGED);
+    login_acknowledged_pkt = [
+        vec![login_acknowledged_pkt.len() as u8],
+        login_acknowledged_pkt,
+    ]
+    .concat();
+    login_acknowledged_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        assert_eq!(new(true), vec![0x02, 0x00, 0x03]);
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(new(false), vec![0x01, 0x03]);
+    }
+}
@@ -1,12 +1,13 @@
 use crate::core::msg::mapper;
+use crate::util::transfer_var;
 
-pub fn new(id: u8, check: bool, compress: bool) -> Vec<u8> {
+pub fn new(id: i32, check: bool, compress: bool) -> Vec<u8> {
     let mut login_plugin_response_pkt: Vec<u8> = Vec::new();
     if compress {
         login_plugin_response_pkt.push(0x00);
     }
     login_plugin_response_pkt.push(mapper::LOGIN_PLUGIN_RESPONSE);
-    login_plugin_response_pkt.push(id);
+    login_plugin_response_pkt.extend(transfer_var::uint2var_int(vec![id]));
     login_plugin_response_pkt.push(match check {
         true => 0x01,
         false => 0x00,
@@ -19,13 +20,30 @@ pub fn new(id: u8, check: bool, compress: bool) -> Vec<u8> {
     login_plugin_response_pkt
 }
 
+// successful response with data, which may be longer than a byte length
+pub fn new_with_data(id: i32, data: Vec<u8>, compress: bool) -> Vec<u8> {
+    let mut login_plugin_response_pkt: Vec<u8> = Vec::new();
+    if compress {
+        login_plugin_response_pkt.push(0x00);
+    }
+    login_plugin_response_pkt.push(mapper::LOGIN_PLUGIN_RESPONSE);
+    login_plugin_response_pkt.extend(transfer_var::uint2var_int(vec![id]));
+    login_plugin_response_pkt.push(0x01);
+    login_plugin_response_pkt.extend(data);
+    [
+        transfer_var::uint2var_int(vec![login_plugin_response_pkt.len() as i32]),
+        login_plugin_response_pkt,
+    ]
+    .concat()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_new_compress() {
-        let id: u8 = 0x01;
+        let id: i32 = 0x01;
         let check: bool = false;
         let result = new(id, check, true);
         //0400020000
@@ -35,11 +53,19 @@ mod tests {
 
     #[test]
     fn test_new_no_compress() {
-        let id: u8 = 0x01;
+        let id: i32 = 0x01;
         let check: bool = false;
         let result = new(id, check, false);
         //03000200
         let expected: Vec<u8> = vec![0x03, 0x02, 0x01, 0x00];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_new_with_data() {
+        let result = new_with_data(0x02, vec![0x07; 200], false);
+        // 3 + 200 bytes, var int length
+        assert_eq!(result[0..5], [0xcb, 0x01, 0x02, 0x02, 0x01]);
+        assert_eq!(result.len(), 205);
+    }
 }
@@ -17,6 +17,17 @@ pub fn new(username: String, uuid: Vec<u8>) -> Vec<u8> {
     login_start_pkt
 }
 
+// 1.20.2+, the uuid is no longer optional
+pub fn new_with_uuid(username: String, uuid: Vec<u8>) -> Vec<u8> {
+    let mut login_start_pkt: Vec<u8> = Vec::new();
+    login_start_pkt.push(mapper::LOGIN_START);
+    login_start_pkt.append(&mut transfer_var::uint2var_int(vec![username.len() as i32]));
+    login_start_pkt.append(&mut username.as_bytes().to_vec());
+    login_start_pkt.append(&mut uuid.clone());
+    login_start_pkt = [vec![login_start_pkt.len() as u8], login_start_pkt].concat();
+    login_start_pkt
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,6 +43,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_new_with_uuid() {
+        let login_start_pkt = new_with_uuid("test".to_string(), vec![0x00; 16]);
+        assert_eq!(login_start_pkt.len(), 23);
+        assert_eq!(
+            login_start_pkt[0..7],
+            [0x16, 0x00, 0x04, 0x74, 0x65, 0x73, 0x74]
+        );
+    }
+
     #[test]
     fn test_new_uuid() {
         let username = "Karenina-na".to_string();
@@ -1,3 +1,4 @@
 pub mod handshake;
+pub mod login_acknowledged;
 pub mod login_plugin_response;
 pub mod login_start;
@@ -2,6 +2,7 @@
 pub const HANDSHAKE: u8 = 0x00;
 pub const LOGIN_START: u8 = 0x00;
 pub const LOGIN_PLUGIN_RESPONSE: u8 = 0x02;
+pub const LOGIN_ACKNOWLEDGED: u8 = 0x03;
 
 // play
 pub const PLUGIN_MESSAGE: u8 = 0x0D;
@@ -11,3 +12,23 @@ pub const RESPAWN: u8 = 0x07;
 pub const CHAT_MESSAGE: u8 = 0x05;
 pub const CHAT_COMMAND: u8 = 0x04;
 pub const CLIENT_INFORMATION: u8 = 0x08;
+pub const PLAYER_ACTION: u8 = 0x1D;
+pub const USE_ITEM_ON: u8 = 0x31;
+pub const SWING_ARM: u8 = 0x2F;
+pub const INTERACT: u8 = 0x10;
+pub const PLAYER_INPUT: u8 = 0x1F;
+pub const COMMAND_SUGGESTIONS_REQUEST: u8 = 0x09;
+pub const RESOURCE_PACK_RESPONSE: u8 = 0x24;
+pub const PONG: u8 = 0x20;
+
+// 1.20.2+, not in the 763 play layout
+pub const ACKNOWLEDGE_CONFIGURATION: u8 = 0x70;
+pub const FINISH_CONFIGURATION: u8 = 0x71;
+pub const CHUNK_BATCH_RECEIVED: u8 = 0x72;
+
+// 1.20.5+
+pub const COOKIE_RESPONSE: u8 = 0x73;
+pub const KNOWN_PACKS: u8 = 0x74;
+
+// 1.20.2+, play only
+pub const PING_REQUEST: u8 = 0x75;
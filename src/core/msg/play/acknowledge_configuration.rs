@@ -0,0 +1,31 @@
+use crate::core::msg::mapper;
+
+// answers start configuration, enters the configuration state
+pub fn new(compress: bool) -> Vec<u8> {
+    let mut acknowledge_configuration_pkt: Vec<u8> = Vec::new();
+    if compress {
+        acknowledge_configuration_pkt.push(0x00);
+    }
+    acknowledge_configuration_pkt.push(mapper::ACKNOWLEDGE_CONFIGURATION);
+    acknowledge_configuration_pkt = [
+        vec![acknowledge_configuration_pkt.len() as u8],
+        acknowledge_configuration_pkt,
+    ]
+    .concat();
+    acknowledge_configuration_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        assert_eq!(new(true), vec![0x02, 0x00, 0x70]);
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(new(false), vec![0x01, 0x70]);
+    }
+}
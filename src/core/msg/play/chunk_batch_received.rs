@@ -0,0 +1,27 @@
+use crate::core::msg::mapper;
+
+// 1.20.2+, chunks per tick the client can take
+pub fn new(chunks_per_tick: f32, compress: bool) -> Vec<u8> {
+    let mut chunk_batch_received_pkt: Vec<u8> = Vec::new();
+    if compress {
+        chunk_batch_received_pkt.push(0x00);
+    }
+    chunk_batch_received_pkt.push(mapper::CHUNK_BATCH_RECEIVED);
+    chunk_batch_received_pkt.extend(chunks_per_tick.to_be_bytes());
+    chunk_batch_received_pkt = [
+        vec![chunk_batch_received_pkt.len() as u8],
+        chunk_batch_received_pkt,
+    ]
+    .concat();
+    chunk_batch_received_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(new(25.0, false), vec![0x05, 0x72, 0x41, 0xc8, 0x00, 0x00]);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::core::msg::mapper;
+use crate::util;
+
+pub fn new(transaction_id: i32, text: String, compress: bool) -> Vec<u8> {
+    let mut request_pkt: Vec<u8> = Vec::new();
+    if compress {
+        request_pkt.push(0x00);
+    }
+    request_pkt.push(mapper::COMMAND_SUGGESTIONS_REQUEST);
+    // transaction id
+    request_pkt.extend(util::transfer_var::uint2var_int(vec![transaction_id]));
+    // text behind the cursor, including `/`
+    request_pkt.extend(util::transfer_var::uint2var_int(vec![text.len() as i32]));
+    request_pkt.extend(text.as_bytes());
+    request_pkt = [vec![request_pkt.len() as u8], request_pkt].concat();
+    request_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_suggestions_request() {
+        let pkt = new(7, "/tp ".to_string(), false);
+        assert_eq!(pkt, vec![0x07, 0x09, 0x07, 0x04, 0x2f, 0x74, 0x70, 0x20]);
+        let pkt = new(7, "/tp ".to_string(), true);
+        assert_eq!(
+            pkt,
+            vec![0x08, 0x00, 0x09, 0x07, 0x04, 0x2f, 0x74, 0x70, 0x20]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::core::msg::mapper;
+use crate::util::transfer_var;
+
+// None if the cookie is unknown
+pub fn new(key: String, payload: Option<Vec<u8>>, compress: bool) -> Vec<u8> {
+    let mut cookie_response_pkt: Vec<u8> = Vec::new();
+    if compress {
+        cookie_response_pkt.push(0x00);
+    }
+    cookie_response_pkt.push(mapper::COOKIE_RESPONSE);
+    cookie_response_pkt.extend(transfer_var::uint2var_int(vec![key.len() as i32]));
+    cookie_response_pkt.extend(key.as_bytes());
+    match payload {
+        Some(payload) => {
+            cookie_response_pkt.push(0x01);
+            cookie_response_pkt.extend(transfer_var::uint2var_int(vec![payload.len() as i32]));
+            cookie_response_pkt.extend(payload);
+        }
+        None => cookie_response_pkt.push(0x00),
+    }
+    cookie_response_pkt = [vec![cookie_response_pkt.len() as u8], cookie_response_pkt].concat();
+    cookie_response_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_payload() {
+        assert_eq!(
+            new("mc:session".to_string(), Some(vec![0x07, 0x08]), false),
+            vec![
+                0x10, 0x73, 0x0a, 0x6d, 0x63, 0x3a, 0x73, 0x65, 0x73, 0x73, 0x69, 0x6f, 0x6e, 0x01,
+                0x02, 0x07, 0x08,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_new_unknown() {
+        assert_eq!(
+            new("mc:session".to_string(), None, true),
+            vec![
+                0x0e, 0x00, 0x73, 0x0a, 0x6d, 0x63, 0x3a, 0x73, 0x65, 0x73, 0x73, 0x69, 0x6f, 0x6e,
+                0x00,
+            ]
+        );
+    }
+}
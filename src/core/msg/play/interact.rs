@@ -0,0 +1,60 @@
+use crate::core::msg::mapper;
+use crate::util::transfer_var;
+
+// kind: 0 interact, 1 attack, 2 interact at (target relative to the entity)
+pub fn new(
+    entity_id: i32,
+    kind: i32,
+    target: Option<(f32, f32, f32)>,
+    sneaking: bool,
+    compress: bool,
+) -> Vec<u8> {
+    let mut interact_pkt: Vec<u8> = Vec::new();
+    if compress {
+        interact_pkt.push(0x00);
+    }
+    interact_pkt.push(mapper::INTERACT);
+    interact_pkt.append(&mut transfer_var::uint2var_int(vec![entity_id, kind]));
+    if kind == 2 {
+        let (x, y, z) = target.unwrap_or((0.0, 0.0, 0.0));
+        interact_pkt.extend(x.to_be_bytes());
+        interact_pkt.extend(y.to_be_bytes());
+        interact_pkt.extend(z.to_be_bytes());
+    }
+    if kind != 1 {
+        // main hand
+        interact_pkt.push(0x00);
+    }
+    interact_pkt.push(if sneaking { 0x01 } else { 0x00 });
+    interact_pkt = [vec![interact_pkt.len() as u8], interact_pkt].concat();
+    interact_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_attack() {
+        let pkt = new(1000, 1, None, false, true);
+        assert_eq!(pkt, vec![0x06, 0x00, 0x10, 0xe8, 0x07, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_new_interact() {
+        let pkt = new(42, 0, None, false, false);
+        assert_eq!(pkt, vec![0x05, 0x10, 0x2a, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_new_interact_at() {
+        let pkt = new(42, 2, Some((0.0, 1.0, 0.0)), true, false);
+        assert_eq!(
+            pkt,
+            vec![
+                0x11, 0x10, 0x2a, 0x02, 0x00, 0x00, 0x00, 0x00, 0x3f, 0x80, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x01
+            ]
+        );
+    }
+}
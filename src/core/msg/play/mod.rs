@@ -1,7 +1,19 @@
+pub mod acknowledge_configuration;
 pub(crate) mod chat_command;
 pub(crate) mod chat_message;
+pub mod chunk_batch_received;
 pub(crate) mod client_information;
+pub(crate) mod command_suggestions_request;
 pub mod confirm_tp;
+pub mod cookie_response;
+pub(crate) mod interact;
 pub mod keep_live;
+pub(crate) mod ping_request;
+pub(crate) mod player_action;
+pub(crate) mod player_input;
 pub mod plugin_message;
+pub mod pong;
+pub(crate) mod resource_pack_response;
 pub mod respawn;
+pub(crate) mod swing_arm;
+pub(crate) mod use_item_on;
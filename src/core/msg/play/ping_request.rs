@@ -0,0 +1,33 @@
+use crate::core::msg::mapper;
+
+pub fn new(payload: i64, compress: bool) -> Vec<u8> {
+    let mut ping_pkt: Vec<u8> = Vec::new();
+    if compress {
+        ping_pkt.push(0x00);
+    }
+    ping_pkt.push(mapper::PING_REQUEST);
+    ping_pkt.extend(payload.to_be_bytes());
+    ping_pkt = [vec![ping_pkt.len() as u8], ping_pkt].concat();
+    ping_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        assert_eq!(
+            new(7, true),
+            vec![0x0A, 0x00, 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07]
+        );
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(
+            new(7, false),
+            vec![0x09, 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::core::msg::mapper;
+use crate::util::transfer_var;
+
+// status: 0 started digging, 1 cancelled digging, 2 finished digging
+pub fn new(
+    status: i32,
+    x: i32,
+    y: i32,
+    z: i32,
+    face: u8,
+    sequence: i32,
+    compress: bool,
+) -> Vec<u8> {
+    let mut player_action_pkt: Vec<u8> = Vec::new();
+    if compress {
+        player_action_pkt.push(0x00);
+    }
+    player_action_pkt.push(mapper::PLAYER_ACTION);
+    player_action_pkt.append(&mut transfer_var::uint2var_int(vec![status]));
+    player_action_pkt.extend(transfer_var::position2long(x, y, z).to_be_bytes());
+    player_action_pkt.push(face);
+    player_action_pkt.append(&mut transfer_var::uint2var_int(vec![sequence]));
+    player_action_pkt = [vec![player_action_pkt.len() as u8], player_action_pkt].concat();
+    player_action_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        let pkt = new(0, 1, 64, -2, 1, 3, true);
+        assert_eq!(
+            pkt,
+            vec![
+                0x0d, 0x00, 0x1d, 0x00, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xe0, 0x40, 0x01, 0x03
+            ]
+        );
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        let pkt = new(2, 1, 64, -2, 1, 200, false);
+        assert_eq!(
+            pkt,
+            vec![
+                0x0d, 0x1d, 0x02, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xe0, 0x40, 0x01, 0xc8, 0x01
+            ]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use crate::core::msg::mapper;
+
+// flags: 0x01 jump, 0x02 unmount
+pub fn new(sideways: f32, forward: f32, flags: u8, compress: bool) -> Vec<u8> {
+    let mut player_input_pkt: Vec<u8> = Vec::new();
+    if compress {
+        player_input_pkt.push(0x00);
+    }
+    player_input_pkt.push(mapper::PLAYER_INPUT);
+    player_input_pkt.extend(sideways.to_be_bytes());
+    player_input_pkt.extend(forward.to_be_bytes());
+    player_input_pkt.push(flags);
+    player_input_pkt = [vec![player_input_pkt.len() as u8], player_input_pkt].concat();
+    player_input_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        let pkt = new(0.0, 0.0, 0x02, true);
+        assert_eq!(
+            pkt,
+            vec![0x0b, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02]
+        );
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        let pkt = new(0.0, 1.0, 0x01, false);
+        assert_eq!(
+            pkt,
+            vec![0x0a, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x3f, 0x80, 0x00, 0x00, 0x01]
+        );
+    }
+}
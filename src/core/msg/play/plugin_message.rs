@@ -1,4 +1,5 @@
 use crate::core::msg::mapper;
+use crate::util::transfer_var::uint2var_int;
 
 pub fn new(channel: String, data: String, compress: bool) -> Vec<u8> {
     let mut plugin_message: Vec<u8> = Vec::new();
@@ -14,6 +15,23 @@ pub fn new(channel: String, data: String, compress: bool) -> Vec<u8> {
     plugin_message
 }
 
+// raw data, which may exceed 127 bytes
+pub fn new_with_data(channel: String, data: Vec<u8>, compress: bool) -> Vec<u8> {
+    let mut plugin_message: Vec<u8> = Vec::new();
+    if compress {
+        plugin_message.push(0x00);
+    }
+    plugin_message.push(mapper::PLUGIN_MESSAGE);
+    plugin_message.extend(uint2var_int(vec![channel.len() as i32]));
+    plugin_message.extend(channel.as_bytes());
+    plugin_message.extend(data);
+    [
+        uint2var_int(vec![plugin_message.len() as i32]),
+        plugin_message,
+    ]
+    .concat()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,4 +52,19 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_new_with_data() {
+        let channel = "bungeecord:main".to_string();
+        let data = b"\x00\x0aGetServers".to_vec();
+        let plugin_message = new_with_data(channel, data, false);
+        assert_eq!(
+            plugin_message,
+            vec![
+                0x1d, 0x0d, 0x0f, 0x62, 0x75, 0x6e, 0x67, 0x65, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x3a,
+                0x6d, 0x61, 0x69, 0x6e, 0x00, 0x0a, 0x47, 0x65, 0x74, 0x53, 0x65, 0x72, 0x76, 0x65,
+                0x72, 0x73
+            ]
+        );
+    }
 }
@@ -0,0 +1,27 @@
+use crate::core::msg::mapper;
+
+pub fn new(id: i32, compress: bool) -> Vec<u8> {
+    let mut pong_pkt: Vec<u8> = Vec::new();
+    if compress {
+        pong_pkt.push(0x00);
+    }
+    pong_pkt.push(mapper::PONG);
+    pong_pkt.extend(id.to_be_bytes());
+    pong_pkt = [vec![pong_pkt.len() as u8], pong_pkt].concat();
+    pong_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        assert_eq!(new(7, true), vec![0x06, 0x00, 0x20, 0x00, 0x00, 0x00, 0x07]);
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(new(7, false), vec![0x05, 0x20, 0x00, 0x00, 0x00, 0x07]);
+    }
+}
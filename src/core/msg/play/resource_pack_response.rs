@@ -0,0 +1,28 @@
+use crate::core::msg::mapper;
+
+// 0: successfully loaded, 1: declined, 2: failed download, 3: accepted
+pub fn new(result: i32, compress: bool) -> Vec<u8> {
+    let mut response: Vec<u8> = Vec::new();
+    if compress {
+        response.push(0x00);
+    }
+    response.push(mapper::RESOURCE_PACK_RESPONSE);
+    response.push(result as u8);
+    response = [vec![response.len() as u8], response].concat();
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_pack_response_compress() {
+        assert_eq!(new(3, true), vec![0x03, 0x00, 0x24, 0x03]);
+    }
+
+    #[test]
+    fn test_resource_pack_response_no_compress() {
+        assert_eq!(new(1, false), vec![0x02, 0x24, 0x01]);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::core::msg::mapper;
+
+pub fn new(compress: bool) -> Vec<u8> {
+    let mut swing_arm_pkt: Vec<u8> = Vec::new();
+    if compress {
+        swing_arm_pkt.push(0x00);
+    }
+    swing_arm_pkt.push(mapper::SWING_ARM);
+    // main hand
+    swing_arm_pkt.push(0x00);
+    swing_arm_pkt = [vec![swing_arm_pkt.len() as u8], swing_arm_pkt].concat();
+    swing_arm_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_compress() {
+        assert_eq!(new(true), vec![0x03, 0x00, 0x2f, 0x00]);
+    }
+
+    #[test]
+    fn test_new_no_compress() {
+        assert_eq!(new(false), vec![0x02, 0x2f, 0x00]);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::core::msg::mapper;
+use crate::util::transfer_var;
+
+// face: 0 bottom, 1 top, 2 north, 3 south, 4 west, 5 east
+pub fn new(
+    x: i32,
+    y: i32,
+    z: i32,
+    face: u8,
+    cursor: (f32, f32, f32),
+    sequence: i32,
+    compress: bool,
+) -> Vec<u8> {
+    let mut use_item_on_pkt: Vec<u8> = Vec::new();
+    if compress {
+        use_item_on_pkt.push(0x00);
+    }
+    use_item_on_pkt.push(mapper::USE_ITEM_ON);
+    // main hand
+    use_item_on_pkt.push(0x00);
+    use_item_on_pkt.extend(transfer_var::position2long(x, y, z).to_be_bytes());
+    use_item_on_pkt.append(&mut transfer_var::uint2var_int(vec![face as i32]));
+    use_item_on_pkt.extend(cursor.0.to_be_bytes());
+    use_item_on_pkt.extend(cursor.1.to_be_bytes());
+    use_item_on_pkt.extend(cursor.2.to_be_bytes());
+    // inside block
+    use_item_on_pkt.push(0x00);
+    use_item_on_pkt.append(&mut transfer_var::uint2var_int(vec![sequence]));
+    use_item_on_pkt = [vec![use_item_on_pkt.len() as u8], use_item_on_pkt].concat();
+    use_item_on_pkt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let pkt = new(1, 64, -2, 1, (0.5, 1.0, 0.5), 4, true);
+        assert_eq!(
+            pkt,
+            vec![
+                0x1a, 0x00, 0x31, 0x00, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xe0, 0x40, 0x01, 0x3f,
+                0x00, 0x00, 0x00, 0x3f, 0x80, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x04
+            ]
+        );
+    }
+}
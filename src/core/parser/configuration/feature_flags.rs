@@ -0,0 +1,23 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+pub fn parse(pkt: Vec<u8>) -> Vec<String> {
+    // parse
+    let mut index = 0;
+    let count = read_var_int(&pkt, &mut index);
+    (0..count).map(|_| read_string(&pkt, &mut index)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // minecraft:vanilla
+        let pkt = vec![
+            0x01, 0x11, 0x6d, 0x69, 0x6e, 0x65, 0x63, 0x72, 0x61, 0x66, 0x74, 0x3a, 0x76, 0x61,
+            0x6e, 0x69, 0x6c, 0x6c, 0x61,
+        ];
+        assert_eq!(parse(pkt), vec!["minecraft:vanilla"]);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// namespace, id, version
+pub fn parse(pkt: Vec<u8>) -> Vec<(String, String, String)> {
+    // parse
+    let mut index = 0;
+    let count = read_var_int(&pkt, &mut index);
+    let mut packs = Vec::new();
+    for _ in 0..count {
+        let namespace = read_string(&pkt, &mut index);
+        let id = read_string(&pkt, &mut index);
+        let version = read_string(&pkt, &mut index);
+        packs.push((namespace, id, version));
+    }
+
+    packs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x01, 0x09, 0x6d, 0x69, 0x6e, 0x65, 0x63, 0x72, 0x61, 0x66, 0x74, 0x04, 0x63, 0x6f,
+            0x72, 0x65, 0x06, 0x31, 0x2e, 0x32, 0x30, 0x2e, 0x36,
+        ];
+        assert_eq!(
+            parse(pkt),
+            vec![(
+                "minecraft".to_string(),
+                "core".to_string(),
+                "1.20.6".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod feature_flags;
+pub(crate) mod known_packs;
+pub(crate) mod update_tags;
@@ -0,0 +1,63 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// registry and its tag count
+pub fn parse(pkt: Vec<u8>) -> Vec<(String, i32)> {
+    // parse
+    let mut index = 0;
+    let count = read_var_int(&pkt, &mut index);
+    let mut registries = Vec::new();
+    for _ in 0..count {
+        let registry = read_string(&pkt, &mut index);
+        let tags = read_var_int(&pkt, &mut index);
+        for _ in 0..tags {
+            read_string(&pkt, &mut index);
+            let entries = read_var_int(&pkt, &mut index);
+            for _ in 0..entries {
+                read_var_int(&pkt, &mut index);
+            }
+        }
+        registries.push((registry, tags));
+    }
+
+    registries
+}
+
+// tags of the item registry and their item ids
+pub fn items(pkt: Vec<u8>) -> Vec<(String, Vec<i32>)> {
+    // parse
+    let mut index = 0;
+    let count = read_var_int(&pkt, &mut index);
+    let mut items = Vec::new();
+    for _ in 0..count {
+        let registry = read_string(&pkt, &mut index);
+        let tags = read_var_int(&pkt, &mut index);
+        for _ in 0..tags {
+            let tag = read_string(&pkt, &mut index);
+            let entries = read_var_int(&pkt, &mut index);
+            let ids = (0..entries)
+                .map(|_| read_var_int(&pkt, &mut index))
+                .collect::<Vec<i32>>();
+            if registry == "minecraft:item" {
+                items.push((tag, ids));
+            }
+        }
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // minecraft:item -> #logs [1, 2]
+        let pkt = vec![
+            0x01, 0x0e, 0x6d, 0x69, 0x6e, 0x65, 0x63, 0x72, 0x61, 0x66, 0x74, 0x3a, 0x69, 0x74,
+            0x65, 0x6d, 0x01, 0x04, 0x6c, 0x6f, 0x67, 0x73, 0x02, 0x01, 0x02,
+        ];
+        assert_eq!(parse(pkt.clone()), vec![("minecraft:item".to_string(), 1)]);
+        assert_eq!(items(pkt), vec![("logs".to_string(), vec![1, 2])]);
+    }
+}
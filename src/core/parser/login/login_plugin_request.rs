@@ -1,30 +1,16 @@
-pub fn parse(pkt: Vec<u8>) -> (u8, String, String) {
-    // id and channel
-    let id = pkt[0];
-    let channel_n = pkt[1] as usize;
-    let channel = &pkt[2..2 + channel_n]
-        .iter()
-        .map(|&c| c as char)
-        .collect::<String>();
-
-    // check data
-    if pkt[2 + channel_n] != 0x01 {
-        panic!("Invalid packet data");
-    }
+use crate::util::transfer_var::{read_string, read_var_int};
 
-    // check len
-    if pkt.len() == 3 + channel_n {
-        return (id, channel.to_string(), "".to_string());
-    }
+// message id, channel, raw data
+pub fn parse(pkt: Vec<u8>) -> (i32, String, Vec<u8>) {
+    // id and channel
+    let mut index = 0;
+    let id = read_var_int(&pkt, &mut index);
+    let channel = read_string(&pkt, &mut index);
 
-    // data
-    let data_n = pkt[3 + channel_n] as usize;
-    let data = &pkt[4 + channel_n..4 + channel_n + data_n]
-        .iter()
-        .map(|&c| c as char)
-        .collect::<String>();
+    // data, the rest of the packet
+    let data = pkt[index..].to_vec();
 
-    (id, channel.to_string(), data.to_string())
+    (id, channel, data)
 }
 
 #[cfg(test)]
@@ -46,7 +32,9 @@ mod tests {
         let (id, channel, data) = parse(pkt);
         assert_eq!(id, 0x00);
         assert_eq!(channel, "fabric-networking-api-v1:early_registration");
-        assert_eq!(data, "adventure:registered_args");
+        // one channel: adventure:registered_args
+        assert_eq!(data[0..2], [0x01, 0x19]);
+        assert_eq!(&data[2..], "adventure:registered_args".as_bytes());
     }
 
     #[test]
@@ -60,6 +48,16 @@ mod tests {
         let (id, channel, data) = parse(pkt);
         assert_eq!(id, 0x01);
         assert_eq!(channel, "fabric:custom_ingredient_sync");
-        assert_eq!(data, "");
+        assert_eq!(data, vec![0x01]);
+    }
+
+    #[test]
+    fn test_parse_not_boolean() {
+        // data not starting with 0x01 must not panic
+        let pkt = vec![0x80, 0x01, 0x03, 0x61, 0x3a, 0x62, 0x05, 0x06];
+        let (id, channel, data) = parse(pkt);
+        assert_eq!(id, 128);
+        assert_eq!(channel, "a:b");
+        assert_eq!(data, vec![0x05, 0x06]);
     }
 }
@@ -1,4 +1,5 @@
 // handshake
+pub const LOGIN_DISCONNECT: u8 = 0x00;
 pub const LOGIN_SUCCESS: u8 = 0x02;
 pub const SET_COMPRESSION: u8 = 0x03;
 pub const LOGIN_PLUGIN_REQUEST: u8 = 0x04;
@@ -15,3 +16,50 @@ pub const UPDATE_TIME: u8 = 0x5E;
 pub const SET_EXPERIENCE: u8 = 0x56;
 pub const SET_HEALTH: u8 = 0x57;
 pub const DISGUISED_CHAT_MESSAGE: u8 = 0x1B;
+pub const PLAYER_CHAT_MESSAGE: u8 = 0x35;
+pub const ACKNOWLEDGE_BLOCK_CHANGE: u8 = 0x06;
+pub const BLOCK_UPDATE: u8 = 0x0A;
+pub const SET_HELD_ITEM: u8 = 0x4D;
+pub const SET_CONTAINER_SLOT: u8 = 0x14;
+pub const LOGIN_PLAY: u8 = 0x28;
+pub const SPAWN_ENTITY: u8 = 0x01;
+pub const SPAWN_PLAYER: u8 = 0x03;
+pub const REMOVE_ENTITIES: u8 = 0x3E;
+pub const UPDATE_ENTITY_POSITION: u8 = 0x2B;
+pub const UPDATE_ENTITY_POSITION_AND_ROTATION: u8 = 0x2C;
+pub const TELEPORT_ENTITY: u8 = 0x68;
+pub const COMMANDS: u8 = 0x10;
+pub const COMMAND_SUGGESTIONS_RESPONSE: u8 = 0x0F;
+pub const BOSS_BAR: u8 = 0x0B;
+pub const SET_TITLE_TEXT: u8 = 0x5F;
+pub const SET_SUBTITLE_TEXT: u8 = 0x5D;
+pub const SET_ACTION_BAR_TEXT: u8 = 0x46;
+pub const SET_TITLES_ANIMATION: u8 = 0x60;
+pub const CLEAR_TITLES: u8 = 0x0E;
+pub const DISPLAY_OBJECTIVE: u8 = 0x51;
+pub const UPDATE_OBJECTIVES: u8 = 0x58;
+pub const UPDATE_SCORE: u8 = 0x5B;
+pub const UPDATE_TEAMS: u8 = 0x5A;
+pub const PLAYER_INFO_REMOVE: u8 = 0x39;
+pub const PLAYER_INFO_UPDATE: u8 = 0x3A;
+pub const TAB_LIST: u8 = 0x65;
+pub const RESOURCE_PACK: u8 = 0x40;
+pub const PING: u8 = 0x32;
+pub const FEATURE_FLAGS: u8 = 0x6B;
+pub const UPDATE_TAGS: u8 = 0x6E;
+
+// 1.20.2+, not in the 763 play layout
+pub const REGISTRY_DATA: u8 = 0x70;
+pub const FINISH_CONFIGURATION: u8 = 0x71;
+pub const START_CONFIGURATION: u8 = 0x72;
+pub const CHUNK_BATCH_FINISHED: u8 = 0x73;
+pub const RESET_SCORE: u8 = 0x74;
+
+// 1.20.5+, in login, configuration and play
+pub const COOKIE_REQUEST: u8 = 0x75;
+pub const STORE_COOKIE: u8 = 0x76;
+pub const TRANSFER: u8 = 0x77;
+pub const KNOWN_PACKS: u8 = 0x78;
+
+// 1.20.2+, answer to our ping request
+pub const PONG_RESPONSE: u8 = 0x79;
@@ -1,3 +1,4 @@
+pub mod configuration;
 pub mod login;
 pub mod mapper;
 pub mod play;
@@ -0,0 +1,18 @@
+use crate::util::transfer_var;
+
+pub fn parse(pkt: Vec<u8>) -> i32 {
+    // parse
+    let sequence = transfer_var::var_int2uint(pkt);
+    sequence[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![0xc8, 0x01];
+        assert_eq!(parse(pkt), 200);
+    }
+}
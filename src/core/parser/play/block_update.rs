@@ -0,0 +1,23 @@
+use crate::util::transfer_var::{long2position, read_var_int};
+
+// position, block state (0: air)
+pub fn parse(pkt: Vec<u8>) -> ((i32, i32, i32), i32) {
+    // parse
+    let position = long2position(i64::from_be_bytes(pkt[0..8].try_into().unwrap()));
+    let mut index = 8;
+    let state = read_var_int(&pkt, &mut index);
+
+    (position, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // (1, 64, -2) -> stone
+        let pkt = vec![0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xe0, 0x40, 0x01];
+        assert_eq!(parse(pkt), ((1, 64, -2), 1));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::core::boss_bar::Action;
+use crate::util;
+use crate::util::transfer_var::{read_string, read_var_int};
+
+pub fn parse(pkt: Vec<u8>) -> (Vec<u8>, Action) {
+    // parse
+    let uuid = pkt[0..16].to_vec();
+    let mut index = 16;
+    let action = read_var_int(&pkt, &mut index);
+    let f32_at = |index: usize| f32::from_be_bytes(pkt[index..index + 4].try_into().unwrap());
+    let action = match action {
+        0 => {
+            let title = util::chat::to_plain(&read_string(&pkt, &mut index));
+            let health = f32_at(index);
+            index += 4;
+            let color = read_var_int(&pkt, &mut index);
+            let division = read_var_int(&pkt, &mut index);
+            Action::Add(title, health, color, division, pkt[index])
+        }
+        1 => Action::Remove,
+        2 => Action::UpdateHealth(f32_at(index)),
+        3 => Action::UpdateTitle(util::chat::to_plain(&read_string(&pkt, &mut index))),
+        4 => {
+            let color = read_var_int(&pkt, &mut index);
+            let division = read_var_int(&pkt, &mut index);
+            Action::UpdateStyle(color, division)
+        }
+        5 => Action::UpdateFlags(pkt[index]),
+        action => Action::Unknown(action),
+    };
+
+    (uuid, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_add() {
+        let pkt = vec![
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc,
+            0xde, 0xf0, 0x00, 0x10, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a, 0x22, 0x45,
+            0x76, 0x65, 0x6e, 0x74, 0x22, 0x7d, 0x3f, 0x80, 0x00, 0x00, 0x02, 0x01, 0x01,
+        ];
+        let (uuid, action) = parse(pkt);
+        assert_eq!(uuid.len(), 16);
+        assert_eq!(action, Action::Add("Event".to_string(), 1.0, 2, 1, 0x01));
+    }
+
+    #[test]
+    fn test_parse_update_health() {
+        let pkt = vec![
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc,
+            0xde, 0xf0, 0x02, 0x3f, 0x00, 0x00, 0x00,
+        ];
+        let (_, action) = parse(pkt);
+        assert_eq!(action, Action::UpdateHealth(0.5));
+    }
+}
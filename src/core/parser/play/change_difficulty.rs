@@ -22,6 +22,6 @@ mod tests {
         let pkt = vec![0x01, 0x00];
         let (difficulty, lock) = parse(pkt);
         assert_eq!(difficulty, "easy");
-        assert_eq!(lock, false);
+        assert!(!lock);
     }
 }
@@ -0,0 +1,15 @@
+pub fn parse(pkt: Vec<u8>) -> bool {
+    // parse
+    pkt[0] == 0x01
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert!(parse(vec![0x01]));
+        assert!(!parse(vec![0x00]));
+    }
+}
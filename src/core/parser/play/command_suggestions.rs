@@ -0,0 +1,43 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+pub fn parse(pkt: Vec<u8>) -> (i32, usize, usize, Vec<String>) {
+    // parse
+    let mut index = 0;
+    let id = read_var_int(&pkt, &mut index);
+    let start = read_var_int(&pkt, &mut index) as usize;
+    let length = read_var_int(&pkt, &mut index) as usize;
+    let count = read_var_int(&pkt, &mut index);
+    let mut matches = Vec::new();
+    for _ in 0..count {
+        matches.push(read_string(&pkt, &mut index));
+        // tooltip
+        if pkt[index] == 0x01 {
+            index += 1;
+            read_string(&pkt, &mut index);
+        } else {
+            index += 1;
+        }
+    }
+
+    (id, start, length, matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // "/tp " -> Steve, Alex (with tooltip)
+        let pkt = vec![
+            0x07, 0x04, 0x00, 0x02, 0x05, 0x53, 0x74, 0x65, 0x76, 0x65, 0x00, 0x04, 0x41, 0x6c,
+            0x65, 0x78, 0x01, 0x0a, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a, 0x22, 0x22,
+            0x7d,
+        ];
+        let (id, start, length, matches) = parse(pkt);
+        assert_eq!(id, 7);
+        assert_eq!(start, 4);
+        assert_eq!(length, 0);
+        assert_eq!(matches, vec!["Steve", "Alex"]);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::core::command_tree;
+use crate::core::command_tree::{CommandTree, Node, NodeKind, Parser};
+
+// the tree comes from the server, every read is bounds checked
+fn take<'a>(pkt: &'a [u8], index: &mut usize, n: usize) -> Result<&'a [u8], String> {
+    let bytes = index
+        .checked_add(n)
+        .and_then(|end| pkt.get(*index..end))
+        .ok_or_else(|| format!("truncated at {}", index))?;
+    *index += n;
+    Ok(bytes)
+}
+
+fn byte(pkt: &[u8], index: &mut usize) -> Result<u8, String> {
+    Ok(take(pkt, index, 1)?[0])
+}
+
+fn var_int(pkt: &[u8], index: &mut usize) -> Result<i32, String> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let b = byte(pkt, index)?;
+        value |= ((b & 0x7F) as u32) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(format!("var int too big at {}", index))
+}
+
+fn string(pkt: &[u8], index: &mut usize) -> Result<String, String> {
+    let n = var_int(pkt, index)?;
+    let n = usize::try_from(n).map_err(|_| format!("negative length {}", n))?;
+    Ok(String::from_utf8_lossy(take(pkt, index, n)?).to_string())
+}
+
+fn node_index(pkt: &[u8], index: &mut usize) -> Result<usize, String> {
+    let n = var_int(pkt, index)?;
+    usize::try_from(n).map_err(|_| format!("negative node index {}", n))
+}
+
+// min / max of a number parser
+fn range<const N: usize, T>(
+    pkt: &[u8],
+    index: &mut usize,
+    from: fn([u8; N]) -> T,
+) -> Result<(Option<T>, Option<T>), String> {
+    let flags = byte(pkt, index)?;
+    let mut read = |present: bool| -> Result<Option<T>, String> {
+        if !present {
+            return Ok(None);
+        }
+        let bytes = take(pkt, index, N)?;
+        Ok(Some(from(bytes.try_into().unwrap())))
+    };
+    let min = read(flags & 0x01 != 0)?;
+    let max = read(flags & 0x02 != 0)?;
+    Ok((min, max))
+}
+
+fn parser(pkt: &[u8], index: &mut usize, protocol: i32) -> Result<Parser, String> {
+    let id = var_int(pkt, index)?;
+    let name =
+        command_tree::parser_name(protocol, id).ok_or_else(|| format!("unknown parser {}", id))?;
+    let parser = match name.as_str() {
+        "brigadier:bool" => Parser::Bool,
+        "brigadier:float" => {
+            let (min, max) = range(pkt, index, f32::from_be_bytes)?;
+            Parser::Float(min, max)
+        }
+        "brigadier:double" => {
+            let (min, max) = range(pkt, index, f64::from_be_bytes)?;
+            Parser::Double(min, max)
+        }
+        "brigadier:integer" => {
+            let (min, max) = range(pkt, index, i32::from_be_bytes)?;
+            Parser::Integer(min, max)
+        }
+        "brigadier:long" => {
+            let (min, max) = range(pkt, index, i64::from_be_bytes)?;
+            Parser::Long(min, max)
+        }
+        "brigadier:string" => Parser::String(var_int(pkt, index)?),
+        "minecraft:entity" => Parser::Entity(byte(pkt, index)?),
+        "minecraft:score_holder" => Parser::ScoreHolder(byte(pkt, index)?),
+        "minecraft:time" => {
+            let min = i32::from_be_bytes(take(pkt, index, 4)?.try_into().unwrap());
+            Parser::Time(min)
+        }
+        "minecraft:resource_or_tag"
+        | "minecraft:resource_or_tag_key"
+        | "minecraft:resource"
+        | "minecraft:resource_key" => {
+            let registry = string(pkt, index)?;
+            Parser::Resource(name, registry)
+        }
+        _ => Parser::Other(name),
+    };
+    Ok(parser)
+}
+
+// parser ids of the protocol version, error on a truncated tree or an unknown parser
+pub fn parse(pkt: Vec<u8>, protocol: i32) -> Result<CommandTree, String> {
+    // parse
+    let mut index = 0;
+    let count = var_int(&pkt, &mut index)?;
+    let mut nodes = Vec::new();
+    for _ in 0..count {
+        let flags = byte(&pkt, &mut index)?;
+        let children_n = var_int(&pkt, &mut index)?;
+        let children = (0..children_n)
+            .map(|_| node_index(&pkt, &mut index))
+            .collect::<Result<Vec<usize>, String>>()?;
+        let redirect = match flags & 0x08 {
+            0 => None,
+            _ => Some(node_index(&pkt, &mut index)?),
+        };
+        let (kind, name) = match flags & 0x03 {
+            1 => (NodeKind::Literal, string(&pkt, &mut index)?),
+            2 => {
+                let name = string(&pkt, &mut index)?;
+                (
+                    NodeKind::Argument(parser(&pkt, &mut index, protocol)?),
+                    name,
+                )
+            }
+            _ => (NodeKind::Root, String::new()),
+        };
+        let suggestions = match flags & 0x10 {
+            0 => None,
+            _ => Some(string(&pkt, &mut index)?),
+        };
+        nodes.push(Node {
+            kind,
+            name,
+            executable: flags & 0x04 != 0,
+            children,
+            redirect,
+            suggestions,
+        });
+    }
+    let root = node_index(&pkt, &mut index)?;
+
+    // walking the tree indexes the nodes
+    let linked = nodes
+        .iter()
+        .flat_map(|node| node.children.iter().chain(node.redirect.iter()))
+        .chain([&root]);
+    for i in linked {
+        if *i >= nodes.len() {
+            return Err(format!("node {} out of {}", i, nodes.len()));
+        }
+    }
+
+    Ok(CommandTree::new(nodes, root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::version::V1_20_1;
+
+    #[test]
+    fn test_parse() {
+        // root -> gamemode -> <mode: integer 0..3, ask server>, execute -> run -> root
+        let pkt = vec![
+            0x06, 0x00, 0x02, 0x01, 0x03, 0x01, 0x01, 0x02, 0x08, 0x67, 0x61, 0x6d, 0x65, 0x6d,
+            0x6f, 0x64, 0x65, 0x16, 0x00, 0x04, 0x6d, 0x6f, 0x64, 0x65, 0x03, 0x03, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x14, 0x6d, 0x69, 0x6e, 0x65, 0x63, 0x72, 0x61,
+            0x66, 0x74, 0x3a, 0x61, 0x73, 0x6b, 0x5f, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x01,
+            0x01, 0x04, 0x07, 0x65, 0x78, 0x65, 0x63, 0x75, 0x74, 0x65, 0x09, 0x00, 0x00, 0x03,
+            0x72, 0x75, 0x6e, 0x01, 0x00, 0x02, 0x74, 0x70, 0x00,
+        ];
+        let tree = parse(pkt, V1_20_1).unwrap();
+        assert_eq!(tree.root, 0);
+        assert_eq!(tree.nodes.len(), 6);
+        assert_eq!(tree.nodes[0].children, vec![1, 3]);
+        assert_eq!(tree.nodes[1].name, "gamemode");
+        assert_eq!(
+            tree.nodes[2].kind,
+            NodeKind::Argument(Parser::Integer(Some(0), Some(3)))
+        );
+        assert!(tree.nodes[2].executable);
+        assert_eq!(
+            tree.nodes[2].suggestions,
+            Some(command_tree::ASK_SERVER.to_string())
+        );
+        assert_eq!(tree.nodes[4].redirect, Some(0));
+        assert!(tree.validate("execute run gamemode 1").is_ok());
+    }
+
+    #[test]
+    fn test_parse_vanilla() {
+        // part of the tree of a vanilla 1.20.1 server: time add <time>,
+        // scoreboard players get <target> <objective>, execute positioned over <heightmap> -> execute,
+        // attribute <target> <attribute> modifier remove <uuid>
+        let pkt = vec![
+            0x13, 0x00, 0x04, 0x01, 0x04, 0x09, 0x0c, 0x01, 0x01, 0x02, 0x04, 0x74, 0x69, 0x6d,
+            0x65, 0x01, 0x01, 0x03, 0x03, 0x61, 0x64, 0x64, 0x06, 0x00, 0x04, 0x74, 0x69, 0x6d,
+            0x65, 0x28, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x05, 0x0a, 0x73, 0x63, 0x6f, 0x72,
+            0x65, 0x62, 0x6f, 0x61, 0x72, 0x64, 0x01, 0x01, 0x06, 0x07, 0x70, 0x6c, 0x61, 0x79,
+            0x65, 0x72, 0x73, 0x01, 0x01, 0x07, 0x03, 0x67, 0x65, 0x74, 0x12, 0x01, 0x08, 0x06,
+            0x74, 0x61, 0x72, 0x67, 0x65, 0x74, 0x1d, 0x00, 0x14, 0x6d, 0x69, 0x6e, 0x65, 0x63,
+            0x72, 0x61, 0x66, 0x74, 0x3a, 0x61, 0x73, 0x6b, 0x5f, 0x73, 0x65, 0x72, 0x76, 0x65,
+            0x72, 0x16, 0x00, 0x09, 0x6f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x69, 0x76, 0x65, 0x16,
+            0x14, 0x6d, 0x69, 0x6e, 0x65, 0x63, 0x72, 0x61, 0x66, 0x74, 0x3a, 0x61, 0x73, 0x6b,
+            0x5f, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x01, 0x01, 0x0a, 0x07, 0x65, 0x78, 0x65,
+            0x63, 0x75, 0x74, 0x65, 0x01, 0x01, 0x0b, 0x0a, 0x70, 0x6f, 0x73, 0x69, 0x74, 0x69,
+            0x6f, 0x6e, 0x65, 0x64, 0x01, 0x01, 0x0d, 0x04, 0x6f, 0x76, 0x65, 0x72, 0x01, 0x01,
+            0x0e, 0x09, 0x61, 0x74, 0x74, 0x72, 0x69, 0x62, 0x75, 0x74, 0x65, 0x0a, 0x00, 0x09,
+            0x09, 0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x6d, 0x61, 0x70, 0x2f, 0x02, 0x01, 0x0f,
+            0x06, 0x74, 0x61, 0x72, 0x67, 0x65, 0x74, 0x06, 0x01, 0x02, 0x01, 0x10, 0x09, 0x61,
+            0x74, 0x74, 0x72, 0x69, 0x62, 0x75, 0x74, 0x65, 0x2b, 0x13, 0x6d, 0x69, 0x6e, 0x65,
+            0x63, 0x72, 0x61, 0x66, 0x74, 0x3a, 0x61, 0x74, 0x74, 0x72, 0x69, 0x62, 0x75, 0x74,
+            0x65, 0x01, 0x01, 0x11, 0x08, 0x6d, 0x6f, 0x64, 0x69, 0x66, 0x69, 0x65, 0x72, 0x01,
+            0x01, 0x12, 0x06, 0x72, 0x65, 0x6d, 0x6f, 0x76, 0x65, 0x06, 0x00, 0x04, 0x75, 0x75,
+            0x69, 0x64, 0x30, 0x00,
+        ];
+        let tree = parse(pkt.clone(), V1_20_1).unwrap();
+        assert_eq!(tree.nodes.len(), 19);
+        assert_eq!(tree.nodes[3].kind, NodeKind::Argument(Parser::Time(0)));
+        assert_eq!(
+            tree.nodes[7].kind,
+            NodeKind::Argument(Parser::ScoreHolder(0))
+        );
+        assert_eq!(
+            tree.nodes[13].kind,
+            NodeKind::Argument(Parser::Other("minecraft:heightmap".to_string()))
+        );
+        assert_eq!(tree.nodes[13].redirect, Some(9));
+        assert_eq!(
+            tree.nodes[15].kind,
+            NodeKind::Argument(Parser::Resource(
+                "minecraft:resource".to_string(),
+                "minecraft:attribute".to_string()
+            ))
+        );
+        assert_eq!(
+            tree.nodes[18].kind,
+            NodeKind::Argument(Parser::Other("minecraft:uuid".to_string()))
+        );
+        assert!(tree.validate("time add 1d").is_ok());
+        assert!(tree.validate("scoreboard players get * kills").is_ok());
+        assert!(tree
+            .validate("attribute @s minecraft:generic.armor modifier remove 0-0-0-0-0")
+            .is_ok());
+        // the heightmap is taken, execute needs more
+        assert_eq!(
+            tree.validate("execute positioned over world_surface"),
+            Err("Incomplete command".to_string())
+        );
+
+        // every cut is an error, not a panic
+        for n in 0..pkt.len() {
+            assert!(parse(pkt[..n].to_vec(), V1_20_1).is_err(), "{}", n);
+        }
+        // uuid as an id past the registry
+        let mut unknown = pkt.clone();
+        unknown[pkt.len() - 2] = 0x63;
+        assert_eq!(
+            parse(unknown, V1_20_1),
+            Err("unknown parser 99".to_string())
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use crate::util::transfer_var::read_string;
+
+// cookie key, same in login, configuration and play
+pub fn parse(pkt: Vec<u8>) -> String {
+    // parse
+    let mut index = 0;
+    read_string(&pkt, &mut index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x0a, 0x6d, 0x63, 0x3a, 0x73, 0x65, 0x73, 0x73, 0x69, 0x6f, 0x6e,
+        ];
+        assert_eq!(parse(pkt), "mc:session");
+    }
+}
@@ -107,7 +107,7 @@ mod tests {
         assert_eq!(msg, "{\"text\":\"fuck you\"}");
         assert_eq!(chat_type, 0x02);
         assert_eq!(chat_type_name, "{\"text\":\"Server\"}");
-        assert_eq!(has_target_name, true);
+        assert!(has_target_name);
         assert_eq!(target_name, "text");
     }
 }
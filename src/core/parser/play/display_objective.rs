@@ -0,0 +1,21 @@
+use crate::util::transfer_var::read_string;
+
+pub fn parse(pkt: Vec<u8>) -> (i8, String) {
+    // parse
+    let position = pkt[0] as i8;
+    let mut index = 1;
+    let name = read_string(&pkt, &mut index);
+
+    (position, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![0x01, 0x05, 0x6b, 0x69, 0x6c, 0x6c, 0x73];
+        assert_eq!(parse(pkt), (1, "kills".to_string()));
+    }
+}
@@ -0,0 +1,15 @@
+pub fn parse(pkt: Vec<u8>) -> i32 {
+    // entity id
+    i32::from_be_bytes(pkt[0..4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![0x00, 0x00, 0x01, 0x5e, 0x00, 0x00, 0x03];
+        assert_eq!(parse(pkt), 350);
+    }
+}
@@ -1,11 +1,41 @@
+pub(crate) mod acknowledge_block_change;
+pub(crate) mod block_update;
+pub(crate) mod boss_bar;
 pub mod change_difficulty;
+pub(crate) mod clear_titles;
+pub(crate) mod command_suggestions;
+pub(crate) mod commands;
+pub(crate) mod cookie_request;
 pub(crate) mod disconnect;
 pub(crate) mod disguised_chat_message;
+pub(crate) mod display_objective;
 pub mod keep_live;
+pub(crate) mod login_play;
+pub(crate) mod ping;
+pub(crate) mod player_chat_message;
+pub(crate) mod player_info_remove;
+pub(crate) mod player_info_update;
 pub mod plugin_message;
+pub(crate) mod pong_response;
+pub(crate) mod remove_entities;
+pub(crate) mod resource_pack;
 pub mod server_data;
+pub(crate) mod set_container_slot;
 pub(crate) mod set_experience;
 pub(crate) mod set_health;
+pub(crate) mod set_held_item;
+pub(crate) mod spawn_entity;
+pub(crate) mod spawn_player;
+pub(crate) mod store_cookie;
 pub mod sync_player_position;
 pub(crate) mod system_chat_message;
+pub(crate) mod tab_list;
+pub(crate) mod teleport_entity;
+pub(crate) mod title_animation;
+pub(crate) mod title_text;
+pub(crate) mod transfer;
+pub(crate) mod update_entity_position;
+pub(crate) mod update_objectives;
+pub(crate) mod update_score;
+pub(crate) mod update_teams;
 pub(crate) mod update_time;
@@ -0,0 +1,14 @@
+pub fn parse(pkt: Vec<u8>) -> i32 {
+    // parse
+    i32::from_be_bytes([pkt[0], pkt[1], pkt[2], pkt[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(vec![0x00, 0x00, 0x01, 0x02]), 258);
+    }
+}
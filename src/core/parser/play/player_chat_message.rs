@@ -0,0 +1,115 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// sender uuid, message, unsigned content (json), filter type, chat type, sender name (json),
+// target name (json)
+pub fn parse(
+    pkt: Vec<u8>,
+) -> (
+    Vec<u8>,
+    String,
+    Option<String>,
+    i32,
+    i32,
+    String,
+    Option<String>,
+) {
+    // header: sender, index, signature
+    let sender = pkt[0..16].to_vec();
+    let mut index = 16;
+    read_var_int(&pkt, &mut index);
+    if pkt[index] == 0x01 {
+        index += 256;
+    }
+    index += 1;
+
+    // body: message, timestamp, salt
+    let message = read_string(&pkt, &mut index);
+    index += 16;
+
+    // previous messages, a signature for unknown ids
+    let previous = read_var_int(&pkt, &mut index);
+    for _ in 0..previous {
+        if read_var_int(&pkt, &mut index) == 0 {
+            index += 256;
+        }
+    }
+
+    // other: unsigned content, filter type (0: pass, 1: fully, 2: partially filtered)
+    let unsigned = match pkt[index] {
+        0x01 => {
+            index += 1;
+            Some(read_string(&pkt, &mut index))
+        }
+        _ => {
+            index += 1;
+            None
+        }
+    };
+    let filter = read_var_int(&pkt, &mut index);
+    if filter == 2 {
+        let longs = read_var_int(&pkt, &mut index) as usize;
+        index += longs * 8;
+    }
+
+    // chat formatting: chat type, sender name, target name
+    let chat_type = read_var_int(&pkt, &mut index);
+    let name = read_string(&pkt, &mut index);
+    let target = match pkt.get(index) {
+        Some(0x01) => {
+            index += 1;
+            Some(read_string(&pkt, &mut index))
+        }
+        _ => None,
+    };
+
+    (sender, message, unsigned, filter, chat_type, name, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // Steve: "hi", unsigned, no previous messages
+        let mut pkt = vec![0x11; 16];
+        pkt.extend(vec![0x00, 0x00, 0x02, 0x68, 0x69]);
+        pkt.extend(vec![0x00; 16]);
+        pkt.extend(vec![0x00, 0x00, 0x00, 0x00]);
+        pkt.extend(vec![0x10]);
+        pkt.extend(b"{\"text\":\"Steve\"}");
+        pkt.push(0x00);
+        let (sender, message, unsigned, filter, chat_type, name, target) = parse(pkt);
+        assert_eq!(sender, vec![0x11; 16]);
+        assert_eq!(message, "hi");
+        assert_eq!(unsigned, None);
+        assert_eq!(filter, 0);
+        assert_eq!(chat_type, 0);
+        assert_eq!(name, "{\"text\":\"Steve\"}");
+        assert_eq!(target, None);
+    }
+
+    #[test]
+    fn test_parse_signed() {
+        // signature, one previous message by id and one by signature, unsigned content
+        let mut pkt = vec![0x11; 16];
+        pkt.extend(vec![0x03, 0x01]);
+        pkt.extend(vec![0xaa; 256]);
+        pkt.extend(vec![0x02, 0x68, 0x69]);
+        pkt.extend(vec![0x00; 16]);
+        pkt.extend(vec![0x02, 0x05, 0x00]);
+        pkt.extend(vec![0xbb; 256]);
+        pkt.extend(vec![0x01, 0x0b]);
+        pkt.extend(b"{\"text\":\"\"}");
+        pkt.extend(vec![
+            0x00, 0x01, 0x04, 0x22, 0x41, 0x6c, 0x22, 0x01, 0x04, 0x22, 0x42, 0x6f,
+        ]);
+        pkt.push(0x22);
+        let (_, message, unsigned, _, chat_type, name, target) = parse(pkt);
+        assert_eq!(message, "hi");
+        assert_eq!(unsigned, Some("{\"text\":\"\"}".to_string()));
+        assert_eq!(chat_type, 1);
+        assert_eq!(name, "\"Al\"");
+        assert_eq!(target, Some("\"Bo\"".to_string()));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::util::transfer_var::read_var_int;
+
+pub fn parse(pkt: Vec<u8>) -> Vec<Vec<u8>> {
+    // parse
+    let mut index = 0;
+    let count = read_var_int(&pkt, &mut index) as usize;
+    (0..count)
+        .map(|i| pkt[index + i * 16..index + (i + 1) * 16].to_vec())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x01, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a,
+            0xbc, 0xde, 0xf0,
+        ];
+        assert_eq!(
+            parse(pkt),
+            vec![vec![
+                0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc,
+                0xde, 0xf0
+            ]]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::core::player_list::PlayerUpdate;
+use crate::util;
+use crate::util::transfer_var::{read_string, read_var_int};
+
+pub fn parse(pkt: Vec<u8>) -> Vec<(Vec<u8>, PlayerUpdate)> {
+    // parse
+    let actions = pkt[0];
+    let mut index = 1;
+    let count = read_var_int(&pkt, &mut index);
+    let mut players = Vec::new();
+    for _ in 0..count {
+        let uuid = pkt[index..index + 16].to_vec();
+        index += 16;
+        let mut update = PlayerUpdate::default();
+        // add player
+        if actions & 0x01 != 0 {
+            update.name = Some(read_string(&pkt, &mut index));
+            let properties = read_var_int(&pkt, &mut index);
+            for _ in 0..properties {
+                read_string(&pkt, &mut index);
+                read_string(&pkt, &mut index);
+                index += 1;
+                if pkt[index - 1] == 0x01 {
+                    read_string(&pkt, &mut index);
+                }
+            }
+        }
+        // initialize chat
+        if actions & 0x02 != 0 {
+            index += 1;
+            if pkt[index - 1] == 0x01 {
+                // session id, key expiry
+                index += 16 + 8;
+                let key = read_var_int(&pkt, &mut index) as usize;
+                index += key;
+                let signature = read_var_int(&pkt, &mut index) as usize;
+                index += signature;
+            }
+        }
+        if actions & 0x04 != 0 {
+            update.gamemode = Some(read_var_int(&pkt, &mut index));
+        }
+        if actions & 0x08 != 0 {
+            update.listed = Some(pkt[index] == 0x01);
+            index += 1;
+        }
+        if actions & 0x10 != 0 {
+            update.latency = Some(read_var_int(&pkt, &mut index));
+        }
+        if actions & 0x20 != 0 {
+            index += 1;
+            update.display_name = match pkt[index - 1] {
+                0x01 => Some(Some(util::chat::to_plain(&read_string(&pkt, &mut index)))),
+                _ => Some(None),
+            };
+        }
+        players.push((uuid, update));
+    }
+
+    players
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_add() {
+        let pkt = vec![
+            0x1d, 0x01, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78,
+            0x9a, 0xbc, 0xde, 0xf0, 0x05, 0x53, 0x74, 0x65, 0x76, 0x65, 0x01, 0x08, 0x74, 0x65,
+            0x78, 0x74, 0x75, 0x72, 0x65, 0x73, 0x03, 0x61, 0x62, 0x63, 0x01, 0x03, 0x73, 0x69,
+            0x67, 0x01, 0x01, 0x2a,
+        ];
+        let players = parse(pkt);
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].0.len(), 16);
+        assert_eq!(
+            players[0].1,
+            PlayerUpdate {
+                name: Some("Steve".to_string()),
+                gamemode: Some(1),
+                listed: Some(true),
+                latency: Some(42),
+                display_name: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_display_name() {
+        let pkt = vec![
+            0x20, 0x01, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78,
+            0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x0f, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a,
+            0x22, 0x42, 0x6f, 0x73, 0x73, 0x22, 0x7d,
+        ];
+        let players = parse(pkt);
+        assert_eq!(players[0].1.display_name, Some(Some("Boss".to_string())));
+    }
+}
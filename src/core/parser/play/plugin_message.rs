@@ -1,14 +1,11 @@
-pub fn parse(pkt: Vec<u8>) -> (String, String) {
-    // parse
-    let channel_n = pkt[0] as usize;
-    let channel = String::from_utf8(pkt[1..channel_n + 1].to_vec()).unwrap();
+use crate::util::transfer_var::read_string;
 
-    if channel != "minecraft:brand" {
-        return (channel, "".to_string());
-    }
-    let data_n = pkt[channel_n + 1] as usize;
-    let data = String::from_utf8(pkt[channel_n + 2..channel_n + 2 + data_n].to_vec()).unwrap();
-    (channel, data)
+pub fn parse(pkt: Vec<u8>) -> (String, Vec<u8>) {
+    // parse
+    let mut index = 0;
+    let channel = read_string(&pkt, &mut index);
+    // the data fills the rest of the packet
+    (channel, pkt[index..].to_vec())
 }
 
 #[cfg(test)]
@@ -23,6 +20,18 @@ mod tests {
         ];
         let (channel, data) = parse(pkt);
         assert_eq!(channel, "minecraft:brand");
-        assert_eq!(data, "Spigot");
+        assert_eq!(read_string(&data, &mut 0), "Spigot");
+    }
+
+    #[test]
+    fn test_parse_raw() {
+        // bungeecord:main GetServer
+        let pkt = vec![
+            0x0f, 0x62, 0x75, 0x6e, 0x67, 0x65, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x3a, 0x6d, 0x61,
+            0x69, 0x6e, 0x00, 0x09, 0x47, 0x65, 0x74, 0x53, 0x65, 0x72, 0x76, 0x65, 0x72,
+        ];
+        let (channel, data) = parse(pkt);
+        assert_eq!(channel, "bungeecord:main");
+        assert_eq!(data, b"\x00\x09GetServer".to_vec());
     }
 }
@@ -0,0 +1,17 @@
+pub fn parse(pkt: Vec<u8>) -> i64 {
+    // payload of our ping request
+    i64::from_be_bytes(pkt[0..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02]),
+            258
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use crate::util;
+
+pub fn parse(pkt: Vec<u8>) -> Vec<i32> {
+    // parse
+    let n_num = util::split::get_var_int_num(pkt.clone(), 1);
+    let n = util::transfer_var::var_int2uint(pkt[0..n_num[0]].to_vec())[0] as usize;
+    let ids = util::transfer_var::var_int2uint(pkt[n_num[0]..].to_vec());
+    ids[..n.min(ids.len())].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![0x03, 0x01, 0xe8, 0x07, 0x7f];
+        assert_eq!(parse(pkt), vec![1, 1000, 127]);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::util::transfer_var::read_string;
+
+// url, sha-1 hash, forced, prompt
+pub fn parse(pkt: Vec<u8>) -> (String, String, bool, Option<String>) {
+    // parse
+    let mut index = 0;
+    let url = read_string(&pkt, &mut index);
+    let hash = read_string(&pkt, &mut index);
+    let forced = pkt[index] == 0x01;
+    let has_prompt = pkt[index + 1] == 0x01;
+    index += 2;
+    let prompt = match has_prompt {
+        true => Some(read_string(&pkt, &mut index)),
+        false => None,
+    };
+
+    (url, hash, forced, prompt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x19, 0x68, 0x74, 0x74, 0x70, 0x3a, 0x2f, 0x2f, 0x31, 0x32, 0x37, 0x2e, 0x30, 0x2e,
+            0x30, 0x2e, 0x31, 0x2f, 0x70, 0x61, 0x63, 0x6b, 0x2e, 0x7a, 0x69, 0x70, 0x28, 0x61,
+            0x39, 0x39, 0x39, 0x33, 0x65, 0x33, 0x36, 0x34, 0x37, 0x30, 0x36, 0x38, 0x31, 0x36,
+            0x61, 0x62, 0x61, 0x33, 0x65, 0x32, 0x35, 0x37, 0x31, 0x37, 0x38, 0x35, 0x30, 0x63,
+            0x32, 0x36, 0x63, 0x39, 0x63, 0x64, 0x30, 0x64, 0x38, 0x39, 0x64, 0x01, 0x01, 0x11,
+            0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a, 0x22, 0x50, 0x6c, 0x65, 0x61, 0x73,
+            0x65, 0x22, 0x7d,
+        ];
+        let (url, hash, forced, prompt) = parse(pkt);
+        assert_eq!(url, "http://127.0.0.1/pack.zip");
+        assert_eq!(hash, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert!(forced);
+        assert_eq!(prompt, Some("{\"text\":\"Please\"}".to_string()));
+    }
+}
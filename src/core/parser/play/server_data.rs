@@ -23,6 +23,6 @@ mod tests {
         let (moto, icon, enforce_chat) = parse(pkt);
         assert_eq!(moto, "{\"text\":\"A Minecraft Server\"}");
         assert_eq!(icon, "".as_bytes());
-        assert_eq!(enforce_chat, true);
+        assert!(enforce_chat);
     }
 }
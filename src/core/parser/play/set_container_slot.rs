@@ -0,0 +1,36 @@
+use crate::util::transfer_var::read_var_int;
+
+// window id, slot, item id (None: empty), the count and nbt are not read
+pub fn parse(pkt: Vec<u8>) -> (i8, i16, Option<i32>) {
+    // parse
+    let window = pkt[0] as i8;
+    let mut index = 1;
+    // state id
+    read_var_int(&pkt, &mut index);
+    let slot = i16::from_be_bytes(pkt[index..index + 2].try_into().unwrap());
+    index += 2;
+    let item = match pkt[index] {
+        0x01 => {
+            index += 1;
+            Some(read_var_int(&pkt, &mut index))
+        }
+        _ => None,
+    };
+
+    (window, slot, item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // window 0, state 3, slot 36, item 820 x1
+        let pkt = vec![0x00, 0x03, 0x00, 0x24, 0x01, 0xb4, 0x06, 0x01, 0x00];
+        assert_eq!(parse(pkt), (0, 36, Some(820)));
+        // cursor, empty
+        let pkt = vec![0xff, 0x03, 0xff, 0xff, 0x00];
+        assert_eq!(parse(pkt), (-1, -1, None));
+    }
+}
@@ -0,0 +1,16 @@
+// hotbar slot, 0 to 8
+pub fn parse(pkt: Vec<u8>) -> u8 {
+    // parse
+    pkt[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![0x04];
+        assert_eq!(parse(pkt), 4);
+    }
+}
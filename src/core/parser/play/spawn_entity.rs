@@ -0,0 +1,45 @@
+use crate::util;
+
+pub fn parse(pkt: Vec<u8>) -> (i32, Vec<u8>, i32, f64, f64, f64) {
+    // parse
+    let id_num = util::split::get_var_int_num(pkt.clone(), 1);
+    let id = util::transfer_var::var_int2uint(pkt[0..id_num[0]].to_vec())[0];
+    let uuid = pkt[id_num[0]..id_num[0] + 16].to_vec();
+
+    let index = id_num[0] + 16;
+    let kind_num = util::split::get_var_int_num(pkt[index..].to_vec(), 1);
+    let kind = util::transfer_var::var_int2uint(pkt[index..index + kind_num[0]].to_vec())[0];
+
+    let index = index + kind_num[0];
+    let x = f64::from_be_bytes(pkt[index..index + 8].try_into().unwrap());
+    let y = f64::from_be_bytes(pkt[index + 8..index + 16].try_into().unwrap());
+    let z = f64::from_be_bytes(pkt[index + 16..index + 24].try_into().unwrap());
+
+    (id, uuid, kind, x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0xe8, 0x07, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78,
+            0x9a, 0xbc, 0xde, 0xf0, 0x76, 0x40, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40,
+            0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x40, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let (id, uuid, kind, x, y, z) = parse(pkt);
+        assert_eq!(id, 1000);
+        assert_eq!(
+            uuid,
+            vec![
+                0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc,
+                0xde, 0xf0
+            ]
+        );
+        assert_eq!(kind, 118);
+        assert_eq!((x, y, z), (10.0, 64.0, -5.0));
+    }
+}
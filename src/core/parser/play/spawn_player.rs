@@ -0,0 +1,35 @@
+use crate::util;
+
+pub fn parse(pkt: Vec<u8>) -> (i32, Vec<u8>, f64, f64, f64) {
+    // parse
+    let id_num = util::split::get_var_int_num(pkt.clone(), 1);
+    let id = util::transfer_var::var_int2uint(pkt[0..id_num[0]].to_vec())[0];
+    let uuid = pkt[id_num[0]..id_num[0] + 16].to_vec();
+
+    let index = id_num[0] + 16;
+    let x = f64::from_be_bytes(pkt[index..index + 8].try_into().unwrap());
+    let y = f64::from_be_bytes(pkt[index + 8..index + 16].try_into().unwrap());
+    let z = f64::from_be_bytes(pkt[index + 16..index + 24].try_into().unwrap());
+
+    (id, uuid, x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x05, 0x03, 0x7f, 0x56, 0x95, 0xcc, 0x30, 0x39, 0x64, 0x9c, 0xaf, 0x8c, 0x00, 0x0e,
+            0x10, 0x7c, 0x14, 0x40, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x50, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00,
+        ];
+        let (id, uuid, x, y, z) = parse(pkt);
+        assert_eq!(id, 5);
+        assert_eq!(uuid[0], 0x03);
+        assert_eq!(uuid[15], 0x14);
+        assert_eq!((x, y, z), (3.5, 64.0, -3.5));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// cookie key, payload
+pub fn parse(pkt: Vec<u8>) -> (String, Vec<u8>) {
+    // parse
+    let mut index = 0;
+    let key = read_string(&pkt, &mut index);
+    let len = read_var_int(&pkt, &mut index) as usize;
+    let payload = pkt[index..index + len].to_vec();
+
+    (key, payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x0a, 0x6d, 0x63, 0x3a, 0x73, 0x65, 0x73, 0x73, 0x69, 0x6f, 0x6e, 0x03, 0x01, 0x02,
+            0x03,
+        ];
+        assert_eq!(
+            parse(pkt),
+            ("mc:session".to_string(), vec![0x01, 0x02, 0x03])
+        );
+    }
+}
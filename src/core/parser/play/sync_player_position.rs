@@ -34,17 +34,6 @@ mod tests {
             0x00, 0x00, 0x00, 0x00, 0x00, 0x0e,
         ];
         let res = parse(pkt);
-        assert_eq!(
-            res,
-            (
-                3.5,
-                86.100000001490116119384765625f64,
-                -3.5,
-                0f32,
-                0f32,
-                true,
-                14
-            )
-        );
+        assert_eq!(res, (3.5, 86.10000000149012f64, -3.5, 0f32, 0f32, true, 14));
     }
 }
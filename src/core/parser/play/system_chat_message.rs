@@ -1,11 +1,10 @@
-use crate::util;
+use crate::util::transfer_var::read_string;
 
 pub fn parse(pkt: Vec<u8>) -> (String, bool) {
     // parse
-    let data_n_num = util::split::get_var_int_num(pkt.clone(), 1);
-    let data_n = util::transfer_var::var_int2uint(pkt[0..data_n_num[0]].to_vec())[0] as usize;
-    let data = String::from_utf8(pkt[1..data_n + 1].to_vec()).unwrap();
-    let is_overlay = pkt[data_n + 1] == 0x01;
+    let mut index = 0;
+    let data = read_string(&pkt, &mut index);
+    let is_overlay = pkt[index] == 0x01;
     (data, is_overlay)
 }
 
@@ -26,6 +25,16 @@ mod tests {
             data,
             "{\"color\":\"red\",\"translate\":\"chat.disabled.options\"}"
         );
-        assert_eq!(is_overlay, false);
+        assert!(!is_overlay);
+    }
+
+    #[test]
+    fn test_parse_long() {
+        // 211 bytes, length of two bytes
+        let text = format!("{{\"text\":\"{}\"}}", "a".repeat(200));
+        let pkt = [vec![0xD3, 0x01], text.as_bytes().to_vec(), vec![0x01]].concat();
+        let (data, is_overlay) = parse(pkt);
+        assert_eq!(data, text);
+        assert!(is_overlay);
     }
 }
@@ -0,0 +1,28 @@
+use crate::util::transfer_var::read_string;
+
+pub fn parse(pkt: Vec<u8>) -> (String, String) {
+    // parse
+    let mut index = 0;
+    let header = read_string(&pkt, &mut index);
+    let footer = read_string(&pkt, &mut index);
+
+    (header, footer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x14, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a, 0x22, 0x54, 0x50, 0x53, 0x3a,
+            0x20, 0x32, 0x30, 0x2e, 0x30, 0x22, 0x7d, 0x15, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74,
+            0x22, 0x3a, 0x22, 0x50, 0x69, 0x6e, 0x67, 0x3a, 0x20, 0x31, 0x32, 0x6d, 0x73, 0x22,
+            0x7d,
+        ];
+        let (header, footer) = parse(pkt);
+        assert_eq!(header, "{\"text\":\"TPS: 20.0\"}");
+        assert_eq!(footer, "{\"text\":\"Ping: 12ms\"}");
+    }
+}
@@ -0,0 +1,28 @@
+use crate::util;
+
+pub fn parse(pkt: Vec<u8>) -> (i32, f64, f64, f64) {
+    // parse
+    let id_num = util::split::get_var_int_num(pkt.clone(), 1);
+    let id = util::transfer_var::var_int2uint(pkt[0..id_num[0]].to_vec())[0];
+
+    let index = id_num[0];
+    let x = f64::from_be_bytes(pkt[index..index + 8].try_into().unwrap());
+    let y = f64::from_be_bytes(pkt[index + 8..index + 16].try_into().unwrap());
+    let z = f64::from_be_bytes(pkt[index + 16..index + 24].try_into().unwrap());
+
+    (id, x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x2a, 0x40, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x50, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0xc0, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        ];
+        assert_eq!(parse(pkt), (42, 10.0, 64.0, -5.0));
+    }
+}
@@ -0,0 +1,21 @@
+pub fn parse(pkt: Vec<u8>) -> (i32, i32, i32) {
+    // parse
+    let fade_in = i32::from_be_bytes(pkt[0..4].try_into().unwrap());
+    let stay = i32::from_be_bytes(pkt[4..8].try_into().unwrap());
+    let fade_out = i32::from_be_bytes(pkt[8..12].try_into().unwrap());
+
+    (fade_in, stay, fade_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x46, 0x00, 0x00, 0x00, 0x14,
+        ];
+        assert_eq!(parse(pkt), (10, 70, 20));
+    }
+}
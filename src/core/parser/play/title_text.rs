@@ -0,0 +1,22 @@
+use crate::util::transfer_var::read_string;
+
+// set title text, set subtitle text and set action bar text
+pub fn parse(pkt: Vec<u8>) -> String {
+    // parse
+    let mut index = 0;
+    read_string(&pkt, &mut index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x1b, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a, 0x22, 0x52, 0x6f, 0x75, 0x6e,
+            0x64, 0x20, 0x33, 0x20, 0x73, 0x74, 0x61, 0x72, 0x74, 0x69, 0x6e, 0x67, 0x22, 0x7d,
+        ];
+        assert_eq!(parse(pkt), "{\"text\":\"Round 3 starting\"}");
+    }
+}
@@ -0,0 +1,25 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// host, port
+pub fn parse(pkt: Vec<u8>) -> (String, u16) {
+    // parse
+    let mut index = 0;
+    let host = read_string(&pkt, &mut index);
+    let port = read_var_int(&pkt, &mut index) as u16;
+
+    (host, port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![
+            0x10, 0x70, 0x6c, 0x61, 0x79, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x2e,
+            0x63, 0x6f, 0x6d, 0xde, 0xc7, 0x01,
+        ];
+        assert_eq!(parse(pkt), ("play.example.com".to_string(), 25566));
+    }
+}
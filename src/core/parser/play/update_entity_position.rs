@@ -0,0 +1,29 @@
+use crate::util;
+
+// also used by update entity position and rotation
+pub fn parse(pkt: Vec<u8>) -> (i32, f64, f64, f64) {
+    // parse
+    let id_num = util::split::get_var_int_num(pkt.clone(), 1);
+    let id = util::transfer_var::var_int2uint(pkt[0..id_num[0]].to_vec())[0];
+
+    // (current * 32 - prev * 32) * 128
+    let index = id_num[0];
+    let dx = i16::from_be_bytes(pkt[index..index + 2].try_into().unwrap()) as f64 / 4096.0;
+    let dy = i16::from_be_bytes(pkt[index + 2..index + 4].try_into().unwrap()) as f64 / 4096.0;
+    let dz = i16::from_be_bytes(pkt[index + 4..index + 6].try_into().unwrap()) as f64 / 4096.0;
+
+    (id, dx, dy, dz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pkt = vec![0xe8, 0x07, 0x10, 0x00, 0xf8, 0x00, 0x00, 0x00, 0x01];
+        let (id, dx, dy, dz) = parse(pkt);
+        assert_eq!(id, 1000);
+        assert_eq!((dx, dy, dz), (1.0, -0.5, 0.0));
+    }
+}
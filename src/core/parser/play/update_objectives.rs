@@ -0,0 +1,47 @@
+use crate::core::scoreboard::Objective;
+use crate::util;
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// objective name, None if removed
+pub fn parse(pkt: Vec<u8>) -> (String, Option<Objective>) {
+    // parse
+    let mut index = 0;
+    let name = read_string(&pkt, &mut index);
+    let mode = pkt[index];
+    index += 1;
+    if mode == 1 {
+        return (name, None);
+    }
+    let display = util::chat::to_plain(&read_string(&pkt, &mut index));
+    let kind = read_var_int(&pkt, &mut index);
+
+    (name, Some(Objective { display, kind }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_create() {
+        let pkt = vec![
+            0x05, 0x6b, 0x69, 0x6c, 0x6c, 0x73, 0x00, 0x10, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74,
+            0x22, 0x3a, 0x22, 0x4b, 0x69, 0x6c, 0x6c, 0x73, 0x22, 0x7d, 0x00,
+        ];
+        let (name, objective) = parse(pkt);
+        assert_eq!(name, "kills");
+        assert_eq!(
+            objective,
+            Some(Objective {
+                display: "Kills".to_string(),
+                kind: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_remove() {
+        let pkt = vec![0x05, 0x6b, 0x69, 0x6c, 0x6c, 0x73, 0x01];
+        assert_eq!(parse(pkt), ("kills".to_string(), None));
+    }
+}
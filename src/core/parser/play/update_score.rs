@@ -0,0 +1,39 @@
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// entity, objective, score (None if removed)
+pub fn parse(pkt: Vec<u8>) -> (String, String, Option<i32>) {
+    // parse
+    let mut index = 0;
+    let entity = read_string(&pkt, &mut index);
+    let action = read_var_int(&pkt, &mut index);
+    let objective = read_string(&pkt, &mut index);
+    let score = match action {
+        0 => Some(read_var_int(&pkt, &mut index)),
+        _ => None,
+    };
+
+    (entity, objective, score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_update() {
+        let pkt = vec![
+            0x05, 0x53, 0x74, 0x65, 0x76, 0x65, 0x00, 0x05, 0x6b, 0x69, 0x6c, 0x6c, 0x73, 0xac,
+            0x02,
+        ];
+        assert_eq!(
+            parse(pkt),
+            ("Steve".to_string(), "kills".to_string(), Some(300))
+        );
+    }
+
+    #[test]
+    fn test_parse_remove() {
+        let pkt = vec![0x05, 0x53, 0x74, 0x65, 0x76, 0x65, 0x01, 0x00];
+        assert_eq!(parse(pkt), ("Steve".to_string(), String::new(), None));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::core::scoreboard::{TeamAction, TeamInfo};
+use crate::util;
+use crate::util::transfer_var::{read_string, read_var_int};
+
+fn team_info(pkt: &[u8], index: &mut usize) -> TeamInfo {
+    let display = util::chat::to_plain(&read_string(pkt, index));
+    // friendly flags
+    *index += 1;
+    // name tag visibility, collision rule
+    read_string(pkt, index);
+    read_string(pkt, index);
+    let color = read_var_int(pkt, index);
+    let prefix = util::chat::to_plain(&read_string(pkt, index));
+    let suffix = util::chat::to_plain(&read_string(pkt, index));
+    TeamInfo {
+        display,
+        prefix,
+        suffix,
+        color,
+    }
+}
+
+fn entities(pkt: &[u8], index: &mut usize) -> Vec<String> {
+    let count = read_var_int(pkt, index);
+    (0..count).map(|_| read_string(pkt, index)).collect()
+}
+
+pub fn parse(pkt: Vec<u8>) -> (String, TeamAction) {
+    // parse
+    let mut index = 0;
+    let name = read_string(&pkt, &mut index);
+    let mode = pkt[index] as i8;
+    index += 1;
+    let action = match mode {
+        0 => {
+            let info = team_info(&pkt, &mut index);
+            TeamAction::Create(info, entities(&pkt, &mut index))
+        }
+        1 => TeamAction::Remove,
+        2 => TeamAction::Update(team_info(&pkt, &mut index)),
+        3 => TeamAction::AddEntities(entities(&pkt, &mut index)),
+        4 => TeamAction::RemoveEntities(entities(&pkt, &mut index)),
+        mode => TeamAction::Unknown(mode),
+    };
+
+    (name, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_create() {
+        let pkt = vec![
+            0x03, 0x72, 0x65, 0x64, 0x00, 0x0e, 0x7b, 0x22, 0x74, 0x65, 0x78, 0x74, 0x22, 0x3a,
+            0x22, 0x52, 0x65, 0x64, 0x22, 0x7d, 0x01, 0x06, 0x61, 0x6c, 0x77, 0x61, 0x79, 0x73,
+            0x06, 0x61, 0x6c, 0x77, 0x61, 0x79, 0x73, 0x0c, 0x0f, 0x7b, 0x22, 0x74, 0x65, 0x78,
+            0x74, 0x22, 0x3a, 0x22, 0x5b, 0x52, 0x5d, 0x20, 0x22, 0x7d, 0x02, 0x22, 0x22, 0x02,
+            0x05, 0x53, 0x74, 0x65, 0x76, 0x65, 0x04, 0x41, 0x6c, 0x65, 0x78,
+        ];
+        let (name, action) = parse(pkt);
+        assert_eq!(name, "red");
+        assert_eq!(
+            action,
+            TeamAction::Create(
+                TeamInfo {
+                    display: "Red".to_string(),
+                    prefix: "[R] ".to_string(),
+                    suffix: String::new(),
+                    color: 12,
+                },
+                vec!["Steve".to_string(), "Alex".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_add_entities() {
+        let pkt = vec![0x03, 0x72, 0x65, 0x64, 0x03, 0x01, 0x03, 0x42, 0x6f, 0x62];
+        assert_eq!(
+            parse(pkt),
+            (
+                "red".to_string(),
+                TeamAction::AddEntities(vec!["Bob".to_string()])
+            )
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+// fields of a player info update, None if not in the update
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlayerUpdate {
+    pub name: Option<String>,
+    pub gamemode: Option<i32>,
+    pub listed: Option<bool>,
+    pub latency: Option<i32>,
+    pub display_name: Option<Option<String>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub gamemode: i32,
+    pub listed: bool,
+    pub latency: i32,
+    pub display_name: Option<String>,
+}
+
+#[derive(Default)]
+pub struct PlayerList {
+    players: HashMap<Vec<u8>, Player>,
+}
+
+impl PlayerList {
+    pub fn new() -> PlayerList {
+        PlayerList::default()
+    }
+
+    pub fn update(&mut self, uuid: Vec<u8>, update: PlayerUpdate) {
+        let player = self.players.entry(uuid).or_default();
+        if let Some(name) = update.name {
+            player.name = name;
+        }
+        if let Some(gamemode) = update.gamemode {
+            player.gamemode = gamemode;
+        }
+        if let Some(listed) = update.listed {
+            player.listed = listed;
+        }
+        if let Some(latency) = update.latency {
+            player.latency = latency;
+        }
+        if let Some(display_name) = update.display_name {
+            player.display_name = display_name;
+        }
+    }
+
+    pub fn remove(&mut self, uuids: Vec<Vec<u8>>) {
+        for uuid in uuids {
+            self.players.remove(&uuid);
+        }
+    }
+
+    // listed players sorted by name
+    pub fn listed(&self) -> Vec<&Player> {
+        let mut players = self
+            .players
+            .values()
+            .filter(|p| p.listed)
+            .collect::<Vec<&Player>>();
+        players.sort_by(|a, b| a.name.cmp(&b.name));
+        players
+    }
+
+    pub fn clear(&mut self) {
+        self.players.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let mut list = PlayerList::new();
+        list.update(
+            vec![0x01],
+            PlayerUpdate {
+                name: Some("Steve".to_string()),
+                listed: Some(true),
+                ..Default::default()
+            },
+        );
+        list.update(
+            vec![0x02],
+            PlayerUpdate {
+                name: Some("Alex".to_string()),
+                listed: Some(true),
+                ..Default::default()
+            },
+        );
+        list.update(
+            vec![0x01],
+            PlayerUpdate {
+                latency: Some(42),
+                ..Default::default()
+            },
+        );
+        let names = list
+            .listed()
+            .iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["Alex", "Steve"]);
+        assert_eq!(list.listed()[1].latency, 42);
+
+        list.remove(vec![vec![0x02]]);
+        assert_eq!(list.listed().len(), 1);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::core::boss_bar::BossBar;
+use crate::core::entity::Entity;
+use crate::core::event::Position;
+use crate::core::latency::LatencyWindow;
+use crate::core::tps;
+use console::style;
+use prettytable::format::consts::FORMAT_BOX_CHARS;
+use prettytable::{row, Table};
+use tokio::time::Duration;
+
+// a line of the sidebar, display is the name with its team prefix and color
+#[derive(Clone, Debug, PartialEq)]
+pub struct Score {
+    pub name: String,
+    pub display: String,
+    pub score: i32,
+}
+
+// a listed player, display is the tab list name or the name with its team
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListedPlayer {
+    pub name: String,
+    pub display: String,
+    pub gamemode: i32,
+    pub latency: i32,
+}
+
+// client -> console, answer of a query or a completion
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryResponse {
+    // not connected
+    Offline,
+    Position(Option<Position>),
+    Server {
+        // motd, enforces secure chat
+        data: Option<(String, bool)>,
+        // since the last packet and keep alive
+        last_packet: Option<Duration>,
+        last_keep_alive: Option<Duration>,
+        // header, footer
+        tab_list: Option<(String, String)>,
+    },
+    // world age, time of day, day
+    Time(Option<(i64, i64, i64)>),
+    // averages of tps::WINDOWS
+    Tps([Option<f64>; 3]),
+    // bar, level, total
+    Exp(Option<(f32, i32, i32)>),
+    // health, food, saturation
+    Health(Option<(f32, i32, f32)>),
+    // nearest first with the distance, none without a position
+    Entities(Option<Vec<(Entity, f64)>>),
+    BossBars(Vec<BossBar>),
+    // title and the top scores
+    Scoreboard(Option<(String, Vec<Score>)>),
+    Players {
+        // tab list header and footer
+        tab_list: Option<(String, String)>,
+        players: Vec<ListedPlayer>,
+    },
+    Channels {
+        registered: Vec<String>,
+        server: Vec<String>,
+        // channel, data
+        unknown: Vec<(String, Vec<u8>)>,
+    },
+    Latency(Vec<LatencyWindow>),
+    Completions(Vec<String>),
+}
+
+impl QueryResponse {
+    // styled lines for the console
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            QueryResponse::Offline => vec![style("Not connected").red().to_string()],
+            QueryResponse::Position(position) => vec![match position {
+                Some(p) => format!(
+                    "x: {}, y: {}, z: {}, yaw: {}, pitch: {}",
+                    style(p.x).green(),
+                    style(p.y).green(),
+                    style(p.z).green(),
+                    style(p.yaw).cyan(),
+                    style(p.pitch).cyan()
+                ),
+                None => style("No position").red().to_string(),
+            }],
+            QueryResponse::Server {
+                data,
+                last_packet,
+                last_keep_alive,
+                tab_list,
+            } => {
+                let data = match data {
+                    Some((motd, enforce_chat)) => format!(
+                        "motor: {}, enforce chat: {}",
+                        style(motd).white(),
+                        style(enforce_chat).red()
+                    ),
+                    None => style("No server data").red().to_string(),
+                };
+                let since = |elapsed: &Option<Duration>| match elapsed {
+                    Some(elapsed) => format!("{:.1}s ago", elapsed.as_secs_f64()),
+                    None => "never".to_string(),
+                };
+                let connection = format!(
+                    "last packet: {}, last keep alive: {}",
+                    style(since(last_packet)).white(),
+                    style(since(last_keep_alive)).white()
+                );
+                let mut lines = vec![data, connection];
+                if let Some((header, footer)) = tab_list {
+                    lines.push(format!("tab list header: {}", style(header).white()));
+                    lines.push(format!("tab list footer: {}", style(footer).white()));
+                }
+                lines
+            }
+            QueryResponse::Time(time) => vec![match time {
+                Some((word_age, time_of_day, day)) => format!(
+                    "word age: {}, day: {}, time of this day: {}",
+                    style(word_age).white(),
+                    style(day).cyan(),
+                    style(time_of_day).green()
+                ),
+                None => style("No time").red().to_string(),
+            }],
+            QueryResponse::Tps(averages) => {
+                if averages[0].is_none() {
+                    return vec![style("No TPS").red().to_string()];
+                }
+                let values = averages
+                    .iter()
+                    .map(|tps| match tps {
+                        Some(tps) if *tps >= 18.0 => {
+                            style(format!("{:.2}", tps)).green().to_string()
+                        }
+                        Some(tps) if *tps >= 15.0 => {
+                            style(format!("{:.2}", tps)).yellow().to_string()
+                        }
+                        Some(tps) => style(format!("{:.2}", tps)).red().to_string(),
+                        None => style("-").white().to_string(),
+                    })
+                    .collect::<Vec<String>>();
+                vec![format!(
+                    "TPS from last {}: {}",
+                    tps::WINDOWS
+                        .iter()
+                        .map(|window| format!("{}m", window.as_secs() / 60))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    values.join(", ")
+                )]
+            }
+            QueryResponse::Exp(exp) => vec![match exp {
+                Some((exp_bar, level, exp_level)) => format!(
+                    "exp bar: {:.2}, level: {}, exp: {}",
+                    style(exp_bar).white(),
+                    style(level).green(),
+                    style(exp_level).white()
+                ),
+                None => style("No exp").red().to_string(),
+            }],
+            QueryResponse::Health(health) => vec![match health {
+                Some((health, food, saturation)) => format!(
+                    "health: {}, food: {}, saturation: {}",
+                    style(health).red(),
+                    style(food).green(),
+                    style(saturation).yellow()
+                ),
+                None => style("No health").red().to_string(),
+            }],
+            QueryResponse::Entities(entities) => match entities {
+                None => vec![style("No position").red().to_string()],
+                Some(entities) if entities.is_empty() => {
+                    vec![style("No entities").red().to_string()]
+                }
+                Some(entities) => entities
+                    .iter()
+                    .map(|(e, distance)| {
+                        format!(
+                            "id: {}, type: {}, distance: {}",
+                            style(e.id).cyan(),
+                            style(e.kind.clone()).yellow(),
+                            style(format!("{:.2}", distance)).green()
+                        )
+                    })
+                    .collect(),
+            },
+            QueryResponse::BossBars(bars) => match bars.is_empty() {
+                true => vec![style("No boss bars").red().to_string()],
+                false => bars.iter().map(|bar| bar.render()).collect(),
+            },
+            QueryResponse::Scoreboard(sidebar) => {
+                let (title, scores) = match sidebar {
+                    Some(sidebar) => sidebar,
+                    None => return vec![style("No scoreboard").red().to_string()],
+                };
+                let mut t = Table::new();
+                t.set_format(*FORMAT_BOX_CHARS);
+                t.set_titles(row![style(title).yellow(), ""]);
+                for score in scores {
+                    t.add_row(row![score.display, style(score.score).red()]);
+                }
+                vec![t.to_string()]
+            }
+            QueryResponse::Players { tab_list, players } => {
+                if players.is_empty() {
+                    return vec![style("No players").red().to_string()];
+                }
+                let (header, footer) = tab_list.clone().unwrap_or_default();
+                let mut t = Table::new();
+                t.set_format(*FORMAT_BOX_CHARS);
+                t.set_titles(row![
+                    style("Player").blue(),
+                    style("Gamemode").white(),
+                    style("Latency").white()
+                ]);
+                for player in players {
+                    let gamemode = match player.gamemode {
+                        0 => "survival",
+                        1 => "creative",
+                        2 => "adventure",
+                        3 => "spectator",
+                        _ => "unknown",
+                    };
+                    t.add_row(row![
+                        player.display,
+                        gamemode,
+                        style(format!("{}ms", player.latency)).green()
+                    ]);
+                }
+                [header, t.to_string(), footer]
+                    .into_iter()
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            }
+            QueryResponse::Channels {
+                registered,
+                server,
+                unknown,
+            } => {
+                let mut lines = vec![
+                    format!("Registered: {}", registered.join(", ")),
+                    format!("Server: {}", server.join(", ")),
+                ];
+                for (channel, data) in unknown {
+                    lines.push(format!("{}: {}", channel, hex::encode(data)));
+                }
+                lines
+            }
+            QueryResponse::Latency(windows) => windows
+                .iter()
+                .map(|window| match &window.stats {
+                    Some(stats) => format!(
+                        "{}: min {}, avg {}, max {}, p95 {} {}",
+                        window.name,
+                        ms(stats.min),
+                        ms(stats.avg),
+                        ms(stats.max),
+                        ms(stats.p95),
+                        window.sparkline
+                    ),
+                    None => format!("{}: no samples", window.name),
+                })
+                .collect(),
+            QueryResponse::Completions(candidates) => candidates.clone(),
+        }
+    }
+}
+
+fn ms(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let health = QueryResponse::Health(Some((7.5, 12, 0.0)));
+        assert!(console::strip_ansi_codes(&health.lines()[0]).contains("health: 7.5"));
+        assert_eq!(
+            console::strip_ansi_codes(&QueryResponse::Health(None).lines()[0]),
+            "No health"
+        );
+
+        let channels = QueryResponse::Channels {
+            registered: vec!["a:a".to_string()],
+            server: vec!["a:a".to_string(), "c:c".to_string()],
+            unknown: vec![("other:main".to_string(), vec![0xab, 0xcd])],
+        };
+        assert_eq!(
+            channels.lines(),
+            vec!["Registered: a:a", "Server: a:a, c:c", "other:main: abcd"]
+        );
+
+        let latency = QueryResponse::Latency(vec![LatencyWindow {
+            name: "Keep alive".to_string(),
+            stats: None,
+            sparkline: String::new(),
+        }]);
+        assert_eq!(latency.lines(), vec!["Keep alive: no samples"]);
+    }
+}
@@ -0,0 +1,138 @@
+use rand::Rng;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    Never,
+    // fixed delay, zero to retry immediately
+    After(Duration),
+    Backoff,
+}
+
+// first rule whose pattern is in the disconnect reason (case insensitive) decides
+pub struct Rule {
+    pattern: String,
+    action: Action,
+}
+
+impl Rule {
+    pub fn new(pattern: &str, action: Action) -> Rule {
+        Rule {
+            pattern: pattern.to_lowercase(),
+            action,
+        }
+    }
+}
+
+pub struct Reconnect {
+    base: Duration,
+    max: Duration,
+    max_attempts: u32,
+    rules: Vec<Rule>,
+    // failed attempts in a row
+    attempt: u32,
+}
+
+impl Reconnect {
+    pub fn new(base: Duration, max: Duration, max_attempts: u32, rules: Vec<Rule>) -> Reconnect {
+        Reconnect {
+            base,
+            max,
+            max_attempts,
+            rules,
+            attempt: 0,
+        }
+    }
+
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    // delay before the next attempt, none to give up
+    pub fn next(&mut self, reason: Option<&str>) -> Option<Duration> {
+        if self.attempt >= self.max_attempts {
+            return None;
+        }
+        let delay = match self.action(reason) {
+            Action::Never => return None,
+            Action::After(delay) => delay,
+            Action::Backoff => self.backoff(),
+        };
+        self.attempt += 1;
+        Some(delay)
+    }
+
+    // joined the server, the next disconnect starts a new series
+    pub fn success(&mut self) {
+        self.attempt = 0;
+    }
+
+    fn action(&self, reason: Option<&str>) -> Action {
+        let reason = reason.unwrap_or_default().to_lowercase();
+        self.rules
+            .iter()
+            .find(|rule| reason.contains(&rule.pattern))
+            .map_or(Action::Backoff, |rule| rule.action.clone())
+    }
+
+    // base * 2^attempt up to max, with jitter in [delay / 2, delay]
+    fn backoff(&self) -> Duration {
+        let delay = self
+            .base
+            .saturating_mul(2u32.saturating_pow(self.attempt))
+            .min(self.max);
+        let jitter = rand::thread_rng().gen_range(0.5..=1.0);
+        delay.mul_f64(jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reconnect() -> Reconnect {
+        Reconnect::new(
+            Duration::from_secs(1),
+            Duration::from_secs(5),
+            5,
+            vec![
+                Rule::new("banned", Action::Never),
+                Rule::new("Server restarting", Action::After(Duration::from_secs(60))),
+                Rule::new("timed out", Action::After(Duration::ZERO)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut reconnect = reconnect();
+        assert_eq!(
+            reconnect.next(Some("multiplayer.disconnect.banned.reason [cheating]")),
+            None
+        );
+        assert_eq!(
+            reconnect.next(Some("SERVER RESTARTING, back soon")),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(reconnect.next(Some("Timed out")), Some(Duration::ZERO));
+        assert_eq!(reconnect.attempt(), 2);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut reconnect = reconnect();
+        // 1s, 2s, 4s, 5s, 5s
+        for max in [1, 2, 4, 5, 5] {
+            let delay = reconnect.next(None).unwrap();
+            let max = Duration::from_secs(max);
+            assert!(delay <= max && delay >= max / 2, "{:?}", delay);
+        }
+        assert_eq!(reconnect.next(None), None);
+        reconnect.success();
+        assert!(reconnect.next(None).is_some());
+    }
+}
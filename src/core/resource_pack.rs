@@ -0,0 +1,222 @@
+use log::{debug, info};
+use sha1::{Digest, Sha1};
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+// resource pack response results
+pub const SUCCESSFULLY_LOADED: i32 = 0;
+pub const DECLINED: i32 = 1;
+pub const FAILED_DOWNLOAD: i32 = 2;
+pub const ACCEPTED: i32 = 3;
+
+// largest pack accepted, as the vanilla client (250 MiB)
+const MAX_SIZE: u64 = 250 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Policy {
+    Accept,
+    Decline,
+    // download, verify and cache
+    Download(String),
+}
+
+impl Policy {
+    pub fn new(policy: &str, cache: String) -> Policy {
+        match policy {
+            "decline" => Policy::Decline,
+            "download" => Policy::Download(cache),
+            _ => Policy::Accept,
+        }
+    }
+}
+
+pub fn sha1_hex(data: &[u8]) -> String {
+    hex::encode(Sha1::digest(data))
+}
+
+// sha-1 of a file, read in chunks
+async fn file_sha1_hex(path: &PathBuf) -> Option<String> {
+    let mut file = fs::File::open(path).await.ok()?;
+    let mut hasher = Sha1::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        match file.read(&mut buf).await.ok()? {
+            0 => break,
+            n => hasher.update(&buf[..n]),
+        }
+    }
+    Some(hex::encode(hasher.finalize()))
+}
+
+// download the pack into the cache, named by its hash
+pub async fn download(url: &str, hash: &str, cache: &str) -> Result<PathBuf, String> {
+    download_limited(url, hash, cache, MAX_SIZE).await
+}
+
+async fn download_limited(
+    url: &str,
+    hash: &str,
+    cache: &str,
+    max_size: u64,
+) -> Result<PathBuf, String> {
+    let hash = hash.to_lowercase();
+    let name = match hash.is_empty() {
+        true => sha1_hex(url.as_bytes()),
+        false => hash.clone(),
+    };
+    let path = PathBuf::from(cache).join(format!("{}.zip", name));
+
+    // cached
+    if !hash.is_empty() && file_sha1_hex(&path).await.as_deref() == Some(hash.as_str()) {
+        info!("Resource pack cached: {}", path.display());
+        return Ok(path);
+    }
+
+    let mut res = match reqwest::get(url).await {
+        Ok(res) if res.status().is_success() => res,
+        Ok(res) => return Err(format!("download resource pack failed: {}", res.status())),
+        Err(e) => return Err(format!("download resource pack failed: {}", e)),
+    };
+    if let Some(len) = res.content_length() {
+        if len > max_size {
+            return Err(format!(
+                "resource pack too large: {} bytes, max {}",
+                len, max_size
+            ));
+        }
+    }
+
+    match fs::create_dir_all(cache).await {
+        Ok(_) => {}
+        Err(e) => return Err(format!("create cache directory failed: {}", e)),
+    }
+    // into a part file, renamed once verified
+    let part = path.with_extension("zip.part");
+    let result = write_part(&mut res, &part, max_size).await;
+    let actual = match result {
+        Ok(actual) => actual,
+        Err(e) => {
+            let _ = fs::remove_file(&part).await;
+            return Err(e);
+        }
+    };
+
+    // verify
+    if !hash.is_empty() && actual != hash {
+        let _ = fs::remove_file(&part).await;
+        return Err(format!(
+            "resource pack hash mismatch: expected {}, got {}",
+            hash, actual
+        ));
+    }
+    match fs::rename(&part, &path).await {
+        Ok(_) => Ok(path),
+        Err(e) => Err(format!("write resource pack failed: {}", e)),
+    }
+}
+
+// streams the body into the file, returns its sha-1
+async fn write_part(
+    res: &mut reqwest::Response,
+    part: &PathBuf,
+    max_size: u64,
+) -> Result<String, String> {
+    let mut file = match fs::File::create(part).await {
+        Ok(file) => file,
+        Err(e) => return Err(format!("write resource pack failed: {}", e)),
+    };
+    let mut hasher = Sha1::new();
+    let mut size = 0;
+    loop {
+        let chunk = match res.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => return Err(format!("read resource pack failed: {}", e)),
+        };
+        size += chunk.len() as u64;
+        if size > max_size {
+            return Err(format!("resource pack too large: over {} bytes", max_size));
+        }
+        hasher.update(&chunk);
+        match file.write_all(&chunk).await {
+            Ok(_) => {}
+            Err(e) => return Err(format!("write resource pack failed: {}", e)),
+        }
+    }
+    match file.flush().await {
+        Ok(_) => {}
+        Err(e) => return Err(format!("write resource pack failed: {}", e)),
+    }
+    debug!("Resource pack downloaded: {} bytes", size);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    // serve one body over http, returns the url
+    async fn serve(body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let header = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(header.as_bytes()).await;
+                let _ = stream.write_all(body).await;
+            }
+        });
+        format!("http://{}/pack.zip", addr)
+    }
+
+    #[test]
+    fn test_sha1_hex() {
+        assert_eq!(sha1_hex(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[tokio::test]
+    async fn test_download() {
+        let url = serve(b"abc").await;
+        let cache = std::env::temp_dir().join(format!("mc-client-pack-{}", std::process::id()));
+        let cache = cache.to_str().unwrap().to_string();
+
+        // verified and cached
+        let path = download(&url, "A9993E364706816ABA3E25717850C26C9CD0D89D", &cache)
+            .await
+            .unwrap();
+        assert_eq!(fs::read(&path).await.unwrap(), b"abc");
+
+        // hash mismatch
+        let res = download(&url, "0000000000000000000000000000000000000000", &cache).await;
+        assert!(res.is_err());
+
+        // over the size limit, nothing left behind
+        let res = download_limited(&url, "", &cache, 2).await;
+        assert!(res.is_err());
+        let mut entries = fs::read_dir(&cache).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name().into_string().unwrap());
+        }
+        assert_eq!(names, vec!["a9993e364706816aba3e25717850c26c9cd0d89d.zip"]);
+
+        fs::remove_dir_all(&cache).await.unwrap();
+    }
+
+    #[test]
+    fn test_policy() {
+        assert_eq!(Policy::new("accept", "cache".to_string()), Policy::Accept);
+        assert_eq!(Policy::new("decline", "cache".to_string()), Policy::Decline);
+        assert_eq!(
+            Policy::new("download", "cache".to_string()),
+            Policy::Download("cache".to_string())
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use console::{style, StyledObject};
+use std::collections::HashMap;
+
+// display slot of the sidebar, 3 + color for the team sidebars
+pub const SIDEBAR: i8 = 1;
+// lines shown on the sidebar
+const SIDEBAR_LINES: usize = 15;
+
+// chat color code to 256-color index
+const COLORS: [u8; 16] = [0, 4, 2, 6, 1, 5, 3, 7, 8, 12, 10, 14, 9, 13, 11, 15];
+
+// paint text with a chat color code (0-15), others are left plain
+pub fn paint<D>(text: D, color: i32) -> StyledObject<D> {
+    match COLORS.get(color as usize) {
+        Some(index) if color >= 0 => style(text).color256(*index),
+        _ => style(text),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Objective {
+    pub display: String,
+    // 0: integer, 1: hearts
+    pub kind: i32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TeamInfo {
+    pub display: String,
+    pub prefix: String,
+    pub suffix: String,
+    pub color: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TeamAction {
+    Create(TeamInfo, Vec<String>),
+    Remove,
+    Update(TeamInfo),
+    AddEntities(Vec<String>),
+    RemoveEntities(Vec<String>),
+    Unknown(i8),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Team {
+    pub info: TeamInfo,
+    pub members: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct Scoreboard {
+    objectives: HashMap<String, Objective>,
+    // objective -> entity -> score
+    scores: HashMap<String, HashMap<String, i32>>,
+    // slot -> objective
+    slots: HashMap<i8, String>,
+    teams: HashMap<String, Team>,
+}
+
+impl Scoreboard {
+    pub fn new() -> Scoreboard {
+        Scoreboard::default()
+    }
+
+    pub fn display(&mut self, slot: i8, objective: String) {
+        if objective.is_empty() {
+            self.slots.remove(&slot);
+        } else {
+            self.slots.insert(slot, objective);
+        }
+    }
+
+    // None removes the objective
+    pub fn update_objective(&mut self, name: String, objective: Option<Objective>) {
+        match objective {
+            Some(objective) => {
+                self.objectives.insert(name, objective);
+            }
+            None => {
+                self.objectives.remove(&name);
+                self.scores.remove(&name);
+                self.slots.retain(|_, o| *o != name);
+            }
+        }
+    }
+
+    // None resets the score, an empty objective resets it on all objectives
+    pub fn update_score(&mut self, entity: String, objective: String, score: Option<i32>) {
+        match score {
+            Some(score) => {
+                self.scores
+                    .entry(objective)
+                    .or_default()
+                    .insert(entity, score);
+            }
+            None if objective.is_empty() => {
+                self.scores.values_mut().for_each(|s| {
+                    s.remove(&entity);
+                });
+            }
+            None => {
+                if let Some(scores) = self.scores.get_mut(&objective) {
+                    scores.remove(&entity);
+                }
+            }
+        }
+    }
+
+    pub fn update_team(&mut self, name: String, action: TeamAction) {
+        match action {
+            TeamAction::Create(info, members) => {
+                self.teams.insert(name, Team { info, members });
+            }
+            TeamAction::Remove => {
+                self.teams.remove(&name);
+            }
+            TeamAction::Update(info) => {
+                if let Some(team) = self.teams.get_mut(&name) {
+                    team.info = info;
+                }
+            }
+            TeamAction::AddEntities(members) => {
+                // an entity is in one team at most
+                self.teams
+                    .values_mut()
+                    .for_each(|t| t.members.retain(|m| !members.contains(m)));
+                if let Some(team) = self.teams.get_mut(&name) {
+                    team.members.extend(members);
+                }
+            }
+            TeamAction::RemoveEntities(members) => {
+                if let Some(team) = self.teams.get_mut(&name) {
+                    team.members.retain(|m| !members.contains(m));
+                }
+            }
+            TeamAction::Unknown(_) => {}
+        }
+    }
+
+    pub fn team_of(&self, entity: &str) -> Option<&Team> {
+        self.teams
+            .values()
+            .find(|t| t.members.iter().any(|m| m == entity))
+    }
+
+    // name with the team prefix, suffix and color
+    pub fn decorate(&self, entity: &str) -> String {
+        match self.team_of(entity) {
+            Some(team) => format!(
+                "{}{}{}",
+                team.info.prefix,
+                paint(entity, team.info.color),
+                team.info.suffix
+            ),
+            None => entity.to_string(),
+        }
+    }
+
+    // title and the top scores of the sidebar seen by a player
+    pub fn sidebar(&self, player: &str) -> Option<(String, Vec<(String, i32)>)> {
+        let team_slot = self
+            .team_of(player)
+            .filter(|t| (0..16).contains(&t.info.color))
+            .and_then(|t| self.slots.get(&(3 + t.info.color as i8)));
+        let name = team_slot.or(self.slots.get(&SIDEBAR))?;
+        let objective = self.objectives.get(name)?;
+        let mut scores = self
+            .scores
+            .get(name)
+            .map(|s| {
+                s.iter()
+                    .map(|(e, v)| (e.clone(), *v))
+                    .collect::<Vec<(String, i32)>>()
+            })
+            .unwrap_or_default();
+        scores.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores.truncate(SIDEBAR_LINES);
+        Some((objective.display.clone(), scores))
+    }
+
+    pub fn clear(&mut self) {
+        self.objectives.clear();
+        self.scores.clear();
+        self.slots.clear();
+        self.teams.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidebar() {
+        let mut scoreboard = Scoreboard::new();
+        scoreboard.update_objective(
+            "kills".to_string(),
+            Some(Objective {
+                display: "Kills".to_string(),
+                kind: 0,
+            }),
+        );
+        scoreboard.display(SIDEBAR, "kills".to_string());
+        scoreboard.update_score("Steve".to_string(), "kills".to_string(), Some(3));
+        scoreboard.update_score("Alex".to_string(), "kills".to_string(), Some(5));
+        scoreboard.update_score("Bob".to_string(), "kills".to_string(), Some(1));
+        scoreboard.update_score("Bob".to_string(), "".to_string(), None);
+        assert_eq!(
+            scoreboard.sidebar("Steve"),
+            Some((
+                "Kills".to_string(),
+                vec![("Alex".to_string(), 5), ("Steve".to_string(), 3)]
+            ))
+        );
+
+        scoreboard.update_objective("kills".to_string(), None);
+        assert_eq!(scoreboard.sidebar("Steve"), None);
+    }
+
+    #[test]
+    fn test_team() {
+        let mut scoreboard = Scoreboard::new();
+        let info = TeamInfo {
+            display: "Red".to_string(),
+            prefix: "[R] ".to_string(),
+            suffix: String::new(),
+            color: 12,
+        };
+        scoreboard.update_team(
+            "red".to_string(),
+            TeamAction::Create(info, vec!["Steve".to_string()]),
+        );
+        scoreboard.update_team(
+            "blue".to_string(),
+            TeamAction::Create(TeamInfo::default(), vec![]),
+        );
+        assert_eq!(
+            console::strip_ansi_codes(&scoreboard.decorate("Steve")),
+            "[R] Steve"
+        );
+        scoreboard.update_team(
+            "blue".to_string(),
+            TeamAction::AddEntities(vec!["Steve".to_string()]),
+        );
+        assert_eq!(scoreboard.team_of("Steve").unwrap().info.display, "");
+        assert_eq!(scoreboard.decorate("Alex"), "Alex");
+    }
+}
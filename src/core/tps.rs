@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use tokio::time::{Duration, Instant};
+
+// 1m, 5m and 15m like the spigot /tps command
+pub const WINDOWS: [Duration; 3] = [
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(15 * 60),
+];
+// a shorter span is too noisy to alert on
+const MIN_SPAN: Duration = Duration::from_secs(10);
+const MAX_TPS: f64 = 20.0;
+
+pub struct Tps {
+    // world age, arrival of the update time packet
+    samples: VecDeque<(i64, Instant)>,
+    // alert below this 1m tps, 0 to disable
+    threshold: f64,
+    alerting: bool,
+}
+
+impl Tps {
+    pub fn new(threshold: f64) -> Tps {
+        Tps {
+            samples: VecDeque::new(),
+            threshold,
+            alerting: false,
+        }
+    }
+
+    // alert or recovery message when the 1m tps crosses the threshold
+    pub fn record(&mut self, world_age: i64, now: Instant) -> Option<String> {
+        if let Some((last_age, _)) = self.samples.back() {
+            // world age went back, another world or server
+            if world_age < *last_age {
+                self.samples.clear();
+            }
+        }
+        self.samples.push_back((world_age, now));
+        while let Some((_, time)) = self.samples.front() {
+            match now.duration_since(*time) > WINDOWS[2] {
+                true => self.samples.pop_front(),
+                false => break,
+            };
+        }
+
+        if self.threshold <= 0.0 || self.span(WINDOWS[0]) < MIN_SPAN {
+            return None;
+        }
+        let tps = self.average(WINDOWS[0])?;
+        match (tps < self.threshold, self.alerting) {
+            (true, false) => {
+                self.alerting = true;
+                Some(format!(
+                    "TPS dropped to {:.2} (below {:.2})",
+                    tps, self.threshold
+                ))
+            }
+            (false, true) => {
+                self.alerting = false;
+                Some(format!("TPS recovered to {:.2}", tps))
+            }
+            _ => None,
+        }
+    }
+
+    // ticks per real second over the window, none before two samples
+    pub fn average(&self, window: Duration) -> Option<f64> {
+        let (last_age, last_time) = *self.samples.back()?;
+        let (first_age, first_time) = self.first(window)?;
+        let elapsed = last_time.duration_since(first_time).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        Some(((last_age - first_age) as f64 / elapsed).min(MAX_TPS))
+    }
+
+    pub fn averages(&self) -> [Option<f64>; 3] {
+        WINDOWS.map(|window| self.average(window))
+    }
+
+    // samples are per connection
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.alerting = false;
+    }
+
+    // oldest sample in the window
+    fn first(&self, window: Duration) -> Option<(i64, Instant)> {
+        let (_, last_time) = self.samples.back()?;
+        self.samples
+            .iter()
+            .find(|(_, time)| last_time.duration_since(*time) <= window)
+            .copied()
+    }
+
+    fn span(&self, window: Duration) -> Duration {
+        match (self.first(window), self.samples.back()) {
+            (Some((_, first)), Some((_, last))) => last.duration_since(first),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average() {
+        let start = Instant::now();
+        let mut tps = Tps::new(0.0);
+        assert_eq!(tps.average(WINDOWS[0]), None);
+        // 20 tps for 5 minutes, then 10 tps for a minute
+        for s in 0..=300 {
+            tps.record(s * 20, start + Duration::from_secs(s as u64));
+        }
+        for s in 1..=60 {
+            tps.record(6000 + s * 10, start + Duration::from_secs(300 + s as u64));
+        }
+        let [one, five, fifteen] = tps.averages();
+        assert_eq!(one, Some(10.0));
+        // 5400 ticks in 300 seconds
+        assert_eq!(five, Some(18.0));
+        assert_eq!(fifteen, Some(6600.0 / 360.0));
+    }
+
+    #[test]
+    fn test_irregular_packets() {
+        let start = Instant::now();
+        let mut tps = Tps::new(0.0);
+        // a late packet carries the ticks of the missed ones
+        for (age, s) in [(0, 0), (20, 1), (100, 5), (120, 6)] {
+            tps.record(age, start + Duration::from_secs(s));
+        }
+        assert_eq!(tps.average(WINDOWS[0]), Some(20.0));
+    }
+
+    #[test]
+    fn test_alert() {
+        let start = Instant::now();
+        let mut tps = Tps::new(15.0);
+        let mut alerts = Vec::new();
+        let mut age = 0;
+        for s in 0..120 {
+            age += if s < 60 { 20 } else { 5 };
+            alerts.extend(tps.record(age, start + Duration::from_secs(s)));
+        }
+        for s in 120..240 {
+            age += 20;
+            alerts.extend(tps.record(age, start + Duration::from_secs(s)));
+        }
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts[0].starts_with("TPS dropped to"));
+        assert!(alerts[1].starts_with("TPS recovered to"));
+    }
+}
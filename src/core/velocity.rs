@@ -0,0 +1,156 @@
+use crate::util::transfer_var::{uint2var_int, write_string};
+use hmac::{Hmac, Mac};
+use md5::{Digest, Md5};
+use sha2::Sha256;
+
+// login plugin channel of velocity modern forwarding
+pub const CHANNEL: &str = "velocity:player_info";
+
+// forwarding versions, only the default one is sent
+const MODERN_DEFAULT: i32 = 1;
+
+// game profile property such as the signed `textures`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Property {
+    pub name: String,
+    pub value: String,
+    pub signature: Option<String>,
+}
+
+pub struct Velocity {
+    secret: String,
+    address: String,
+    // uuid of the authenticated account, offline if absent
+    uuid: Option<Vec<u8>>,
+    properties: Vec<Property>,
+}
+
+impl Default for Velocity {
+    fn default() -> Velocity {
+        Velocity {
+            secret: String::new(),
+            address: "127.0.0.1".to_string(),
+            uuid: None,
+            properties: Vec::new(),
+        }
+    }
+}
+
+impl Velocity {
+    pub fn new(secret: String, address: String) -> Velocity {
+        Velocity {
+            secret,
+            address,
+            ..Velocity::default()
+        }
+    }
+
+    // profile of an online login
+    pub fn set_profile(&mut self, uuid: Vec<u8>, properties: Vec<Property>) {
+        self.uuid = Some(uuid);
+        self.properties = properties;
+    }
+
+    pub fn enabled(&self) -> bool {
+        !self.secret.is_empty()
+    }
+
+    // signature + forwarding data of the player
+    pub fn respond(&self, username: &str) -> Vec<u8> {
+        let mut payload = uint2var_int(vec![MODERN_DEFAULT]);
+        payload.extend(write_string(&self.address));
+        match &self.uuid {
+            Some(uuid) => payload.extend(uuid),
+            None => payload.extend(offline_uuid(username)),
+        }
+        payload.extend(write_string(username));
+        payload.extend(uint2var_int(vec![self.properties.len() as i32]));
+        for property in &self.properties {
+            payload.extend(write_string(&property.name));
+            payload.extend(write_string(&property.value));
+            match &property.signature {
+                Some(signature) => {
+                    payload.push(0x01);
+                    payload.extend(write_string(signature));
+                }
+                None => payload.push(0x00),
+            }
+        }
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes()).unwrap();
+        mac.update(&payload);
+        [mac.finalize().into_bytes().to_vec(), payload].concat()
+    }
+}
+
+// md5 of "OfflinePlayer:<name>" as a version 3 uuid
+pub fn offline_uuid(username: &str) -> Vec<u8> {
+    let mut uuid = Md5::digest(format!("OfflinePlayer:{}", username).as_bytes()).to_vec();
+    uuid[6] = (uuid[6] & 0x0f) | 0x30;
+    uuid[8] = (uuid[8] & 0x3f) | 0x80;
+    uuid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_uuid() {
+        // b50ad385-829d-3141-a216-7e7d7539ba7f
+        assert_eq!(
+            hex::encode(offline_uuid("Notch")),
+            "b50ad385829d3141a2167e7d7539ba7f"
+        );
+    }
+
+    #[test]
+    fn test_respond() {
+        let velocity = Velocity::new("secret".to_string(), "127.0.0.1".to_string());
+        let data = velocity.respond("Notch");
+        let payload = [
+            vec![0x01, 0x09],
+            b"127.0.0.1".to_vec(),
+            offline_uuid("Notch"),
+            vec![0x05],
+            b"Notch".to_vec(),
+            vec![0x00],
+        ]
+        .concat();
+        assert_eq!(data[32..], payload);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(&payload);
+        assert!(mac.verify_slice(&data[..32]).is_ok());
+        assert!(!Velocity::default().enabled());
+    }
+
+    #[test]
+    fn test_respond_profile() {
+        let mut velocity = Velocity::new("secret".to_string(), "127.0.0.1".to_string());
+        velocity.set_profile(
+            vec![0x11; 16],
+            vec![Property {
+                name: "textures".to_string(),
+                value: "e30=".to_string(),
+                signature: Some("c2ln".to_string()),
+            }],
+        );
+        let data = velocity.respond("Notch");
+        let payload = [
+            vec![0x01, 0x09],
+            b"127.0.0.1".to_vec(),
+            vec![0x11; 16],
+            vec![0x05],
+            b"Notch".to_vec(),
+            vec![0x01, 0x08],
+            b"textures".to_vec(),
+            vec![0x04],
+            b"e30=".to_vec(),
+            vec![0x01, 0x04],
+            b"c2ln".to_vec(),
+        ]
+        .concat();
+        assert_eq!(data[32..], payload);
+    }
+}
@@ -0,0 +1,833 @@
+use crate::core::msg::mapper as serverbound;
+use crate::core::parser::mapper as clientbound;
+use crate::util;
+use crate::util::transfer_var::{read_string, read_var_int};
+
+// packets are handled and built in the 763 (1.20.1) layout,
+// other versions are translated to and from it
+pub const V1_20_1: i32 = 763;
+pub const V1_20_2: i32 = 764;
+// 1.20.3 and 1.20.4
+pub const V1_20_3: i32 = 765;
+// 1.20.5 and 1.20.6
+pub const V1_20_5: i32 = 766;
+
+// 763 to 766
+pub fn supported(protocol: i32) -> bool {
+    (V1_20_1..=V1_20_5).contains(&protocol)
+}
+
+// connection state the packet ids belong to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    Login,
+    Configuration,
+    Play,
+}
+
+// (packet id of the version, packet id of 763)
+const CLIENTBOUND_PLAY_764: [(u8, u8); 42] = [
+    (0x01, clientbound::SPAWN_ENTITY),
+    (0x05, clientbound::ACKNOWLEDGE_BLOCK_CHANGE),
+    (0x09, clientbound::BLOCK_UPDATE),
+    (0x0A, clientbound::BOSS_BAR),
+    (0x0B, clientbound::CHANGE_DIFFICULTY),
+    (0x0C, clientbound::CHUNK_BATCH_FINISHED),
+    (0x0F, clientbound::CLEAR_TITLES),
+    (0x10, clientbound::COMMAND_SUGGESTIONS_RESPONSE),
+    (0x11, clientbound::COMMANDS),
+    (0x15, clientbound::SET_CONTAINER_SLOT),
+    (0x18, clientbound::PLUGIN_MESSAGE),
+    (0x1B, clientbound::DISCONNECT),
+    (0x1C, clientbound::DISGUISED_CHAT_MESSAGE),
+    (0x24, clientbound::KEEP_LIVE),
+    (0x29, clientbound::LOGIN_PLAY),
+    (0x2C, clientbound::UPDATE_ENTITY_POSITION),
+    (0x2D, clientbound::UPDATE_ENTITY_POSITION_AND_ROTATION),
+    (0x33, clientbound::PING),
+    (0x34, clientbound::PONG_RESPONSE),
+    (0x37, clientbound::PLAYER_CHAT_MESSAGE),
+    (0x3B, clientbound::PLAYER_INFO_REMOVE),
+    (0x3C, clientbound::PLAYER_INFO_UPDATE),
+    (0x3E, clientbound::SYNC_PLAYER_POSITION),
+    (0x40, clientbound::REMOVE_ENTITIES),
+    (0x42, clientbound::RESOURCE_PACK),
+    (0x47, clientbound::SERVER_DATA),
+    (0x48, clientbound::SET_ACTION_BAR_TEXT),
+    (0x4F, clientbound::SET_HELD_ITEM),
+    (0x53, clientbound::DISPLAY_OBJECTIVE),
+    (0x58, clientbound::SET_EXPERIENCE),
+    (0x59, clientbound::SET_HEALTH),
+    (0x5A, clientbound::UPDATE_OBJECTIVES),
+    (0x5C, clientbound::UPDATE_TEAMS),
+    (0x5D, clientbound::UPDATE_SCORE),
+    (0x5F, clientbound::SET_SUBTITLE_TEXT),
+    (0x60, clientbound::UPDATE_TIME),
+    (0x61, clientbound::SET_TITLE_TEXT),
+    (0x62, clientbound::SET_TITLES_ANIMATION),
+    (0x65, clientbound::START_CONFIGURATION),
+    (0x67, clientbound::SYSTEM_CHAT_MESSAGE),
+    (0x68, clientbound::TAB_LIST),
+    (0x6B, clientbound::TELEPORT_ENTITY),
+];
+
+const CLIENTBOUND_PLAY_765: [(u8, u8); 43] = [
+    (0x01, clientbound::SPAWN_ENTITY),
+    (0x05, clientbound::ACKNOWLEDGE_BLOCK_CHANGE),
+    (0x09, clientbound::BLOCK_UPDATE),
+    (0x0A, clientbound::BOSS_BAR),
+    (0x0B, clientbound::CHANGE_DIFFICULTY),
+    (0x0C, clientbound::CHUNK_BATCH_FINISHED),
+    (0x0F, clientbound::CLEAR_TITLES),
+    (0x10, clientbound::COMMAND_SUGGESTIONS_RESPONSE),
+    (0x11, clientbound::COMMANDS),
+    (0x15, clientbound::SET_CONTAINER_SLOT),
+    (0x18, clientbound::PLUGIN_MESSAGE),
+    (0x1B, clientbound::DISCONNECT),
+    (0x1C, clientbound::DISGUISED_CHAT_MESSAGE),
+    (0x24, clientbound::KEEP_LIVE),
+    (0x29, clientbound::LOGIN_PLAY),
+    (0x2C, clientbound::UPDATE_ENTITY_POSITION),
+    (0x2D, clientbound::UPDATE_ENTITY_POSITION_AND_ROTATION),
+    (0x33, clientbound::PING),
+    (0x34, clientbound::PONG_RESPONSE),
+    (0x37, clientbound::PLAYER_CHAT_MESSAGE),
+    (0x3B, clientbound::PLAYER_INFO_REMOVE),
+    (0x3C, clientbound::PLAYER_INFO_UPDATE),
+    (0x3E, clientbound::SYNC_PLAYER_POSITION),
+    (0x40, clientbound::REMOVE_ENTITIES),
+    (0x42, clientbound::RESET_SCORE),
+    (0x44, clientbound::RESOURCE_PACK),
+    (0x49, clientbound::SERVER_DATA),
+    (0x4A, clientbound::SET_ACTION_BAR_TEXT),
+    (0x51, clientbound::SET_HELD_ITEM),
+    (0x55, clientbound::DISPLAY_OBJECTIVE),
+    (0x5A, clientbound::SET_EXPERIENCE),
+    (0x5B, clientbound::SET_HEALTH),
+    (0x5C, clientbound::UPDATE_OBJECTIVES),
+    (0x5E, clientbound::UPDATE_TEAMS),
+    (0x5F, clientbound::UPDATE_SCORE),
+    (0x61, clientbound::SET_SUBTITLE_TEXT),
+    (0x62, clientbound::UPDATE_TIME),
+    (0x63, clientbound::SET_TITLE_TEXT),
+    (0x64, clientbound::SET_TITLES_ANIMATION),
+    (0x67, clientbound::START_CONFIGURATION),
+    (0x69, clientbound::SYSTEM_CHAT_MESSAGE),
+    (0x6A, clientbound::TAB_LIST),
+    (0x6D, clientbound::TELEPORT_ENTITY),
+];
+
+const CLIENTBOUND_PLAY_766: [(u8, u8); 46] = [
+    (0x01, clientbound::SPAWN_ENTITY),
+    (0x05, clientbound::ACKNOWLEDGE_BLOCK_CHANGE),
+    (0x09, clientbound::BLOCK_UPDATE),
+    (0x0A, clientbound::BOSS_BAR),
+    (0x0B, clientbound::CHANGE_DIFFICULTY),
+    (0x0C, clientbound::CHUNK_BATCH_FINISHED),
+    (0x0F, clientbound::CLEAR_TITLES),
+    (0x10, clientbound::COMMAND_SUGGESTIONS_RESPONSE),
+    (0x11, clientbound::COMMANDS),
+    (0x15, clientbound::SET_CONTAINER_SLOT),
+    (0x16, clientbound::COOKIE_REQUEST),
+    (0x19, clientbound::PLUGIN_MESSAGE),
+    (0x1D, clientbound::DISCONNECT),
+    (0x1E, clientbound::DISGUISED_CHAT_MESSAGE),
+    (0x26, clientbound::KEEP_LIVE),
+    (0x2B, clientbound::LOGIN_PLAY),
+    (0x2E, clientbound::UPDATE_ENTITY_POSITION),
+    (0x2F, clientbound::UPDATE_ENTITY_POSITION_AND_ROTATION),
+    (0x35, clientbound::PING),
+    (0x36, clientbound::PONG_RESPONSE),
+    (0x39, clientbound::PLAYER_CHAT_MESSAGE),
+    (0x3D, clientbound::PLAYER_INFO_REMOVE),
+    (0x3E, clientbound::PLAYER_INFO_UPDATE),
+    (0x40, clientbound::SYNC_PLAYER_POSITION),
+    (0x42, clientbound::REMOVE_ENTITIES),
+    (0x44, clientbound::RESET_SCORE),
+    (0x46, clientbound::RESOURCE_PACK),
+    (0x4B, clientbound::SERVER_DATA),
+    (0x4C, clientbound::SET_ACTION_BAR_TEXT),
+    (0x53, clientbound::SET_HELD_ITEM),
+    (0x57, clientbound::DISPLAY_OBJECTIVE),
+    (0x5C, clientbound::SET_EXPERIENCE),
+    (0x5D, clientbound::SET_HEALTH),
+    (0x5E, clientbound::UPDATE_OBJECTIVES),
+    (0x60, clientbound::UPDATE_TEAMS),
+    (0x61, clientbound::UPDATE_SCORE),
+    (0x63, clientbound::SET_SUBTITLE_TEXT),
+    (0x64, clientbound::UPDATE_TIME),
+    (0x65, clientbound::SET_TITLE_TEXT),
+    (0x66, clientbound::SET_TITLES_ANIMATION),
+    (0x69, clientbound::START_CONFIGURATION),
+    (0x6B, clientbound::STORE_COOKIE),
+    (0x6C, clientbound::SYSTEM_CHAT_MESSAGE),
+    (0x6D, clientbound::TAB_LIST),
+    (0x70, clientbound::TELEPORT_ENTITY),
+    (0x73, clientbound::TRANSFER),
+];
+
+const CLIENTBOUND_CONFIGURATION_764: [(u8, u8); 9] = [
+    (0x00, clientbound::PLUGIN_MESSAGE),
+    (0x01, clientbound::DISCONNECT),
+    (0x02, clientbound::FINISH_CONFIGURATION),
+    (0x03, clientbound::KEEP_LIVE),
+    (0x04, clientbound::PING),
+    (0x05, clientbound::REGISTRY_DATA),
+    (0x06, clientbound::RESOURCE_PACK),
+    (0x07, clientbound::FEATURE_FLAGS),
+    (0x08, clientbound::UPDATE_TAGS),
+];
+
+const CLIENTBOUND_CONFIGURATION_765: [(u8, u8); 9] = [
+    (0x00, clientbound::PLUGIN_MESSAGE),
+    (0x01, clientbound::DISCONNECT),
+    (0x02, clientbound::FINISH_CONFIGURATION),
+    (0x03, clientbound::KEEP_LIVE),
+    (0x04, clientbound::PING),
+    (0x05, clientbound::REGISTRY_DATA),
+    (0x07, clientbound::RESOURCE_PACK),
+    (0x08, clientbound::FEATURE_FLAGS),
+    (0x09, clientbound::UPDATE_TAGS),
+];
+
+const CLIENTBOUND_CONFIGURATION_766: [(u8, u8); 13] = [
+    (0x00, clientbound::COOKIE_REQUEST),
+    (0x01, clientbound::PLUGIN_MESSAGE),
+    (0x02, clientbound::DISCONNECT),
+    (0x03, clientbound::FINISH_CONFIGURATION),
+    (0x04, clientbound::KEEP_LIVE),
+    (0x05, clientbound::PING),
+    (0x07, clientbound::REGISTRY_DATA),
+    (0x09, clientbound::RESOURCE_PACK),
+    (0x0A, clientbound::STORE_COOKIE),
+    (0x0B, clientbound::TRANSFER),
+    (0x0C, clientbound::FEATURE_FLAGS),
+    (0x0D, clientbound::UPDATE_TAGS),
+    (0x0E, clientbound::KNOWN_PACKS),
+];
+
+const CLIENTBOUND_LOGIN_766: [(u8, u8); 5] = [
+    (0x00, clientbound::LOGIN_DISCONNECT),
+    (0x02, clientbound::LOGIN_SUCCESS),
+    (0x03, clientbound::SET_COMPRESSION),
+    (0x04, clientbound::LOGIN_PLUGIN_REQUEST),
+    (0x05, clientbound::COOKIE_REQUEST),
+];
+
+// (packet id of 763, packet id of the version)
+const SERVERBOUND_PLAY_764: [(u8, u8); 18] = [
+    (serverbound::CONFIRM_TP, 0x00),
+    (serverbound::CHAT_COMMAND, 0x04),
+    (serverbound::CHAT_MESSAGE, 0x05),
+    (serverbound::CHUNK_BATCH_RECEIVED, 0x07),
+    (serverbound::RESPAWN, 0x08),
+    (serverbound::CLIENT_INFORMATION, 0x09),
+    (serverbound::COMMAND_SUGGESTIONS_REQUEST, 0x0A),
+    (serverbound::ACKNOWLEDGE_CONFIGURATION, 0x0B),
+    (serverbound::PLUGIN_MESSAGE, 0x0F),
+    (serverbound::INTERACT, 0x12),
+    (serverbound::KEEP_LIVE, 0x14),
+    (serverbound::PING_REQUEST, 0x1D),
+    (serverbound::PLAYER_ACTION, 0x20),
+    (serverbound::PLAYER_INPUT, 0x22),
+    (serverbound::PONG, 0x23),
+    (serverbound::RESOURCE_PACK_RESPONSE, 0x27),
+    (serverbound::SWING_ARM, 0x32),
+    (serverbound::USE_ITEM_ON, 0x34),
+];
+
+const SERVERBOUND_PLAY_765: [(u8, u8); 18] = [
+    (serverbound::CONFIRM_TP, 0x00),
+    (serverbound::CHAT_COMMAND, 0x04),
+    (serverbound::CHAT_MESSAGE, 0x05),
+    (serverbound::CHUNK_BATCH_RECEIVED, 0x07),
+    (serverbound::RESPAWN, 0x08),
+    (serverbound::CLIENT_INFORMATION, 0x09),
+    (serverbound::COMMAND_SUGGESTIONS_REQUEST, 0x0A),
+    (serverbound::ACKNOWLEDGE_CONFIGURATION, 0x0B),
+    (serverbound::PLUGIN_MESSAGE, 0x10),
+    (serverbound::INTERACT, 0x13),
+    (serverbound::KEEP_LIVE, 0x15),
+    (serverbound::PING_REQUEST, 0x1E),
+    (serverbound::PLAYER_ACTION, 0x21),
+    (serverbound::PLAYER_INPUT, 0x23),
+    (serverbound::PONG, 0x24),
+    (serverbound::RESOURCE_PACK_RESPONSE, 0x28),
+    (serverbound::SWING_ARM, 0x33),
+    (serverbound::USE_ITEM_ON, 0x35),
+];
+
+const SERVERBOUND_PLAY_766: [(u8, u8); 19] = [
+    (serverbound::CONFIRM_TP, 0x00),
+    (serverbound::CHAT_COMMAND, 0x04),
+    (serverbound::CHAT_MESSAGE, 0x06),
+    (serverbound::CHUNK_BATCH_RECEIVED, 0x08),
+    (serverbound::RESPAWN, 0x09),
+    (serverbound::CLIENT_INFORMATION, 0x0A),
+    (serverbound::COMMAND_SUGGESTIONS_REQUEST, 0x0B),
+    (serverbound::ACKNOWLEDGE_CONFIGURATION, 0x0C),
+    (serverbound::COOKIE_RESPONSE, 0x11),
+    (serverbound::PLUGIN_MESSAGE, 0x12),
+    (serverbound::INTERACT, 0x16),
+    (serverbound::KEEP_LIVE, 0x18),
+    (serverbound::PING_REQUEST, 0x21),
+    (serverbound::PLAYER_ACTION, 0x24),
+    (serverbound::PLAYER_INPUT, 0x26),
+    (serverbound::PONG, 0x27),
+    (serverbound::RESOURCE_PACK_RESPONSE, 0x2B),
+    (serverbound::SWING_ARM, 0x36),
+    (serverbound::USE_ITEM_ON, 0x38),
+];
+
+// same for 764 and 765
+const SERVERBOUND_CONFIGURATION: [(u8, u8); 6] = [
+    (serverbound::CLIENT_INFORMATION, 0x00),
+    (serverbound::PLUGIN_MESSAGE, 0x01),
+    (serverbound::FINISH_CONFIGURATION, 0x02),
+    (serverbound::KEEP_LIVE, 0x03),
+    (serverbound::PONG, 0x04),
+    (serverbound::RESOURCE_PACK_RESPONSE, 0x05),
+];
+
+const SERVERBOUND_CONFIGURATION_766: [(u8, u8); 8] = [
+    (serverbound::CLIENT_INFORMATION, 0x00),
+    (serverbound::COOKIE_RESPONSE, 0x01),
+    (serverbound::PLUGIN_MESSAGE, 0x02),
+    (serverbound::FINISH_CONFIGURATION, 0x03),
+    (serverbound::KEEP_LIVE, 0x04),
+    (serverbound::PONG, 0x05),
+    (serverbound::RESOURCE_PACK_RESPONSE, 0x06),
+    (serverbound::KNOWN_PACKS, 0x07),
+];
+
+// handshake and login start keep their ids
+const SERVERBOUND_LOGIN_766: [(u8, u8); 4] = [
+    (serverbound::LOGIN_START, 0x00),
+    (serverbound::LOGIN_PLUGIN_RESPONSE, 0x02),
+    (serverbound::LOGIN_ACKNOWLEDGED, 0x03),
+    (serverbound::COOKIE_RESPONSE, 0x04),
+];
+
+pub struct Version {
+    protocol: i32,
+    // uuid of the last resource pack (765+)
+    pack_uuid: Option<Vec<u8>>,
+}
+
+impl Version {
+    pub fn new(protocol: i32) -> Version {
+        Version {
+            protocol,
+            pack_uuid: None,
+        }
+    }
+
+    pub fn protocol(&self) -> i32 {
+        self.protocol
+    }
+
+    // first release of the protocol
+    pub fn name(&self) -> &'static str {
+        match self.protocol {
+            V1_20_1 => "1.20.1",
+            V1_20_2 => "1.20.2",
+            V1_20_3 => "1.20.3",
+            V1_20_5 => "1.20.5",
+            _ => "unknown",
+        }
+    }
+
+    // login acknowledged and the configuration state
+    pub fn has_configuration(&self) -> bool {
+        self.protocol > V1_20_1
+    }
+
+    // packet id and packet in the 763 layout, None if not handled
+    pub fn clientbound(
+        &mut self,
+        state: State,
+        packet_id: u8,
+        packet: Vec<u8>,
+    ) -> Option<(u8, Vec<u8>)> {
+        let table: &[(u8, u8)] = match (self.protocol, state) {
+            (V1_20_2, State::Play) => &CLIENTBOUND_PLAY_764,
+            (V1_20_2, State::Configuration) => &CLIENTBOUND_CONFIGURATION_764,
+            (V1_20_3, State::Play) => &CLIENTBOUND_PLAY_765,
+            (V1_20_3, State::Configuration) => &CLIENTBOUND_CONFIGURATION_765,
+            (V1_20_5, State::Play) => &CLIENTBOUND_PLAY_766,
+            (V1_20_5, State::Configuration) => &CLIENTBOUND_CONFIGURATION_766,
+            (V1_20_5, State::Login) => &CLIENTBOUND_LOGIN_766,
+            _ => return Some((packet_id, packet)),
+        };
+        let (_, id) = table.iter().find(|(wire, _)| *wire == packet_id)?;
+        // chat components are nbt since 765
+        let (id, mut packet) = match self.protocol >= V1_20_3 && state != State::Login {
+            true => self.nbt_chat(*id, packet),
+            false => (*id, packet),
+        };
+        if self.protocol >= V1_20_5 && id == clientbound::SERVER_DATA {
+            // enforces secure chat moved to login (play)
+            packet.push(0x00);
+        }
+        if self.protocol >= V1_20_5 && id == clientbound::SET_CONTAINER_SLOT {
+            packet = slot_766(&packet);
+        }
+        Some((id, packet))
+    }
+
+    // packet (with length) built in the 763 layout, None if not sent in this state
+    pub fn serverbound(
+        &self,
+        state: State,
+        mut packet: Vec<u8>,
+        compress: bool,
+    ) -> Option<Vec<u8>> {
+        let table: &[(u8, u8)] = match (self.protocol, state) {
+            (V1_20_2, State::Play) => &SERVERBOUND_PLAY_764,
+            (V1_20_3, State::Play) => &SERVERBOUND_PLAY_765,
+            (V1_20_2 | V1_20_3, State::Configuration) => &SERVERBOUND_CONFIGURATION,
+            (V1_20_5, State::Play) => &SERVERBOUND_PLAY_766,
+            (V1_20_5, State::Configuration) => &SERVERBOUND_CONFIGURATION_766,
+            (V1_20_5, State::Login) => &SERVERBOUND_LOGIN_766,
+            _ => return Some(packet),
+        };
+        // length, (data length,) packet id
+        let mut index = 0;
+        read_var_int(&packet, &mut index);
+        if compress {
+            read_var_int(&packet, &mut index);
+        }
+        let canonical = packet[index];
+        let (_, id) = table.iter().find(|(id, _)| *id == canonical)?;
+        packet[index] = *id;
+        if self.protocol >= V1_20_3 && canonical == serverbound::RESOURCE_PACK_RESPONSE {
+            // uuid before the result
+            let uuid = self.pack_uuid.clone().unwrap_or(vec![0x00; 16]);
+            packet.splice(index + 1..index + 1, uuid);
+            packet[0] += 16;
+        }
+        if self.protocol >= V1_20_5 && canonical == serverbound::CHAT_COMMAND {
+            // unsigned chat command, only the command is left
+            let mut end = index + 1;
+            read_string(&packet, &mut end);
+            packet.truncate(end);
+            packet[0] = (packet.len() - 1) as u8;
+        }
+        Some(packet)
+    }
+
+    fn nbt_chat(&mut self, id: u8, packet: Vec<u8>) -> (u8, Vec<u8>) {
+        let mut p = Rewrite::new(&packet);
+        match id {
+            clientbound::DISCONNECT
+            | clientbound::SET_TITLE_TEXT
+            | clientbound::SET_SUBTITLE_TEXT
+            | clientbound::SET_ACTION_BAR_TEXT
+            | clientbound::SYSTEM_CHAT_MESSAGE
+            | clientbound::SERVER_DATA => {
+                p.chat();
+            }
+            clientbound::TAB_LIST => {
+                p.chat();
+                p.chat();
+            }
+            clientbound::DISGUISED_CHAT_MESSAGE => {
+                p.chat();
+                p.var_int();
+                p.chat();
+                p.optional_chat();
+            }
+            clientbound::PLAYER_CHAT_MESSAGE => {
+                // header
+                p.copy(16);
+                p.var_int();
+                if p.byte() == 0x01 {
+                    p.copy(256);
+                }
+                // body, previous messages
+                p.string();
+                p.copy(16);
+                for _ in 0..p.var_int() {
+                    if p.var_int() == 0 {
+                        p.copy(256);
+                    }
+                }
+                // unsigned content, filter
+                p.optional_chat();
+                if p.var_int() == 2 {
+                    let longs = p.var_int() as usize;
+                    p.copy(longs * 8);
+                }
+                p.var_int();
+                p.chat();
+                p.optional_chat();
+            }
+            clientbound::BOSS_BAR => {
+                p.copy(16);
+                // add, update title
+                if matches!(p.var_int(), 0 | 3) {
+                    p.chat();
+                }
+            }
+            clientbound::UPDATE_OBJECTIVES => {
+                p.string();
+                if matches!(p.byte(), 0 | 2) {
+                    p.chat();
+                    p.var_int();
+                    // number format is not in 763
+                    return (id, p.out);
+                }
+            }
+            clientbound::UPDATE_TEAMS => {
+                p.string();
+                if matches!(p.byte(), 0 | 2) {
+                    p.chat();
+                    p.copy(1);
+                    p.string();
+                    p.string();
+                    p.var_int();
+                    p.chat();
+                    p.chat();
+                }
+            }
+            clientbound::UPDATE_SCORE => {
+                // entity, objective, score -> entity, action, objective, score
+                let entity = p.skip_string();
+                let objective = p.skip_string();
+                let score = p.skip_var_int();
+                p.put_string(&entity);
+                p.put_var_int(0);
+                p.put_string(&objective);
+                p.put_var_int(score);
+                return (id, p.out);
+            }
+            clientbound::RESET_SCORE => {
+                let entity = p.skip_string();
+                let objective = match p.skip_byte() {
+                    0x01 => p.skip_string(),
+                    _ => String::new(),
+                };
+                p.put_string(&entity);
+                p.put_var_int(1);
+                p.put_string(&objective);
+                return (clientbound::UPDATE_SCORE, p.out);
+            }
+            clientbound::PLAYER_INFO_UPDATE => {
+                let actions = p.byte();
+                for _ in 0..p.var_int() {
+                    p.copy(16);
+                    if actions & 0x01 != 0 {
+                        p.string();
+                        for _ in 0..p.var_int() {
+                            p.string();
+                            p.string();
+                            if p.byte() == 0x01 {
+                                p.string();
+                            }
+                        }
+                    }
+                    if actions & 0x02 != 0 && p.byte() == 0x01 {
+                        p.copy(16 + 8);
+                        let key = p.var_int() as usize;
+                        p.copy(key);
+                        let signature = p.var_int() as usize;
+                        p.copy(signature);
+                    }
+                    if actions & 0x04 != 0 {
+                        p.var_int();
+                    }
+                    if actions & 0x08 != 0 {
+                        p.copy(1);
+                    }
+                    if actions & 0x10 != 0 {
+                        p.var_int();
+                    }
+                    if actions & 0x20 != 0 {
+                        p.optional_chat();
+                    }
+                }
+            }
+            clientbound::RESOURCE_PACK => {
+                self.pack_uuid = Some(p.skip(16));
+                p.string();
+                p.string();
+                p.copy(1);
+                p.optional_chat();
+            }
+            clientbound::COMMAND_SUGGESTIONS_RESPONSE => {
+                p.var_int();
+                p.var_int();
+                p.var_int();
+                for _ in 0..p.var_int() {
+                    p.string();
+                    p.optional_chat();
+                }
+            }
+            _ => {}
+        }
+        (id, p.finish())
+    }
+}
+
+// count and item components -> present, item, count and no nbt
+fn slot_766(packet: &[u8]) -> Vec<u8> {
+    let mut p = Rewrite::new(packet);
+    // window, state id, slot
+    p.copy(1);
+    p.var_int();
+    p.copy(2);
+    let count = p.skip_var_int();
+    if count <= 0 {
+        p.out.push(0x00);
+        return p.out;
+    }
+    let item = p.skip_var_int();
+    p.out.push(0x01);
+    p.put_var_int(item);
+    p.out.push(count as u8);
+    p.out.push(0x00);
+    p.out
+}
+
+// copies the fields of a packet, chat components are turned from nbt into json
+struct Rewrite<'a> {
+    pkt: &'a [u8],
+    index: usize,
+    out: Vec<u8>,
+}
+
+impl<'a> Rewrite<'a> {
+    fn new(pkt: &'a [u8]) -> Rewrite<'a> {
+        Rewrite {
+            pkt,
+            index: 0,
+            out: Vec::new(),
+        }
+    }
+
+    fn copy(&mut self, n: usize) {
+        let end = (self.index + n).min(self.pkt.len());
+        self.out.extend_from_slice(&self.pkt[self.index..end]);
+        self.index = end;
+    }
+
+    fn skip(&mut self, n: usize) -> Vec<u8> {
+        let end = (self.index + n).min(self.pkt.len());
+        let bytes = self.pkt[self.index..end].to_vec();
+        self.index = end;
+        bytes
+    }
+
+    fn byte(&mut self) -> u8 {
+        let byte = self.pkt[self.index];
+        self.copy(1);
+        byte
+    }
+
+    fn skip_byte(&mut self) -> u8 {
+        self.index += 1;
+        self.pkt[self.index - 1]
+    }
+
+    fn var_int(&mut self) -> i32 {
+        let start = self.index;
+        let value = read_var_int(self.pkt, &mut self.index);
+        self.out.extend_from_slice(&self.pkt[start..self.index]);
+        value
+    }
+
+    fn skip_var_int(&mut self) -> i32 {
+        read_var_int(self.pkt, &mut self.index)
+    }
+
+    fn string(&mut self) {
+        let start = self.index;
+        read_string(self.pkt, &mut self.index);
+        self.out.extend_from_slice(&self.pkt[start..self.index]);
+    }
+
+    fn skip_string(&mut self) -> String {
+        read_string(self.pkt, &mut self.index)
+    }
+
+    fn put_var_int(&mut self, value: i32) {
+        self.out
+            .extend(util::transfer_var::uint2var_int(vec![value]));
+    }
+
+    fn put_string(&mut self, text: &str) {
+        self.put_var_int(text.len() as i32);
+        self.out.extend_from_slice(text.as_bytes());
+    }
+
+    fn chat(&mut self) {
+        let json = util::nbt::read(self.pkt, &mut self.index).to_string();
+        self.put_string(&json);
+    }
+
+    fn optional_chat(&mut self) {
+        if self.byte() == 0x01 {
+            self.chat();
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.copy(self.pkt.len() - self.index);
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        assert_eq!(Version::new(V1_20_1).name(), "1.20.1");
+        assert_eq!(Version::new(V1_20_3).name(), "1.20.3");
+        assert_eq!(Version::new(V1_20_5).name(), "1.20.5");
+    }
+
+    #[test]
+    fn test_clientbound_ids() {
+        let mut version = Version::new(V1_20_2);
+        assert_eq!(
+            version.clientbound(State::Play, 0x24, vec![0x01]),
+            Some((clientbound::KEEP_LIVE, vec![0x01]))
+        );
+        assert_eq!(
+            version.clientbound(State::Configuration, 0x02, vec![]),
+            Some((clientbound::FINISH_CONFIGURATION, vec![]))
+        );
+        // chunk data is not handled
+        assert_eq!(version.clientbound(State::Play, 0x25, vec![0x01]), None);
+        // 763 is passed through
+        let mut version = Version::new(V1_20_1);
+        assert_eq!(
+            version.clientbound(State::Play, 0x25, vec![0x01]),
+            Some((0x25, vec![0x01]))
+        );
+    }
+
+    #[test]
+    fn test_serverbound_ids() {
+        let version = Version::new(V1_20_3);
+        // keep alive
+        assert_eq!(
+            version.serverbound(State::Play, vec![0x03, 0x00, 0x12, 0x07], true),
+            Some(vec![0x03, 0x00, 0x15, 0x07])
+        );
+        assert_eq!(
+            version.serverbound(State::Configuration, vec![0x02, 0x12, 0x07], false),
+            Some(vec![0x02, 0x03, 0x07])
+        );
+        // no chat in configuration
+        assert_eq!(
+            version.serverbound(State::Configuration, vec![0x02, 0x05, 0x00], false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_nbt_chat() {
+        let mut version = Version::new(V1_20_3);
+        // system chat: {text: "Hi"}, not overlay
+        let packet = vec![
+            0x0a, 0x08, 0x00, 0x04, 0x74, 0x65, 0x78, 0x74, 0x00, 0x02, 0x48, 0x69, 0x00, 0x00,
+        ];
+        let (id, packet) = version.clientbound(State::Play, 0x69, packet).unwrap();
+        assert_eq!(id, clientbound::SYSTEM_CHAT_MESSAGE);
+        assert_eq!(
+            crate::core::parser::play::system_chat_message::parse(packet),
+            ("{\"text\":\"Hi\"}".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_player_chat() {
+        let mut version = Version::new(V1_20_3);
+        // Al: "hi", unsigned, no previous messages, chat type 0
+        let mut packet = vec![0x11; 16];
+        packet.extend(vec![0x00, 0x00, 0x02, 0x68, 0x69]);
+        packet.extend(vec![0x00; 16]);
+        packet.extend(vec![0x00, 0x00, 0x00, 0x00]);
+        packet.extend(vec![
+            0x0a, 0x08, 0x00, 0x04, 0x74, 0x65, 0x78, 0x74, 0x00, 0x02, 0x41, 0x6c, 0x00, 0x00,
+        ]);
+        let (id, packet) = version.clientbound(State::Play, 0x37, packet).unwrap();
+        assert_eq!(id, clientbound::PLAYER_CHAT_MESSAGE);
+        let (_, message, _, _, _, name, target) =
+            crate::core::parser::play::player_chat_message::parse(packet);
+        assert_eq!(message, "hi");
+        assert_eq!(name, "{\"text\":\"Al\"}");
+        assert_eq!(target, None);
+    }
+
+    #[test]
+    fn test_reset_score() {
+        let mut version = Version::new(V1_20_3);
+        // Steve, no objective
+        let packet = vec![0x05, 0x53, 0x74, 0x65, 0x76, 0x65, 0x00];
+        let (id, packet) = version.clientbound(State::Play, 0x42, packet).unwrap();
+        assert_eq!(id, clientbound::UPDATE_SCORE);
+        assert_eq!(
+            crate::core::parser::play::update_score::parse(packet),
+            ("Steve".to_string(), String::new(), None)
+        );
+    }
+
+    #[test]
+    fn test_resource_pack_uuid() {
+        let mut version = Version::new(V1_20_3);
+        let mut packet = vec![0x11; 16];
+        // url "u", hash "h", not forced, no prompt
+        packet.extend(vec![0x01, 0x75, 0x01, 0x68, 0x00, 0x00]);
+        let (id, packet) = version.clientbound(State::Play, 0x44, packet).unwrap();
+        assert_eq!(id, clientbound::RESOURCE_PACK);
+        assert_eq!(packet, vec![0x01, 0x75, 0x01, 0x68, 0x00, 0x00]);
+        let response = version
+            .serverbound(State::Play, vec![0x02, 0x24, 0x03], false)
+            .unwrap();
+        assert_eq!(response[0], 0x12);
+        assert_eq!(response[1], 0x28);
+        assert_eq!(response[2..18], [0x11; 16]);
+        assert_eq!(response[18], 0x03);
+    }
+
+    #[test]
+    fn test_v1_20_5() {
+        let mut version = Version::new(V1_20_5);
+        assert_eq!(
+            version.clientbound(State::Login, 0x05, vec![0x00]),
+            Some((clientbound::COOKIE_REQUEST, vec![0x00]))
+        );
+        assert_eq!(
+            version.clientbound(State::Configuration, 0x0E, vec![0x00]),
+            Some((clientbound::KNOWN_PACKS, vec![0x00]))
+        );
+        // cookie response in login
+        assert_eq!(
+            version.serverbound(State::Login, vec![0x02, 0x73, 0x00], false),
+            Some(vec![0x02, 0x04, 0x00])
+        );
+        // chat command "tp", timestamp, salt, no signatures, acknowledged
+        let mut command = vec![0x00, 0x04, 0x02, 0x74, 0x70];
+        command.extend(vec![0x00; 8 + 8 + 1 + 1 + 3]);
+        command[0] = (command.len() - 1) as u8;
+        assert_eq!(
+            version.serverbound(State::Play, command, false),
+            Some(vec![0x04, 0x04, 0x02, 0x74, 0x70])
+        );
+        // window 0, state 3, slot 36, 1 x item 820 with a component
+        let slot = vec![
+            0x00, 0x03, 0x00, 0x24, 0x01, 0xb4, 0x06, 0x01, 0x00, 0x05, 0x01,
+        ];
+        assert_eq!(
+            version.clientbound(State::Play, 0x15, slot),
+            Some((
+                clientbound::SET_CONTAINER_SLOT,
+                vec![0x00, 0x03, 0x00, 0x24, 0x01, 0xb4, 0x06, 0x01, 0x00]
+            ))
+        );
+    }
+}
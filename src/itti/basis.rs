@@ -1,27 +1,63 @@
+use crate::itti::proxy::Proxy;
+use crate::itti::resolve;
 use log::{debug, info, warn};
-use std::net::{IpAddr, SocketAddr};
+use std::future::Future;
+use std::net::SocketAddr;
 use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 
-trait IttiInterface {}
+// byte stream to the server, tcp or in memory
+pub trait IttiInterface: Send + Sync {
+    // open the stream to the address, again after close
+    fn connect(&mut self) -> impl Future<Output = io::Result<()>> + Send;
+
+    // whole packets, written in order
+    fn send(&self, data: Vec<u8>) -> impl Future<Output = io::Result<()>> + Send;
+
+    // as send, waits until written and returns the write time
+    fn send_written(&self, data: Vec<u8>) -> impl Future<Output = io::Result<Instant>> + Send;
+
+    // next chunk as read, split or merged packets, empty once the server closed
+    fn recv(&mut self) -> impl Future<Output = io::Result<Vec<u8>>> + Send;
+
+    // when the last received chunk was read from the stream
+    fn arrival(&self) -> Instant;
+
+    // drop the stream, a stalled server included
+    fn close(&mut self) -> impl Future<Output = ()> + Send;
+
+    // host and port of the handshake
+    fn address(&self) -> (String, u16);
+
+    // transfer, used by the next connect
+    fn set_address(&mut self, host: String, port: u16);
+}
+
+// packet, and where to report the write time
+type Write = (Vec<u8>, Option<oneshot::Sender<Instant>>);
 
 #[allow(clippy::upper_case_acronyms)]
 pub struct ITTI {
-    reader_rx: Option<mpsc::Receiver<Vec<u8>>>,
-    writer_tx: Option<mpsc::Sender<Vec<u8>>>,
+    // chunks with their read time
+    reader_rx: Option<mpsc::Receiver<(Vec<u8>, Instant)>>,
+    writer_tx: Option<mpsc::Sender<Write>>,
     reader_end_rx: Option<oneshot::Receiver<()>>,
     writer_end_rx: Option<oneshot::Receiver<()>>,
+    reader_task: Option<JoinHandle<()>>,
+    arrival: Instant,
 
     reader_buf: i32,
     writer_buf: i32,
 
     pub ip: String,
     pub port: String,
+    // dns server, the system one if none
+    resolver: Option<SocketAddr>,
+    proxy: Option<Proxy>,
 }
 
-impl IttiInterface for ITTI {}
-
 impl ITTI {
     pub fn new(ip: String, port: String, reader_buf: i32, writer_buf: i32) -> ITTI {
         ITTI {
@@ -33,9 +69,47 @@ impl ITTI {
             writer_tx: None,
             reader_end_rx: None,
             writer_end_rx: None,
+            reader_task: None,
+            arrival: Instant::now(),
+            resolver: None,
+            proxy: None,
         }
     }
-    pub async fn build(&mut self) -> io::Result<()> {
+
+    pub fn set_resolver(&mut self, resolver: Option<SocketAddr>) {
+        self.resolver = resolver;
+    }
+
+    pub fn set_proxy(&mut self, proxy: Option<Proxy>) {
+        self.proxy = proxy;
+    }
+
+    #[allow(dead_code)]
+    pub async fn try_recv(&mut self, timeout: std::time::Duration) -> io::Result<Vec<u8>> {
+        if let Some(reader_rx) = &mut self.reader_rx {
+            match tokio::time::timeout(timeout, reader_rx.recv()).await {
+                Ok(Some((data, arrival))) => {
+                    self.arrival = arrival;
+                    Ok(data)
+                }
+                Ok(None) => {
+                    info!("try_recv: None");
+                    Ok(Vec::new())
+                }
+                Err(e) => {
+                    debug!("try_recv: timeout - {:?}", e);
+                    Ok(Vec::new())
+                }
+            }
+        } else {
+            warn!("try_recv: channel closed");
+            Err(io::Error::other("channel closed"))
+        }
+    }
+}
+
+impl IttiInterface for ITTI {
+    async fn connect(&mut self) -> io::Result<()> {
         let (reader_tx, reader_rx) = mpsc::channel(self.reader_buf as usize);
         let (writer_tx, mut writer_rx) = mpsc::channel(self.writer_buf as usize);
         let (reader_end_tx, reader_end_rx) = oneshot::channel();
@@ -46,15 +120,20 @@ impl ITTI {
         self.reader_end_rx = Some(reader_end_rx);
         self.writer_end_rx = Some(writer_end_rx);
 
-        let tcp = TcpStream::connect(SocketAddr::new(
-            self.ip.parse::<IpAddr>().unwrap(),
-            self.port.parse::<u16>().unwrap(),
-        ))
-        .await?;
+        // ip or host name, the handshake keeps the host name
+        let port = self.port.parse::<u16>().unwrap();
+        let tcp = match &self.proxy {
+            // no srv lookup, the proxy resolves the host name
+            Some(proxy) => proxy.connect(&self.ip, port).await?,
+            None => {
+                let addrs = resolve::resolve(&self.ip, port, self.resolver).await?;
+                resolve::connect(addrs).await?
+            }
+        };
         let (mut reader, mut writer) = tcp.into_split();
 
         // reader
-        tokio::spawn(async move {
+        self.reader_task = Some(tokio::spawn(async move {
             loop {
                 let mut buf = vec![0; 4096];
                 match reader.read(&mut buf).await {
@@ -64,7 +143,7 @@ impl ITTI {
                             break;
                         }
                         let data = buf[..n].to_vec();
-                        if let Err(e) = reader_tx.send(data).await {
+                        if let Err(e) = reader_tx.send((data, Instant::now())).await {
                             info!("reader: send failed - {:?}", e.to_string());
                             break;
                         }
@@ -77,17 +156,20 @@ impl ITTI {
             }
 
             reader_end_tx.send(()).unwrap();
-        });
+        }));
 
         // writer
         tokio::spawn(async move {
             loop {
                 match writer_rx.recv().await {
-                    Some(data) => {
+                    Some((data, written_tx)) => {
                         if let Err(e) = writer.write_all(&data).await {
                             info!("writer: write failed - {:?}", e.to_string());
                             break;
                         }
+                        if let Some(written_tx) = written_tx {
+                            let _ = written_tx.send(Instant::now());
+                        }
                     }
                     None => {
                         debug!("writer: channel closed");
@@ -102,59 +184,69 @@ impl ITTI {
         Ok(())
     }
 
-    pub async fn send(&self, data: Vec<u8>) -> io::Result<()> {
+    async fn send(&self, data: Vec<u8>) -> io::Result<()> {
         if let Some(writer_tx) = &self.writer_tx {
-            match writer_tx.send(data).await {
+            match writer_tx.send((data, None)).await {
                 Ok(_) => Ok(()),
                 Err(_) => {
                     warn!("send: send failed");
-                    Err(io::Error::new(io::ErrorKind::Other, "send failed"))
+                    Err(io::Error::other("send failed"))
                 }
             }
         } else {
             warn!("send: channel closed");
-            Err(io::Error::new(io::ErrorKind::Other, "send: channel closed"))
+            Err(io::Error::other("send: channel closed"))
         }
     }
 
-    pub async fn recv(&mut self) -> io::Result<Vec<u8>> {
-        if let Some(reader_rx) = &mut self.reader_rx {
-            match reader_rx.recv().await {
-                Some(data) => Ok(data),
-                None => {
-                    info!("recv: None, server closed");
-                    Ok(Vec::new())
-                }
+    async fn send_written(&self, data: Vec<u8>) -> io::Result<Instant> {
+        let writer_tx = match &self.writer_tx {
+            Some(writer_tx) => writer_tx,
+            None => {
+                warn!("send: channel closed");
+                return Err(io::Error::other("send: channel closed"));
             }
-        } else {
-            warn!("recv: channel closed");
-            Err(io::Error::new(io::ErrorKind::Other, "channel closed"))
+        };
+        let (written_tx, written_rx) = oneshot::channel();
+        match writer_tx.send((data, Some(written_tx))).await {
+            Ok(_) => {}
+            Err(_) => {
+                warn!("send: send failed");
+                return Err(io::Error::other("send failed"));
+            }
+        }
+        // dropped when the write failed
+        match written_rx.await {
+            Ok(written) => Ok(written),
+            Err(_) => Err(io::Error::other("write failed")),
         }
     }
 
-    #[allow(dead_code)]
-    pub async fn try_recv(&mut self, timeout: std::time::Duration) -> io::Result<Vec<u8>> {
+    async fn recv(&mut self) -> io::Result<Vec<u8>> {
         if let Some(reader_rx) = &mut self.reader_rx {
-            match tokio::time::timeout(timeout, reader_rx.recv()).await {
-                Ok(Some(data)) => Ok(data),
-                Ok(None) => {
-                    info!("try_recv: None");
-                    Ok(Vec::new())
+            match reader_rx.recv().await {
+                Some((data, arrival)) => {
+                    self.arrival = arrival;
+                    Ok(data)
                 }
-                Err(e) => {
-                    debug!("try_recv: timeout - {:?}", e);
+                None => {
+                    info!("recv: None, server closed");
                     Ok(Vec::new())
                 }
             }
         } else {
-            warn!("try_recv: channel closed");
-            Err(io::Error::new(io::ErrorKind::Other, "channel closed"))
+            warn!("recv: channel closed");
+            Err(io::Error::other("channel closed"))
         }
     }
 
-    pub async fn stop(&mut self) {
+    async fn close(&mut self) {
         drop(self.writer_tx.take());
         drop(self.reader_rx.take());
+        // a stalled server never ends the read
+        if let Some(reader_task) = self.reader_task.take() {
+            reader_task.abort();
+        }
 
         // wait
         if let Some(reader_end_rx) = &mut self.reader_end_rx {
@@ -167,11 +259,24 @@ impl ITTI {
         }
         info!("itti: quit");
     }
+
+    fn arrival(&self) -> Instant {
+        self.arrival
+    }
+
+    fn address(&self) -> (String, u16) {
+        (self.ip.clone(), self.port.parse().unwrap_or_default())
+    }
+
+    fn set_address(&mut self, host: String, port: u16) {
+        self.ip = host;
+        self.port = port.to_string();
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::itti::basis::ITTI;
+    use crate::itti::basis::{IttiInterface, ITTI};
     use env_logger::{Builder, Target};
     use lazy_static::lazy_static;
     use log::{debug, info};
@@ -261,7 +366,7 @@ mod tests {
 
         info!("itti_test start");
         let mut itti = ITTI::new("127.0.0.1".to_string(), "8080".to_string(), 1, 1);
-        itti.build().await.unwrap();
+        itti.connect().await.unwrap();
         info!("itti_test connected");
 
         // reader
@@ -313,8 +418,25 @@ mod tests {
         assert_eq!(data.len(), 0);
 
         // end-test
-        itti.stop().await;
+        itti.close().await;
 
         info!("itti_test end");
     }
+
+    #[tokio::test]
+    async fn itti_stop_stalled_test() {
+        // accepts and never writes nor closes
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+        });
+
+        let mut itti = ITTI::new("127.0.0.1".to_string(), port.to_string(), 1, 1);
+        itti.connect().await.unwrap();
+        tokio::time::timeout(tokio::time::Duration::from_secs(1), itti.close())
+            .await
+            .unwrap();
+    }
 }
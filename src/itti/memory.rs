@@ -0,0 +1,143 @@
+use crate::itti::basis::IttiInterface;
+use log::{debug, info, warn};
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt, DuplexStream, ReadHalf, WriteHalf};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+// in memory stream for tests, the other end plays the server
+pub struct Memory {
+    // until connect
+    stream: Option<DuplexStream>,
+    reader: Option<ReadHalf<DuplexStream>>,
+    writer: Mutex<Option<WriteHalf<DuplexStream>>>,
+    arrival: Instant,
+
+    host: String,
+    port: u16,
+}
+
+// client end and server end, `max_buf_size` bytes in flight per direction
+pub fn duplex(max_buf_size: usize) -> (Memory, DuplexStream) {
+    let (client, server) = io::duplex(max_buf_size);
+    let memory = Memory {
+        stream: Some(client),
+        reader: None,
+        writer: Mutex::new(None),
+        arrival: Instant::now(),
+        host: "localhost".to_string(),
+        port: 25565,
+    };
+    (memory, server)
+}
+
+impl IttiInterface for Memory {
+    // once, there is no server to connect again
+    async fn connect(&mut self) -> io::Result<()> {
+        match self.stream.take() {
+            Some(stream) => {
+                let (reader, writer) = io::split(stream);
+                self.reader = Some(reader);
+                *self.writer.lock().await = Some(writer);
+                debug!("memory: connected");
+                Ok(())
+            }
+            None => {
+                warn!("memory: already connected");
+                Err(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    "memory stream already used",
+                ))
+            }
+        }
+    }
+
+    async fn send(&self, data: Vec<u8>) -> io::Result<()> {
+        match &mut *self.writer.lock().await {
+            Some(writer) => writer.write_all(&data).await,
+            None => {
+                warn!("send: not connected");
+                Err(io::Error::other("send: not connected"))
+            }
+        }
+    }
+
+    async fn send_written(&self, data: Vec<u8>) -> io::Result<Instant> {
+        self.send(data).await?;
+        Ok(Instant::now())
+    }
+
+    async fn recv(&mut self) -> io::Result<Vec<u8>> {
+        match &mut self.reader {
+            Some(reader) => {
+                let mut buf = vec![0; 4096];
+                // empty once the server end dropped
+                let n = reader.read(&mut buf).await?;
+                self.arrival = Instant::now();
+                Ok(buf[..n].to_vec())
+            }
+            None => {
+                warn!("recv: not connected");
+                Err(io::Error::other("recv: not connected"))
+            }
+        }
+    }
+
+    fn arrival(&self) -> Instant {
+        self.arrival
+    }
+
+    async fn close(&mut self) {
+        self.reader = None;
+        if let Some(mut writer) = self.writer.lock().await.take() {
+            match writer.shutdown().await {
+                Ok(_) => {}
+                Err(e) => {
+                    debug!("memory: shutdown failed - {}", e);
+                }
+            }
+        }
+        info!("memory: closed");
+    }
+
+    fn address(&self) -> (String, u16) {
+        (self.host.clone(), self.port)
+    }
+
+    fn set_address(&mut self, host: String, port: u16) {
+        self.host = host;
+        self.port = port;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_duplex() {
+        let (mut memory, mut server) = duplex(64);
+        assert!(memory.send(vec![0x01]).await.is_err());
+        memory.connect().await.unwrap();
+        assert!(memory.connect().await.is_err());
+
+        memory.send(vec![0x01, 0x02]).await.unwrap();
+        memory.send(vec![0x03]).await.unwrap();
+        let mut buf = [0; 3];
+        server.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [0x01, 0x02, 0x03]);
+
+        let written = memory.send_written(vec![0x06]).await.unwrap();
+        let mut buf = [0; 1];
+        server.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [0x06]);
+
+        server.write_all(&[0x04, 0x05]).await.unwrap();
+        assert_eq!(memory.recv().await.unwrap(), vec![0x04, 0x05]);
+        assert!(memory.arrival() >= written);
+        drop(server);
+        assert!(memory.recv().await.unwrap().is_empty());
+
+        memory.close().await;
+        assert!(memory.recv().await.is_err());
+    }
+}
@@ -1 +1,4 @@
 pub mod basis;
+pub mod memory;
+pub mod proxy;
+mod resolve;
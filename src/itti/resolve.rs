@@ -0,0 +1,235 @@
+use hickory_resolver::config::{
+    LookupIpStrategy, NameServerConfigGroup, ResolverConfig, ResolverOpts,
+};
+use hickory_resolver::TokioAsyncResolver;
+use log::{debug, info, warn};
+use std::net::{IpAddr, SocketAddr};
+use tokio::io;
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+use tokio::time::Duration;
+
+// delay before racing the next address (happy eyeballs, rfc 8305)
+const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+// srv records are only looked up for the default port, as the vanilla client
+const DEFAULT_PORT: u16 = 25565;
+
+// addresses of the server: ip, _minecraft._tcp srv record (default port), then a/aaaa records
+pub async fn resolve(
+    host: &str,
+    port: u16,
+    resolver: Option<SocketAddr>,
+) -> io::Result<Vec<SocketAddr>> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(vec![SocketAddr::new(ip, port)]);
+    }
+    // both families for happy eyeballs
+    let mut opts = ResolverOpts::default();
+    opts.ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+    let resolver = match resolver {
+        Some(addr) => TokioAsyncResolver::tokio(
+            ResolverConfig::from_parts(
+                None,
+                vec![],
+                NameServerConfigGroup::from_ips_clear(&[addr.ip()], addr.port(), true),
+            ),
+            opts,
+        ),
+        None => match hickory_resolver::system_conf::read_system_conf() {
+            Ok((config, _)) => TokioAsyncResolver::tokio(config, opts),
+            Err(e) => {
+                warn!("resolve: system config failed - {}", e);
+                TokioAsyncResolver::tokio(ResolverConfig::default(), opts)
+            }
+        },
+    };
+
+    // srv record, an explicit port wins
+    let srv = match port {
+        DEFAULT_PORT => {
+            resolver
+                .srv_lookup(format!("_minecraft._tcp.{}.", host.trim_end_matches('.')))
+                .await
+        }
+        _ => Err(format!("port {} configured", port).into()),
+    };
+    let (target, port) = match srv {
+        Ok(lookup) => match lookup
+            .iter()
+            .min_by_key(|srv| (srv.priority(), u16::MAX - srv.weight()))
+        {
+            Some(srv) => {
+                info!("resolve: srv {} -> {}:{}", host, srv.target(), srv.port());
+                (srv.target().to_utf8(), srv.port())
+            }
+            None => (host.to_string(), port),
+        },
+        Err(e) => {
+            debug!("resolve: no srv record for {} - {}", host, e);
+            (host.to_string(), port)
+        }
+    };
+
+    match resolver.lookup_ip(target.as_str()).await {
+        Ok(lookup) => {
+            let addrs = interleave(lookup.iter().map(|ip| SocketAddr::new(ip, port)).collect());
+            debug!("resolve: {} -> {:?}", target, addrs);
+            Ok(addrs)
+        }
+        Err(e) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("resolve {} failed: {}", target, e),
+        )),
+    }
+}
+
+// alternate the address families, starting with the first one
+fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let first = match addrs.first() {
+        Some(addr) => addr.is_ipv6(),
+        None => return addrs,
+    };
+    let (mut a, mut b): (Vec<SocketAddr>, Vec<SocketAddr>) =
+        addrs.into_iter().partition(|addr| addr.is_ipv6() == first);
+    let mut result = Vec::new();
+    a.reverse();
+    b.reverse();
+    while !a.is_empty() || !b.is_empty() {
+        result.extend(a.pop());
+        result.extend(b.pop());
+    }
+    result
+}
+
+// race the addresses, a new attempt starts after a delay or when the last one failed
+pub async fn connect(addrs: Vec<SocketAddr>) -> io::Result<TcpStream> {
+    let mut pending = addrs.into_iter();
+    let mut attempts = JoinSet::new();
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no address");
+    loop {
+        if let Some(addr) = pending.next() {
+            attempts.spawn(async move { (addr, TcpStream::connect(addr).await) });
+        } else if attempts.is_empty() {
+            return Err(last_error);
+        }
+        let result = match pending.len() {
+            0 => attempts.join_next().await,
+            _ => match tokio::time::timeout(ATTEMPT_DELAY, attempts.join_next()).await {
+                Ok(result) => result,
+                // start the next attempt
+                Err(_) => continue,
+            },
+        };
+        match result {
+            Some(Ok((addr, Ok(stream)))) => {
+                debug!("connect: {} connected", addr);
+                return Ok(stream);
+            }
+            Some(Ok((addr, Err(e)))) => {
+                debug!("connect: {} failed - {}", addr, e);
+                last_error = e;
+            }
+            Some(Err(e)) => {
+                last_error = io::Error::other(e);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_resolver::proto::op::{Message, MessageType};
+    use hickory_resolver::proto::rr::rdata::{A, SRV};
+    use hickory_resolver::proto::rr::{Name, RData, Record, RecordType};
+    use std::net::Ipv4Addr;
+    use tokio::net::{TcpListener, UdpSocket};
+
+    // answers _minecraft._tcp.mc.test with backend.test:25570, backend.test with 127.0.0.1,
+    // mc.test with 127.0.0.2
+    async fn dns_server() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 512];
+            loop {
+                let (n, peer) = socket.recv_from(&mut buf).await.unwrap();
+                let request = Message::from_vec(&buf[..n]).unwrap();
+                let query = request.queries()[0].clone();
+                let mut response = Message::new();
+                response
+                    .set_id(request.id())
+                    .set_message_type(MessageType::Response)
+                    .set_recursion_desired(true)
+                    .set_recursion_available(true)
+                    .add_query(query.clone());
+                let name = query.name().to_utf8();
+                let rdata = match (query.query_type(), name.as_str()) {
+                    (RecordType::SRV, "_minecraft._tcp.mc.test.") => Some(RData::SRV(SRV::new(
+                        0,
+                        5,
+                        25570,
+                        Name::from_utf8("backend.test.").unwrap(),
+                    ))),
+                    (RecordType::A, "backend.test.") => {
+                        Some(RData::A(A(Ipv4Addr::new(127, 0, 0, 1))))
+                    }
+                    (RecordType::A, "mc.test.") => Some(RData::A(A(Ipv4Addr::new(127, 0, 0, 2)))),
+                    _ => None,
+                };
+                if let Some(rdata) = rdata {
+                    response.add_answer(Record::from_rdata(query.name().clone(), 60, rdata));
+                }
+                socket
+                    .send_to(&response.to_vec().unwrap(), peer)
+                    .await
+                    .unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let dns = dns_server().await;
+        assert_eq!(
+            resolve("127.0.0.1", 25565, Some(dns)).await.unwrap(),
+            vec!["127.0.0.1:25565".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(
+            resolve("mc.test", 25565, Some(dns)).await.unwrap(),
+            vec!["127.0.0.1:25570".parse::<SocketAddr>().unwrap()]
+        );
+        // no srv lookup for another port
+        assert_eq!(
+            resolve("mc.test", 25566, Some(dns)).await.unwrap(),
+            vec!["127.0.0.2:25566".parse::<SocketAddr>().unwrap()]
+        );
+        assert!(resolve("unknown.test", 25565, Some(dns)).await.is_err());
+    }
+
+    #[test]
+    fn test_interleave() {
+        let addrs = ["[::1]:1", "[::2]:1", "127.0.0.1:1", "127.0.0.2:1"]
+            .iter()
+            .map(|a| a.parse::<SocketAddr>().unwrap())
+            .collect::<Vec<SocketAddr>>();
+        let result = interleave(addrs.clone());
+        assert_eq!(result, vec![addrs[0], addrs[2], addrs[1], addrs[3]]);
+    }
+
+    #[tokio::test]
+    async fn test_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // a closed port first
+        let closed = {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let stream = connect(vec![closed, addr]).await.unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+        assert!(connect(vec![closed]).await.is_err());
+    }
+}
@@ -0,0 +1,53 @@
+//! Minecraft 1.20.1 - 1.20.6 client, the protocol behind the `mc-client` console.
+//!
+//! [`ClientBuilder`] starts a session loop that connects on [`Client::connect`]
+//! and reports everything it sees as [`ClientEvent`]s:
+//!
+//! ```no_run
+//! use mc_client::{ClientBuilder, ClientEvent};
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ClientBuilder::new("127.0.0.1", 25565)
+//!     .username("Steve")
+//!     .version(765)
+//!     .build()?;
+//! let mut events = client.events();
+//! client.connect().await?;
+//! while let Some(event) = events.next().await {
+//!     match event {
+//!         ClientEvent::Chat { message, .. } if message.contains("ping") => {
+//!             client.chat("pong").await?;
+//!         }
+//!         ClientEvent::Health { health, .. } if health <= 0.0 => client.respawn().await?,
+//!         ClientEvent::Kick(reason) => {
+//!             println!("kicked: {}", reason);
+//!             break;
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! client.quit().await?;
+//! client.wait().await;
+//! # Ok(())
+//! # }
+//! ```
+
+mod api;
+mod core;
+pub mod itti;
+pub mod util;
+pub mod yggdrasil;
+
+// what the console sets up through ClientBuilder::configure
+pub use crate::core::client::Client as Session;
+pub use crate::core::msg::login::handshake::Forwarding;
+pub use crate::core::{bungeecord, dig, forge, reconnect, resource_pack, velocity};
+
+// parts of the query responses
+pub use crate::core::boss_bar::BossBar;
+pub use crate::core::entity::Entity;
+pub use crate::core::latency::{LatencyWindow, Stats};
+
+pub use crate::core::event::{ClientEvent, ConnectionState, ConsoleCommand, Position, Query};
+pub use crate::core::query::{ListedPlayer, QueryResponse, Score};
+pub use api::{BuildError, Client, ClientBuilder, Closed, Events};
@@ -1,25 +1,22 @@
-use crate::core::client::Client;
-use crate::yggdrasil::refresh;
 use chrono::Local;
 use config::factory::Config;
 use console::style;
 use dialoguer::{FuzzySelect, Password};
 use log::{debug, error, info, warn};
+use mc_client::yggdrasil::{authenticate, profile, refresh};
+use mc_client::{
+    forge, itti, reconnect, resource_pack, velocity, yggdrasil, ClientBuilder, Forwarding,
+};
 use std::fs::OpenOptions;
 use std::process::exit;
 use std::sync::Arc;
-use tokio::sync::mpsc;
 use tracing_subscriber::filter::FilterFn;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{fmt, Layer};
-use yggdrasil::authenticate;
 
 mod config;
-mod core;
-mod itti;
-mod util;
-mod yggdrasil;
+mod terminal;
 
 #[tokio::main]
 async fn main() {
@@ -34,7 +31,17 @@ async fn main() {
     };
     init_log(config.log.log_level);
 
-    let mut client;
+    // http client of yggdrasil, through the proxy
+    let http = match yggdrasil::client(&config.general.proxy.url) {
+        Ok(http) => http,
+        Err(e) => {
+            error!("build http client failed: {}", e);
+            exit(0)
+        }
+    };
+
+    let player;
+    let mut profile_id = None;
 
     // yggdrasil
     match config.general.account.password.as_str() {
@@ -48,7 +55,7 @@ async fn main() {
                 "You are using offline login (username: {})",
                 style(config.general.account.username.clone()).yellow(),
             );
-            client = Client::new(config.general.account.username, 763, config.general.lang);
+            player = config.general.account.username.clone();
         }
         "" => {
             // interactive login
@@ -71,12 +78,13 @@ async fn main() {
             };
             let username = config.general.account.username.clone();
             let url = config.general.auth_server.host.clone();
-            let name = yggdrasil_login(url, username, password).await;
+            let (name, id) = yggdrasil_login(&http, url, username, password).await;
             if name.is_empty() {
                 error!("login failed");
                 exit(0);
             }
-            client = Client::new(name.clone(), 763, config.general.lang);
+            profile_id = Some(id);
+            player = name.clone();
             info!(
                 "login {} using {}({}) success",
                 config.general.auth_server.host.clone(),
@@ -104,12 +112,13 @@ async fn main() {
             let username = config.general.account.username.clone();
             let password = password.to_string();
             let url = config.general.auth_server.host.clone();
-            let name = yggdrasil_login(url, username, password).await;
+            let (name, id) = yggdrasil_login(&http, url, username, password).await;
             if name.is_empty() {
                 error!("login failed");
                 exit(0);
             }
-            client = Client::new(name.clone(), 763, config.general.lang);
+            profile_id = Some(id);
+            player = name.clone();
             info!(
                 "login {} using {}({}) success",
                 config.general.auth_server.host.clone(),
@@ -126,39 +135,154 @@ async fn main() {
         }
     };
 
-    // itti
-    let mut itti = itti::basis::ITTI::new(
-        config.general.server.host,
-        config.general.server.port.to_string(),
-        config.buffer.tcp_buffer.reader as i32,
-        config.buffer.tcp_buffer.writer as i32,
-    );
+    // skin properties of an online login, for ip forwarding
+    let forwarded = config.bungeecord.enabled || !config.velocity.forwarding_secret.is_empty();
+    let properties = match &profile_id {
+        Some(id) if forwarded => {
+            yggdrasil_properties(&http, config.general.auth_server.host.clone(), id.clone()).await
+        }
+        _ => Vec::new(),
+    };
+
+    // bungeecord forwarding
+    let forwarding = match config.bungeecord.enabled {
+        true => {
+            let uuid = match &profile_id {
+                Some(id) => id.clone(),
+                None => hex::encode(velocity::offline_uuid(&config.general.account.username)),
+            };
+            let properties = match properties.is_empty() {
+                true => String::new(),
+                false => match serde_json::to_string(&properties) {
+                    Ok(properties) => properties,
+                    Err(e) => {
+                        warn!("serialize profile properties failed: {}", e);
+                        String::new()
+                    }
+                },
+            };
+            Some(Forwarding::BungeeCord {
+                client_ip: config.bungeecord.address,
+                uuid,
+                properties,
+            })
+        }
+        false => None,
+    };
 
-    let (command_tx, mut command_rx) =
-        mpsc::channel(config.buffer.console_client_buffer.command as usize); // command channel (Console -> Client)
-    let (response_tx, response_rx) =
-        mpsc::channel(config.buffer.console_client_buffer.response as usize); // response channel (Client -> Console)
-    let (msg_tx, msg_rx) = mpsc::channel(16); // message channel (Client -> Display)
+    // velocity modern forwarding
+    let mut velocity =
+        velocity::Velocity::new(config.velocity.forwarding_secret, config.velocity.address);
+    if let Some(uuid) = profile_id.as_ref().and_then(|id| hex::decode(id).ok()) {
+        velocity.set_profile(
+            uuid,
+            properties
+                .into_iter()
+                .map(|p| velocity::Property {
+                    name: p.name,
+                    value: p.value,
+                    signature: p.signature,
+                })
+                .collect(),
+        );
+    }
 
-    // start console
-    match response_tx.send(vec!["reconnect".to_string()]).await {
-        Ok(_) => {}
-        Err(_) => {
-            error!("init console failed");
+    // proxy
+    let proxy = match itti::proxy::Proxy::parse(&config.general.proxy.url) {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            error!("parse proxy failed: {}", e);
             exit(0)
         }
-    }
-    core::console::build_console(command_tx, response_rx, msg_rx);
+    };
 
-    // start client
-    server_loop(
-        &mut itti,
-        &mut client,
-        &mut command_rx,
-        &response_tx,
-        &msg_tx,
+    // auto reconnect
+    let reconnect = match config.reconnect.enabled {
+        true => Some(reconnect::Reconnect::new(
+            std::time::Duration::from_secs(config.reconnect.base_delay),
+            std::time::Duration::from_secs(config.reconnect.max_delay),
+            config.reconnect.max_attempts,
+            config
+                .reconnect
+                .rules
+                .iter()
+                .map(|rule| {
+                    let action = match (rule.retry, rule.delay) {
+                        (false, _) => reconnect::Action::Never,
+                        (true, Some(delay)) => {
+                            reconnect::Action::After(std::time::Duration::from_secs(delay))
+                        }
+                        (true, None) => reconnect::Action::Backoff,
+                    };
+                    reconnect::Rule::new(&rule.reason, action)
+                })
+                .collect(),
+        )),
+        false => None,
+    };
+
+    // client, not connected yet
+    let client = ClientBuilder::new(
+        &config.general.server.host,
+        config.general.server.port as u16,
+    )
+    .username(&player)
+    .version(config.general.protocol_version)
+    .lang(&config.general.lang)
+    .timeout(std::time::Duration::from_secs(config.general.timeout))
+    .buffers(
+        config.buffer.tcp_buffer.reader as i32,
+        config.buffer.tcp_buffer.writer as i32,
+    )
+    .channels(
+        config.buffer.console_client_buffer.command as usize, // command channel (Console -> Client)
+        config.buffer.console_client_buffer.response as usize, // response channel (Client -> Console)
     )
-    .await;
+    .resolver(config.general.server.resolver.parse().ok())
+    .proxy(proxy)
+    .reconnect(reconnect)
+    .configure(move |client| {
+        client.set_tps_alert(config.tps.alert_threshold);
+
+        // resource pack
+        client.set_resource_pack(resource_pack::Policy::new(
+            &config.resource_pack.policy,
+            config.resource_pack.cache,
+        ));
+        client.set_forge(forge::Forge::new(&config.forge.marker, config.forge.mods));
+        client.set_velocity(velocity);
+        if let Some(forwarding) = forwarding {
+            client.set_forwarding(forwarding);
+        }
+
+        // plugin channels
+        for channel in config.channel.register {
+            let name = channel.clone();
+            client.register_channel(
+                &channel,
+                Box::new(move |data| {
+                    info!("Plugin channel {}: {}", name, hex::encode(data));
+                    None
+                }),
+            );
+        }
+    })
+    .build();
+    let client = match client {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("build client failed: {}", e);
+            exit(0);
+        }
+    };
+
+    // start console
+    terminal::build_console(client.clone());
+
+    // until quit
+    client.wait().await;
+
+    println!("bye!");
 }
 
 fn init_log(level: String) {
@@ -328,14 +452,19 @@ fn init_log(level: String) {
         .init();
 }
 
-async fn yggdrasil_login(url: String, username: String, password: String) -> String {
+async fn yggdrasil_login(
+    http: &reqwest::Client,
+    url: String,
+    username: String,
+    password: String,
+) -> (String, String) {
     // authenticate
-    match authenticate::send(url.clone(), username, password, true).await {
+    match authenticate::send(http, url.clone(), username, password, true).await {
         Ok(response) => {
             match response.error {
                 Some(e) => {
                     error!("login in {} failed: {}", url, e);
-                    "".to_string()
+                    (String::new(), String::new())
                 }
                 None => match (
                     response.access_token,
@@ -396,6 +525,7 @@ async fn yggdrasil_login(url: String, username: String, password: String) -> Str
                         }
                         // send refresh to select profile
                         match refresh::send(
+                            http,
                             url.clone(),
                             access_token,
                             client_token,
@@ -408,7 +538,7 @@ async fn yggdrasil_login(url: String, username: String, password: String) -> Str
                             Ok(response) => match response.error {
                                 Some(e) => {
                                     error!("login in {} failed: {}", url, e);
-                                    "".to_string()
+                                    (String::new(), String::new())
                                 }
                                 None => match response.selected_profile {
                                     Some(profile) => {
@@ -417,80 +547,45 @@ async fn yggdrasil_login(url: String, username: String, password: String) -> Str
                                         debug!("client_token: {}", response.client_token.unwrap());
                                         debug!("user: {:?}", response.user.unwrap());
                                         debug!("selected profile: {:?}", profile);
-                                        profile.name
+                                        (profile.name, profile.id)
                                     }
                                     None => {
                                         error!("login in {} failed: unknown error", url);
-                                        "".to_string()
+                                        (String::new(), String::new())
                                     }
                                 },
                             },
                             Err(e) => {
                                 error!("login in {} failed: {}", url, e);
-                                "".to_string()
+                                (String::new(), String::new())
                             }
                         }
                     }
                     _ => {
                         error!("login in {} failed: unknown error", url);
-                        "".to_string()
+                        (String::new(), String::new())
                     }
                 },
             }
         }
         Err(e) => {
             error!("login in {} failed: {}", url, e);
-            "".to_string()
+            (String::new(), String::new())
         }
     }
 }
 
-async fn server_loop(
-    itti: &mut itti::basis::ITTI,
-    client: &mut Client,
-    command_rx: &mut mpsc::Receiver<Vec<String>>,
-    response_tx: &mpsc::Sender<Vec<String>>,
-    msg_tx: &mpsc::Sender<Vec<String>>,
-) {
-    'outer: loop {
-        // reconnect
-        match response_tx.send(vec!["reconnect".to_string()]).await {
-            Ok(_) => {}
-            Err(_) => {
-                debug!("console quit");
-                break;
-            }
-        }
-        loop {
-            match command_rx.recv().await {
-                Some(command) => {
-                    if command.is_empty() {
-                        debug!("quit");
-
-                        break 'outer;
-                    }
-                    if command == vec!["reconnect"] {
-                        break;
-                    } else {
-                        warn!("Unknown command: {:?}", command)
-                    }
-                }
-                None => {
-                    warn!("client already quit");
-
-                    break 'outer;
-                }
-            }
+async fn yggdrasil_properties(
+    http: &reqwest::Client,
+    url: String,
+    id: String,
+) -> Vec<profile::Property> {
+    // skin properties of the profile
+    match profile::send(http, url.clone(), id).await {
+        Ok(response) => response.properties,
+        Err(e) => {
+            warn!("get profile from {} failed: {}", url, e);
+            Vec::new()
         }
-        // clear channel
-        while command_rx.try_recv().is_ok() {}
-        // start client
-        client.start(itti, command_rx, response_tx, msg_tx).await;
-        // reset
-        client.reset();
-        // stop
-        itti.stop().await;
     }
-
-    println!("bye!");
 }
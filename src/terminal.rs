@@ -0,0 +1,671 @@
+use console::{style, Term};
+use crossterm::execute;
+use dialoguer::{Completion, FuzzySelect, Input};
+use log::{debug, error, info, warn};
+use prettytable::format::consts::FORMAT_BOX_CHARS;
+use prettytable::{row, Table};
+use std::cell::RefCell;
+use std::io::{stdout, Write};
+use std::sync::Arc;
+use tokio::io::AsyncBufReadExt;
+use tokio::runtime::Handle;
+use tokio::select;
+
+use mc_client::dig;
+use mc_client::{Client, ClientEvent, ConnectionState, ConsoleCommand, Query};
+
+pub fn build_console(client: Arc<Client>) {
+    let mut events = client.events();
+    // console -- io channel
+    tokio::spawn(async move {
+        // boss bars, title or action bar beside the prompt, until it fades out
+        let mut status: Option<(String, Option<tokio::time::Instant>)> = None;
+        // not connected until /connect
+        let mut offline = true;
+        loop {
+            // not connect
+            if offline {
+                if reconnect_loop(&client).await {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    offline = false;
+                } else {
+                    // quit
+                    break;
+                }
+            }
+            // connect
+            let mut command = String::new();
+            let mut buf = tokio::io::BufReader::new(tokio::io::stdin());
+            loop {
+                match &status {
+                    Some((text, _)) => print!("{} {} ", text, style(">").cyan()), // display status >
+                    None => print!("{} ", style(">").cyan()),                     // display >
+                }
+                match stdout().flush() {
+                    Ok(_) => {}
+                    Err(_) => {
+                        warn!("flush screen failed");
+                    }
+                }
+                select! {
+                    // input
+                    res = buf.read_line(&mut command) => {
+                        match res {
+                            Ok(_) => {
+                                break;
+                            }
+                            Err(_) => {
+                                info!("client already quit");
+                                println!("client already {}", style("quit").red());
+                                break;
+                            }
+                        }
+                    }
+                    // status line fade out
+                    _ = tokio::time::sleep_until(status.as_ref().and_then(|(_, t)| *t).unwrap_or(tokio::time::Instant::now())), if status.as_ref().is_some_and(|(_, t)| t.is_some()) => {
+                        status = None;
+                        match execute!(
+                            Term::stdout(),
+                            crossterm::cursor::MoveToColumn(0),
+                            crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+                        ) {
+                            Ok(_) => {}
+                            Err(_) => {
+                                warn!("clear screen failed");
+                            }
+                        }
+                    }
+                    // display
+                    res = events.next() => {
+                        let event = match res {
+                            Some(event) => event,
+                            None => {
+                                info!("client already quit");
+                                println!("client already {}", style("quit").red());
+                                break;
+                            }
+                        };
+                        // clear input display
+                        match execute!(
+                            Term::stdout(),
+                            crossterm::cursor::MoveToColumn(0),
+                            crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+                        ) {
+                            Ok(_) => {}
+                            Err(_) => {
+                                warn!("clear screen failed");
+                            }
+                        }
+                        let lines = match event {
+                            ClientEvent::Display(lines) => lines,
+                            ClientEvent::Chat { line, .. } => vec![line],
+                            ClientEvent::StatusLine(text, duration) => {
+                                status = match text.is_empty() || duration.is_zero() {
+                                    true => None,
+                                    false => Some((text, tokio::time::Instant::now().checked_add(duration))),
+                                };
+                                continue;
+                            }
+                            ClientEvent::Connection(ConnectionState::Offline) => {
+                                // the menu shows after the next enter
+                                offline = true;
+                                vec![format!(
+                                    "client {}, press {} to continue",
+                                    style("not connect").red(),
+                                    style("enter").cyan()
+                                )]
+                            }
+                            ClientEvent::Connection(ConnectionState::Connecting) => {
+                                offline = false;
+                                continue;
+                            }
+                            _ => continue,
+                        };
+                        // display message
+                        lines.iter().for_each(|msg| {
+                            println!("{}{}", style("▌").white(), msg);
+                        });
+                    }
+                }
+            }
+            // clear input display
+            match execute!(
+                Term::stdout(),
+                crossterm::cursor::MoveToPreviousLine(1),
+                crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+            ) {
+                Ok(_) => {}
+                Err(_) => {
+                    warn!("clear screen failed");
+                }
+            }
+            // input after the disconnect goes nowhere
+            if offline {
+                continue;
+            }
+            // command control
+            if !command_handle(command, &client).await {
+                break;
+            };
+        }
+    });
+}
+
+async fn reconnect_loop(client: &Client) -> bool {
+    info!("client not connect, please input /help for more information");
+    println!(
+        "client {}, please input {} for more information",
+        style("not connect").red(),
+        style("/help").cyan()
+    );
+    const RECONNECT_COMMANDS: [&str; 3] = ["/help", "/quit", "/connect"];
+    loop {
+        match FuzzySelect::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .default(0)
+            .items(&RECONNECT_COMMANDS)
+            .max_length(5)
+            .interact()
+        {
+            Ok(0) => {
+                // help --
+                let mut t = Table::new();
+                t.set_format(*FORMAT_BOX_CHARS);
+                t.set_titles(row![style("Command").blue(), style("Description").white()]);
+                t.add_row(row![style("/quit").yellow(), "Quit console"]);
+                t.add_row(row![style("/connect").yellow(), "Connect to server"]);
+                t.add_row(row![style("/help").yellow(), "Show help"]);
+                t.printstd();
+            }
+            Ok(1) => {
+                // quit
+                match client.quit().await {
+                    Ok(_) => {}
+                    Err(_) => {
+                        info!("client already quit");
+                        println!("client already {}", style("quit").red());
+                    }
+                }
+                info!("console quit");
+                println!("console {}", style("quit").red());
+                return false;
+            }
+            Ok(2) => {
+                // connect, the session loop is gone after quit
+                match client.connect().await {
+                    Ok(_) => break,
+                    Err(_) => {
+                        info!("client already quit");
+                        println!("client already {}", style("quit").red());
+                        return false;
+                    }
+                }
+            }
+            _ => {
+                error!("unknown command")
+            }
+        }
+        debug!("client not connect, please input /reconnect");
+    }
+    true
+}
+
+// tab completion from the command tree and server suggestions
+struct CommandCompletion<'a> {
+    client: &'a Client,
+    // candidates of the last completion and the shown one, tab again to cycle
+    last: RefCell<(Vec<String>, usize)>,
+}
+
+impl Completion for CommandCompletion<'_> {
+    fn get(&self, input: &str) -> Option<String> {
+        let mut last = self.last.borrow_mut();
+        let (candidates, index) = &mut *last;
+        if candidates.get(*index).is_some_and(|c| c == input) {
+            *index = (*index + 1) % candidates.len();
+            return Some(candidates[*index].clone());
+        }
+        // called inside `block_in_place`
+        let res = Handle::current().block_on(self.client.complete(input))?;
+        debug!("command suggestions: {:?}", res);
+        *last = (res, 0);
+        last.0.first().cloned()
+    }
+}
+
+async fn command_handle(command: String, client: &Client) -> bool {
+    match command.trim() {
+        "/quit" => {
+            // quit
+            match client.quit().await {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+            println!("console {}", style("quit").red());
+            return false;
+        }
+        "/respawn" => {
+            // respawn
+            match client.respawn().await {
+                Ok(_) => {
+                    println!("You {}", style("respawn").green());
+                }
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/position" => {
+            // get position
+            match client.query(Query::Position).await {
+                Some(res) => {
+                    info!("position: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/server" => {
+            // get server data
+            match client.query(Query::Server).await {
+                Some(res) => {
+                    info!("server data: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/time" => {
+            // get time
+            match client.query(Query::Time).await {
+                Some(res) => {
+                    info!("time: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/tps" => {
+            // get tps
+            match client.query(Query::Tps).await {
+                Some(res) => {
+                    info!("tps: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/exp" => {
+            // get exp
+            match client.query(Query::Exp).await {
+                Some(res) => {
+                    info!("exp: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/health" => {
+            // get health
+            match client.query(Query::Health).await {
+                Some(res) => {
+                    info!("health: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/help" => {
+            // help
+            let mut t = Table::new();
+            t.set_format(*FORMAT_BOX_CHARS);
+            t.set_titles(row![style("Command").blue(), style("Description").white()]);
+            t.add_row(row![style("/clear").yellow(), "Clear console"]);
+            t.add_row(row![style("/quit").yellow(), "Quit console"]);
+            t.add_row(row![style("/respawn").yellow(), "Respawn"]);
+            t.add_row(row![style("/position").yellow(), "Get position"]);
+            t.add_row(row![style("/server").yellow(), "Get server data"]);
+            t.add_row(row![style("/time").yellow(), "Get time"]);
+            t.add_row(row![
+                style("/tps").yellow(),
+                "Get tps of the last 1m, 5m, 15m"
+            ]);
+            t.add_row(row![style("/exp").yellow(), "Get exp"]);
+            t.add_row(row![style("/health").yellow(), "Get health"]);
+            t.add_row(row![
+                style("/dig x y z [block]").yellow(),
+                "Dig block (/dig cancel to stop)"
+            ]);
+            t.add_row(row![
+                style("/place x y z <face>").yellow(),
+                "Place block on face (top, bottom, north, south, west, east)"
+            ]);
+            t.add_row(row![
+                style("/attack <entity-id|type>").yellow(),
+                "Attack entity (nearest of a type)"
+            ]);
+            t.add_row(row![style("/use <entity-id|type>").yellow(), "Use entity"]);
+            t.add_row(row![
+                style("/mount <entity-id|type>").yellow(),
+                "Mount entity"
+            ]);
+            t.add_row(row![style("/dismount").yellow(), "Dismount"]);
+            t.add_row(row![style("/entities").yellow(), "Get entities around"]);
+            t.add_row(row![style("/bossbars").yellow(), "Get boss bars"]);
+            t.add_row(row![
+                style("/scoreboard").yellow(),
+                "Get sidebar scoreboard"
+            ]);
+            t.add_row(row![style("/players").yellow(), "Get player list"]);
+            t.add_row(row![
+                style("/channels").yellow(),
+                "Get plugin channels and unknown messages"
+            ]);
+            t.add_row(row![
+                style("/latency").yellow(),
+                "Get latency statistics and history"
+            ]);
+            t.add_row(row![style("/servers").yellow(), "Get proxy servers"]);
+            t.add_row(row![
+                style("/switch <server>").yellow(),
+                "Switch proxy server"
+            ]);
+            t.add_row(row![style("chat message").yellow(), "Send message"]);
+            t.add_row(row![style("//command").yellow(), "Send server command"]);
+            t.add_row(row![
+                style("//").yellow(),
+                "Input server command with tab completion"
+            ]);
+            t.printstd();
+        }
+        "/clear" => {
+            // clear
+            match Term::stdout().clear_screen() {
+                Ok(_) => {}
+                Err(_) => {
+                    warn!("clear screen failed");
+                }
+            }
+        }
+        "/dig cancel" => {
+            // cancel dig
+            match client.send(ConsoleCommand::CancelDig).await {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        msg if msg.starts_with("/dig ") => {
+            // dig
+            let args = msg.split_whitespace().skip(1).collect::<Vec<&str>>();
+            if args.len() < 3
+                || args.len() > 4
+                || args[..3].iter().any(|a| a.parse::<i32>().is_err())
+            {
+                println!("{}: /dig x y z [block]", style("Usage").red());
+                return true;
+            }
+            let block = args.get(3).unwrap_or(&dig::DEFAULT_BLOCK).to_string();
+            match client
+                .send(ConsoleCommand::Dig {
+                    x: args[0].parse().unwrap(),
+                    y: args[1].parse().unwrap(),
+                    z: args[2].parse().unwrap(),
+                    block,
+                })
+                .await
+            {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        msg if msg.starts_with("/place ") => {
+            // place
+            let args = msg.split_whitespace().skip(1).collect::<Vec<&str>>();
+            let face = match args.get(3) {
+                Some(&"bottom") | Some(&"0") => Some(0),
+                Some(&"top") | Some(&"1") => Some(1),
+                Some(&"north") | Some(&"2") => Some(2),
+                Some(&"south") | Some(&"3") => Some(3),
+                Some(&"west") | Some(&"4") => Some(4),
+                Some(&"east") | Some(&"5") => Some(5),
+                _ => None,
+            };
+            let face = match face {
+                Some(face)
+                    if args.len() == 4 && args[..3].iter().all(|a| a.parse::<i32>().is_ok()) =>
+                {
+                    face
+                }
+                _ => {
+                    println!("{}: /place x y z <face>", style("Usage").red());
+                    return true;
+                }
+            };
+            match client
+                .send(ConsoleCommand::Place {
+                    x: args[0].parse().unwrap(),
+                    y: args[1].parse().unwrap(),
+                    z: args[2].parse().unwrap(),
+                    face,
+                })
+                .await
+            {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/entities" => {
+            // get entities around
+            match client.query(Query::Entities).await {
+                Some(res) => {
+                    info!("entities: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "//" => {
+            // server command with tab completion
+            let completion = CommandCompletion {
+                client,
+                last: RefCell::new((vec![], 0)),
+            };
+            let input = tokio::task::block_in_place(|| {
+                Input::<String>::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                    .with_prompt("/")
+                    .completion_with(&completion)
+                    .interact_text()
+            });
+            match input {
+                Ok(input) if !input.trim().is_empty() => {
+                    let command = input.trim().trim_start_matches('/').to_string();
+                    match client.send(ConsoleCommand::Command(command)).await {
+                        Ok(_) => {}
+                        Err(_) => {
+                            info!("client already quit");
+                            println!("client already {}", style("quit").red());
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("read command failed: {}", e);
+                }
+            }
+        }
+        "/scoreboard" | "/players" | "/channels" | "/latency" => {
+            // get sidebar, player list, plugin channels or latency
+            let query = match command.trim() {
+                "/scoreboard" => Query::Scoreboard,
+                "/players" => Query::Players,
+                "/channels" => Query::Channels,
+                _ => Query::Latency,
+            };
+            match client.query(query).await {
+                Some(res) => {
+                    debug!("{}: {:?}", command.trim(), res);
+                    for line in res.lines() {
+                        println!("{}", line.trim_end());
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/bossbars" => {
+            // get boss bars
+            match client.query(Query::BossBars).await {
+                Some(res) => {
+                    info!("boss bars: {:?}", res);
+                    for line in res.lines() {
+                        println!("{}", line);
+                    }
+                }
+                None => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/servers" => {
+            // list the servers of the proxy, answered as display messages
+            match client.send(ConsoleCommand::Servers).await {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        msg if msg.starts_with("/switch ") => {
+            // move to another server of the proxy
+            let args = msg.split_whitespace().collect::<Vec<&str>>();
+            if args.len() != 2 {
+                println!("{}: /switch <server>", style("Usage").red());
+                return true;
+            }
+            match client
+                .send(ConsoleCommand::Switch(args[1].to_string()))
+                .await
+            {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "/dismount" => {
+            // dismount
+            match client.send(ConsoleCommand::Dismount).await {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        msg if msg.starts_with("/attack ")
+            || msg.starts_with("/use ")
+            || msg.starts_with("/mount ") =>
+        {
+            // attack, use or mount an entity
+            let args = msg.split_whitespace().collect::<Vec<&str>>();
+            if args.len() != 2 {
+                println!("{}: {} <entity-id|type>", style("Usage").red(), args[0]);
+                return true;
+            }
+            let target = args[1].to_string();
+            let request = match args[0] {
+                "/attack" => ConsoleCommand::Attack(target),
+                "/use" => ConsoleCommand::Use(target),
+                _ => ConsoleCommand::Mount(target),
+            };
+            match client.send(request).await {
+                Ok(_) => {}
+                Err(_) => {
+                    info!("client already quit");
+                    println!("client already {}", style("quit").red());
+                }
+            }
+        }
+        "" => {}
+        msg => {
+            if msg.starts_with('/') {
+                if let Some(command) = msg.strip_prefix("//") {
+                    // send message
+                    match client
+                        .send(ConsoleCommand::Command(command.to_string()))
+                        .await
+                    {
+                        Ok(_) => {}
+                        Err(_) => {
+                            info!("client already quit");
+                        }
+                    }
+                } else {
+                    println!("{}: {}", style("Unknown command").red(), msg);
+                    info!("Unknown command: {}", msg);
+                }
+            } else {
+                // send message
+                match client.send(ConsoleCommand::Chat(msg.to_string())).await {
+                    Ok(_) => {}
+                    Err(_) => {
+                        info!("client already quit");
+                    }
+                }
+            }
+        }
+    }
+    true
+}
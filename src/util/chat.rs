@@ -0,0 +1,63 @@
+use serde_json::Value;
+
+// render a chat component (json) as plain text
+pub fn to_plain(json: &str) -> String {
+    match serde_json::from_str::<Value>(json) {
+        Ok(value) => plain(&value),
+        // not json, such as a legacy string
+        Err(_) => json.to_string(),
+    }
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(values) => values.iter().map(plain).collect(),
+        Value::Object(component) => {
+            let mut text = match (component.get("text"), component.get("translate")) {
+                (Some(text), _) => plain(text),
+                // no language file, keep the key and its arguments
+                (None, Some(Value::String(key))) => match component.get("with") {
+                    Some(Value::Array(with)) => format!(
+                        "{} [{}]",
+                        key,
+                        with.iter().map(plain).collect::<Vec<String>>().join(", ")
+                    ),
+                    _ => key.clone(),
+                },
+                _ => String::new(),
+            };
+            if let Some(Value::Array(extra)) = component.get("extra") {
+                extra.iter().for_each(|e| text.push_str(&plain(e)));
+            }
+            text
+        }
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_plain() {
+        assert_eq!(
+            to_plain("{\"text\":\"A Minecraft Server\"}"),
+            "A Minecraft Server"
+        );
+        assert_eq!(
+            to_plain(
+                "{\"text\":\"\",\"extra\":[{\"text\":\"Time \",\"color\":\"gold\"},\"left\"]}"
+            ),
+            "Time left"
+        );
+        assert_eq!(
+            to_plain("{\"translate\":\"chat.type.text\",\"with\":[\"Steve\",{\"text\":\"hi\"}]}"),
+            "chat.type.text [Steve, hi]"
+        );
+        assert_eq!(to_plain("\"plain\""), "plain");
+        assert_eq!(to_plain("not json"), "not json");
+    }
+}
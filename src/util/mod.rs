@@ -1,3 +1,5 @@
+pub mod chat;
+pub mod nbt;
 pub mod split;
 pub mod transfer_var;
 pub mod zlib;
@@ -0,0 +1,109 @@
+use serde_json::{Map, Value};
+
+// read a network nbt tag (1.20.2+, the root tag has no name) as json
+pub fn read(pkt: &[u8], index: &mut usize) -> Value {
+    let tag = pkt[*index];
+    *index += 1;
+    read_payload(pkt, index, tag)
+}
+
+fn take<const N: usize>(pkt: &[u8], index: &mut usize) -> [u8; N] {
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(&pkt[*index..*index + N]);
+    *index += N;
+    bytes
+}
+
+fn read_string(pkt: &[u8], index: &mut usize) -> String {
+    let len = u16::from_be_bytes(take(pkt, index)) as usize;
+    let text = String::from_utf8_lossy(&pkt[*index..*index + len]).to_string();
+    *index += len;
+    text
+}
+
+fn read_payload(pkt: &[u8], index: &mut usize, tag: u8) -> Value {
+    match tag {
+        1 => Value::from(i8::from_be_bytes(take(pkt, index))),
+        2 => Value::from(i16::from_be_bytes(take(pkt, index))),
+        3 => Value::from(i32::from_be_bytes(take(pkt, index))),
+        4 => Value::from(i64::from_be_bytes(take(pkt, index))),
+        5 => Value::from(f32::from_be_bytes(take(pkt, index))),
+        6 => Value::from(f64::from_be_bytes(take(pkt, index))),
+        7 | 11 | 12 => {
+            let len = i32::from_be_bytes(take(pkt, index)).max(0);
+            let element = match tag {
+                7 => 1,
+                11 => 3,
+                _ => 4,
+            };
+            Value::Array(
+                (0..len)
+                    .map(|_| read_payload(pkt, index, element))
+                    .collect(),
+            )
+        }
+        8 => Value::String(read_string(pkt, index)),
+        9 => {
+            let element = pkt[*index];
+            *index += 1;
+            let len = i32::from_be_bytes(take(pkt, index)).max(0);
+            Value::Array(
+                (0..len)
+                    .map(|_| read_payload(pkt, index, element))
+                    .collect(),
+            )
+        }
+        10 => {
+            let mut compound = Map::new();
+            loop {
+                let tag = pkt[*index];
+                *index += 1;
+                if tag == 0 {
+                    break;
+                }
+                let name = read_string(pkt, index);
+                compound.insert(name, read_payload(pkt, index, tag));
+            }
+            // lists of mixed types wrap their elements in {"": value}
+            match compound.remove("") {
+                Some(value) if compound.is_empty() => value,
+                Some(value) => {
+                    compound.insert(String::new(), value);
+                    Value::Object(compound)
+                }
+                None => Value::Object(compound),
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_string() {
+        let pkt = vec![0x08, 0x00, 0x02, 0x68, 0x69, 0xff];
+        let mut index = 0;
+        assert_eq!(read(&pkt, &mut index), Value::from("hi"));
+        assert_eq!(index, 5);
+    }
+
+    #[test]
+    fn test_read_compound() {
+        // {text: "Hi", bold: 1b, extra: [{"": "!"}]}
+        let pkt = vec![
+            0x0a, 0x08, 0x00, 0x04, 0x74, 0x65, 0x78, 0x74, 0x00, 0x02, 0x48, 0x69, 0x01, 0x00,
+            0x04, 0x62, 0x6f, 0x6c, 0x64, 0x01, 0x09, 0x00, 0x05, 0x65, 0x78, 0x74, 0x72, 0x61,
+            0x0a, 0x00, 0x00, 0x00, 0x01, 0x08, 0x00, 0x00, 0x00, 0x01, 0x21, 0x00, 0x00,
+        ];
+        let mut index = 0;
+        let value = read(&pkt, &mut index);
+        assert_eq!(index, pkt.len());
+        assert_eq!(
+            value,
+            serde_json::json!({"text": "Hi", "bold": 1, "extra": ["!"]})
+        );
+    }
+}
@@ -33,6 +33,31 @@ pub fn var_int2uint(b: Vec<u8>) -> Vec<i32> {
     res.iter().map(|x| *x as i32).collect()
 }
 
+// read a var int at index and move the index past it
+pub fn read_var_int(pkt: &[u8], index: &mut usize) -> i32 {
+    let num = crate::util::split::get_var_int_num(pkt[*index..].to_vec(), 1);
+    let value = var_int2uint(pkt[*index..*index + num[0]].to_vec())[0];
+    *index += num[0];
+    value
+}
+
+// read a var int prefixed string at index and move the index past it
+pub fn read_string(pkt: &[u8], index: &mut usize) -> String {
+    let n = read_var_int(pkt, index) as usize;
+    let value = String::from_utf8_lossy(&pkt[*index..*index + n]).to_string();
+    *index += n;
+    value
+}
+
+// a var int prefixed string
+pub fn write_string(text: &str) -> Vec<u8> {
+    [
+        uint2var_int(vec![text.len() as i32]),
+        text.as_bytes().to_vec(),
+    ]
+    .concat()
+}
+
 #[allow(dead_code)]
 pub fn uint2var_long(n: Vec<i64>) -> Vec<u8> {
     let n: Vec<u64> = n.iter().map(|x| *x as u64).collect();
@@ -68,6 +93,17 @@ pub fn var_long2uint(b: Vec<u8>) -> Vec<i64> {
     res.iter().map(|x| *x as i64).collect()
 }
 
+pub fn position2long(x: i32, y: i32, z: i32) -> i64 {
+    (((x as i64) & 0x3FFFFFF) << 38) | (((z as i64) & 0x3FFFFFF) << 12) | ((y as i64) & 0xFFF)
+}
+
+pub fn long2position(val: i64) -> (i32, i32, i32) {
+    let x = (val >> 38) as i32;
+    let y = (val << 52 >> 52) as i32;
+    let z = (val << 26 >> 38) as i32;
+    (x, y, z)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,6 +134,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_write_string() {
+        let data = write_string("forge");
+        assert_eq!(data, vec![0x05, 0x66, 0x6f, 0x72, 0x67, 0x65]);
+        assert_eq!(read_string(&data, &mut 0), "forge");
+    }
+
     #[test]
     fn test_uint2var_long() {
         let n: Vec<i64> = vec![
@@ -151,4 +194,21 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_position2long() {
+        let res = position2long(18357644, 831, -20882616);
+        assert_eq!(
+            res.to_be_bytes(),
+            [0x46, 0x07, 0x63, 0x2C, 0x15, 0xB4, 0x83, 0x3F]
+        );
+        assert_eq!(position2long(-1, -1, -1), -1);
+    }
+
+    #[test]
+    fn test_long2position() {
+        let val = i64::from_be_bytes([0x46, 0x07, 0x63, 0x2C, 0x15, 0xB4, 0x83, 0x3F]);
+        assert_eq!(long2position(val), (18357644, 831, -20882616));
+        assert_eq!(long2position(position2long(-3, 64, 7)), (-3, 64, 7));
+    }
 }
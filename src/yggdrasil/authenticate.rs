@@ -47,13 +47,13 @@ pub struct User {
     pub properties: Vec<u8>,
 }
 pub async fn send(
+    client: &Client,
     url: String,
     username: String,
     password: String,
     request_user: bool,
 ) -> Result<Response, String> {
-    let client: Client = Client::new();
-    let url = format!("https://{}/api/yggdrasil/authserver/authenticate", url);
+    let url = super::endpoint(&url, "/api/yggdrasil/authserver/authenticate");
     match client
         .post(url)
         .json(&json!({
@@ -75,37 +75,3 @@ pub async fn send(
         Err(e) => Err(e.to_string()),
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[tokio::test]
-    async fn test_send() {
-        let response = send(
-            "littleskin.cn".to_string(),
-            "weizixiang0@outlook.com".to_string(),
-            "xxx".to_string(),
-            true,
-        )
-        .await;
-        match response {
-            Ok(response) => {
-                assert_eq!(response.access_token, None);
-                assert_eq!(response.client_token, None);
-                assert_eq!(response.available_profiles, None);
-                assert_eq!(response.user, None);
-                assert_eq!(
-                    response.error,
-                    Some("ForbiddenOperationException".to_string())
-                );
-                assert_eq!(
-                    response.error_message,
-                    Some("输入的邮箱与密码不匹配".to_string())
-                );
-            }
-            Err(e) => {
-                println!("{}", e);
-            }
-        }
-    }
-}
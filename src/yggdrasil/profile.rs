@@ -0,0 +1,62 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Response {
+    #[serde(rename = "id")]
+    pub id: String,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "properties")]
+    pub properties: Vec<Property>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Property {
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "value")]
+    pub value: String,
+
+    #[serde(rename = "signature")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+pub async fn send(client: &Client, url: String, id: String) -> Result<Response, String> {
+    let url = super::endpoint(
+        &url,
+        &format!(
+            "/api/yggdrasil/sessionserver/session/minecraft/profile/{}?unsigned=false",
+            id
+        ),
+    );
+    match client.get(url).send().await {
+        Ok(response) => Ok(match response.json::<Response>().await {
+            Ok(response) => response,
+            Err(e) => return Err(e.to_string()),
+        }),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_properties() {
+        let properties = vec![Property {
+            name: "textures".to_string(),
+            value: "e30=".to_string(),
+            signature: None,
+        }];
+        assert_eq!(
+            serde_json::to_string(&properties).unwrap(),
+            r#"[{"name":"textures","value":"e30="}]"#
+        );
+    }
+}
@@ -52,6 +52,7 @@ pub struct User {
 }
 
 pub async fn send(
+    client: &Client,
     url: String,
     access_token: String,
     client_token: String,
@@ -59,8 +60,7 @@ pub async fn send(
     select_name: String,
     select_id: String,
 ) -> Result<Response, String> {
-    let client: Client = Client::new();
-    let url = format!("https://{}/api/yggdrasil/authserver/refresh", url);
+    let url = super::endpoint(&url, "/api/yggdrasil/authserver/refresh");
     match client
         .post(url)
         .json(&json!({
@@ -82,40 +82,3 @@ pub async fn send(
         Err(e) => Err(e.to_string()),
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[tokio::test]
-    async fn test_send() {
-        let response = send(
-            "littleskin.cn".to_string(),
-            "xxx".to_string(),
-            "xxx".to_string(),
-            true,
-            "xxx".to_string(),
-            "xxx".to_string(),
-        )
-        .await;
-        match response {
-            Ok(response) => {
-                assert_eq!(response.access_token, None);
-                assert_eq!(response.client_token, None);
-                assert_eq!(response.available_profiles, None);
-                assert_eq!(response.user, None);
-                assert_eq!(response.selected_profile, None);
-                assert_eq!(
-                    response.error,
-                    Some("ForbiddenOperationException".to_string())
-                );
-                assert_eq!(
-                    response.error_message,
-                    Some("无效的 AccessToken，请重新登录".to_string())
-                );
-            }
-            Err(e) => {
-                println!("{}", e);
-            }
-        }
-    }
-}
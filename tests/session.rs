@@ -0,0 +1,127 @@
+mod support;
+
+use mc_client::util::transfer_var::read_string;
+use mc_client::{Client, ClientBuilder, ClientEvent, ConnectionState, Events};
+use support::{serverbound, MockServer, WAIT};
+use tokio::time::timeout;
+
+async fn next_event(events: &mut Events, f: impl Fn(&ClientEvent) -> bool) -> ClientEvent {
+    timeout(WAIT, async {
+        loop {
+            let event = events.next().await.expect("client quit");
+            if f(&event) {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("event not published")
+}
+
+fn client(server: &MockServer) -> Client {
+    ClientBuilder::new("127.0.0.1", server.port)
+        .username("Steve")
+        .version(763)
+        .build()
+        .unwrap()
+}
+
+async fn login_play_chat_kick(threshold: Option<i32>) {
+    let server = MockServer::bind().await;
+    let client = client(&server);
+    let mut events = client.events();
+    client.connect().await.unwrap();
+
+    // login
+    let mut conn = server.accept().await;
+    let handshake = conn.login("Steve", threshold).await;
+    assert_eq!(handshake.protocol, 763);
+    assert_eq!(handshake.host, "127.0.0.1");
+    assert_eq!(handshake.port, server.port);
+    assert_eq!(handshake.intent, 2);
+
+    // play
+    conn.login_play(350).await;
+    next_event(&mut events, |e| {
+        *e == ClientEvent::Connection(ConnectionState::Joined)
+    })
+    .await;
+    conn.keep_alive(0x071B44F3).await;
+    conn.time(1915212, 13009).await;
+    conn.health(20.0, 20, 5.0).await;
+    let health = next_event(&mut events, |e| matches!(e, ClientEvent::Health { .. })).await;
+    assert_eq!(
+        health,
+        ClientEvent::Health {
+            health: 20.0,
+            food: 20,
+            saturation: 5.0
+        }
+    );
+
+    // chat from the server, longer than the threshold and than 127 bytes
+    let text = "hello ".repeat(60);
+    conn.chat(&format!("{{\"text\":\"{}\"}}", text)).await;
+    match next_event(&mut events, |e| matches!(e, ClientEvent::Chat { .. })).await {
+        ClientEvent::Chat {
+            sender, message, ..
+        } => {
+            assert_eq!(sender, None);
+            assert_eq!(message, text);
+        }
+        _ => unreachable!(),
+    }
+
+    // chat and command from the client
+    client.chat("hi").await.unwrap();
+    let chat = conn.expect(serverbound::CHAT_MESSAGE).await;
+    assert_eq!(read_string(&chat, &mut 0), "hi");
+    client.command("/time query daytime").await.unwrap();
+    let command = conn.expect(serverbound::CHAT_COMMAND).await;
+    assert_eq!(read_string(&command, &mut 0), "time query daytime");
+
+    // kick
+    conn.kick("{\"text\":\"Kicked by an operator\"}").await;
+    let kick = next_event(&mut events, |e| matches!(e, ClientEvent::Kick(_))).await;
+    assert_eq!(kick, ClientEvent::Kick("Kicked by an operator".to_string()));
+    next_event(&mut events, |e| {
+        *e == ClientEvent::Connection(ConnectionState::Offline)
+    })
+    .await;
+
+    client.quit().await.unwrap();
+    timeout(WAIT, client.wait()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_login_play_chat_kick() {
+    login_play_chat_kick(None).await;
+}
+
+#[tokio::test]
+async fn test_login_play_chat_kick_compressed() {
+    login_play_chat_kick(Some(64)).await;
+}
+
+#[tokio::test]
+async fn test_query_after_join() {
+    let server = MockServer::bind().await;
+    let client = client(&server);
+    let mut events = client.events();
+    // not connected
+    assert_eq!(client.query(mc_client::Query::Health).await, None);
+
+    client.connect().await.unwrap();
+    let mut conn = server.accept().await;
+    conn.login("Steve", None).await;
+    conn.login_play(1).await;
+    conn.health(7.5, 12, 0.0).await;
+    next_event(&mut events, |e| matches!(e, ClientEvent::Health { .. })).await;
+    assert_eq!(
+        client.query(mc_client::Query::Health).await,
+        Some(mc_client::QueryResponse::Health(Some((7.5, 12, 0.0))))
+    );
+
+    client.quit().await.unwrap();
+    timeout(WAIT, client.wait()).await.unwrap();
+}
@@ -0,0 +1,297 @@
+// mock servers for the integration tests
+#![allow(dead_code)]
+
+use mc_client::util::transfer_var::{read_string, read_var_int, uint2var_int};
+use mc_client::util::zlib;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{timeout, Duration};
+
+// a missing packet fails the test instead of hanging it
+pub const WAIT: Duration = Duration::from_secs(5);
+
+// clientbound ids of 763 (1.20.1)
+pub mod clientbound {
+    pub const LOGIN_SUCCESS: u8 = 0x02;
+    pub const SET_COMPRESSION: u8 = 0x03;
+    pub const DISCONNECT: u8 = 0x1A;
+    pub const KEEP_ALIVE: u8 = 0x23;
+    pub const LOGIN_PLAY: u8 = 0x28;
+    pub const SET_HEALTH: u8 = 0x57;
+    pub const UPDATE_TIME: u8 = 0x5E;
+    pub const SYSTEM_CHAT_MESSAGE: u8 = 0x64;
+}
+
+// serverbound ids of 763 (1.20.1)
+pub mod serverbound {
+    pub const HANDSHAKE: u8 = 0x00;
+    pub const LOGIN_START: u8 = 0x00;
+    pub const CHAT_COMMAND: u8 = 0x04;
+    pub const CHAT_MESSAGE: u8 = 0x05;
+    pub const KEEP_ALIVE: u8 = 0x12;
+}
+
+pub fn string(s: &str) -> Vec<u8> {
+    [uint2var_int(vec![s.len() as i32]), s.as_bytes().to_vec()].concat()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Handshake {
+    pub protocol: i32,
+    pub host: String,
+    pub port: u16,
+    pub intent: i32,
+}
+
+// minecraft server on a local port, one connection at a time
+pub struct MockServer {
+    listener: TcpListener,
+    pub port: u16,
+}
+
+impl MockServer {
+    pub async fn bind() -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        MockServer { listener, port }
+    }
+
+    pub async fn accept(&self) -> Connection {
+        let (stream, _) = timeout(WAIT, self.listener.accept())
+            .await
+            .expect("client did not connect")
+            .unwrap();
+        Connection {
+            stream,
+            threshold: None,
+        }
+    }
+}
+
+pub struct Connection {
+    stream: TcpStream,
+    // compression after set compression
+    threshold: Option<i32>,
+}
+
+impl Connection {
+    // one packet, compressed at the threshold
+    pub async fn write(&mut self, id: u8, payload: &[u8]) {
+        let data = [vec![id], payload.to_vec()].concat();
+        let data = match self.threshold {
+            Some(threshold) if data.len() as i32 >= threshold => [
+                uint2var_int(vec![data.len() as i32]),
+                zlib::compress(data).unwrap(),
+            ]
+            .concat(),
+            Some(_) => [vec![0x00], data].concat(),
+            None => data,
+        };
+        let frame = [uint2var_int(vec![data.len() as i32]), data].concat();
+        self.stream.write_all(&frame).await.unwrap();
+    }
+
+    // next packet id and payload
+    pub async fn read(&mut self) -> (u8, Vec<u8>) {
+        timeout(WAIT, self.read_frame())
+            .await
+            .expect("client sent nothing")
+    }
+
+    async fn read_frame(&mut self) -> (u8, Vec<u8>) {
+        let mut len = 0;
+        for i in 0..3 {
+            let b = self.stream.read_u8().await.unwrap();
+            len |= ((b & 0x7F) as usize) << (7 * i);
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+        let mut data = vec![0; len];
+        self.stream.read_exact(&mut data).await.unwrap();
+        if self.threshold.is_some() {
+            let mut index = 0;
+            let data_len = read_var_int(&data, &mut index);
+            data = match data_len {
+                0 => data[index..].to_vec(),
+                _ => zlib::decompress(data[index..].to_vec()).unwrap(),
+            };
+        }
+        (data[0], data[1..].to_vec())
+    }
+
+    // payload of the next packet with the id, others are skipped
+    pub async fn expect(&mut self, id: u8) -> Vec<u8> {
+        loop {
+            let (packet_id, payload) = self.read().await;
+            if packet_id == id {
+                return payload;
+            }
+        }
+    }
+
+    pub async fn handshake(&mut self) -> Handshake {
+        let pkt = self.expect(serverbound::HANDSHAKE).await;
+        let mut index = 0;
+        let protocol = read_var_int(&pkt, &mut index);
+        let host = read_string(&pkt, &mut index);
+        let port = u16::from_be_bytes([pkt[index], pkt[index + 1]]);
+        index += 2;
+        let intent = read_var_int(&pkt, &mut index);
+        Handshake {
+            protocol,
+            host,
+            port,
+            intent,
+        }
+    }
+
+    // handshake and login start up to login success, returns the handshake
+    pub async fn login(&mut self, username: &str, threshold: Option<i32>) -> Handshake {
+        let handshake = self.handshake().await;
+        let login_start = self.expect(serverbound::LOGIN_START).await;
+        assert_eq!(read_string(&login_start, &mut 0), username);
+        if let Some(threshold) = threshold {
+            self.write(clientbound::SET_COMPRESSION, &uint2var_int(vec![threshold]))
+                .await;
+            self.threshold = Some(threshold);
+        }
+        let login_success = [vec![0x01; 16], string(username), vec![0x00]].concat();
+        self.write(clientbound::LOGIN_SUCCESS, &login_success).await;
+        handshake
+    }
+
+    // entity id, the rest of the packet is not read by the client
+    pub async fn login_play(&mut self, entity_id: i32) {
+        let pkt = [entity_id.to_be_bytes().to_vec(), vec![0x00]].concat();
+        self.write(clientbound::LOGIN_PLAY, &pkt).await;
+    }
+
+    // sends a keep alive and asserts the echo
+    pub async fn keep_alive(&mut self, id: i64) {
+        self.write(clientbound::KEEP_ALIVE, &id.to_be_bytes()).await;
+        assert_eq!(
+            self.expect(serverbound::KEEP_ALIVE).await,
+            id.to_be_bytes().to_vec()
+        );
+    }
+
+    pub async fn chat(&mut self, json: &str) {
+        let pkt = [string(json), vec![0x00]].concat();
+        self.write(clientbound::SYSTEM_CHAT_MESSAGE, &pkt).await;
+    }
+
+    pub async fn time(&mut self, world_age: i64, time_of_day: i64) {
+        let pkt = [world_age.to_be_bytes(), time_of_day.to_be_bytes()].concat();
+        self.write(clientbound::UPDATE_TIME, &pkt).await;
+    }
+
+    pub async fn health(&mut self, health: f32, food: i32, saturation: f32) {
+        let pkt = [
+            health.to_be_bytes().to_vec(),
+            uint2var_int(vec![food]),
+            saturation.to_be_bytes().to_vec(),
+        ]
+        .concat();
+        self.write(clientbound::SET_HEALTH, &pkt).await;
+    }
+
+    // disconnect with the json reason, then close
+    pub async fn kick(mut self, json: &str) {
+        self.write(clientbound::DISCONNECT, &string(json)).await;
+        self.stream.shutdown().await.unwrap();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+// http server answering every request with the json of the route, 404 otherwise
+pub struct HttpStub {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl HttpStub {
+    // routes of (method, path prefix, status, json)
+    pub async fn start(routes: Vec<(&'static str, &'static str, u16, String)>) -> HttpStub {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(_) => break,
+                };
+                let request = match read_request(&mut stream).await {
+                    Some(request) => request,
+                    None => continue,
+                };
+                let (status, body) = routes
+                    .iter()
+                    .find(|(method, path, _, _)| {
+                        request.method == *method && request.path.starts_with(path)
+                    })
+                    .map(|(_, _, status, body)| (*status, body.clone()))
+                    .unwrap_or((404, String::new()));
+                log.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        HttpStub { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut data = Vec::new();
+    let mut buf = [0; 1024];
+    // headers
+    let end = loop {
+        let n = stream.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buf[..n]);
+        if let Some(i) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break i + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&data[..end]).to_string();
+    let mut line = head.lines().next()?.split_whitespace();
+    let method = line.next()?.to_string();
+    let path = line.next()?.to_string();
+    let length = head
+        .lines()
+        .filter_map(|l| l.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    // body
+    while data.len() < end + length {
+        let n = stream.read(&mut buf).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+    let body = String::from_utf8_lossy(&data[end..]).to_string();
+    Some(Request { method, path, body })
+}
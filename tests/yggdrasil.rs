@@ -0,0 +1,180 @@
+mod support;
+
+use mc_client::yggdrasil::{authenticate, profile, refresh};
+use serde_json::{json, Value};
+use support::HttpStub;
+
+const ID: &str = "037f5695cc3039649caf8c000e107c14";
+
+fn forbidden(message: &str) -> String {
+    json!({
+        "error": "ForbiddenOperationException",
+        "errorMessage": message
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_authenticate() {
+    let stub = HttpStub::start(vec![(
+        "POST",
+        "/api/yggdrasil/authserver/authenticate",
+        200,
+        json!({
+            "accessToken": "access",
+            "clientToken": "client",
+            "availableProfiles": [{"id": ID, "name": "Karenina"}],
+            "user": {"id": "user", "properties": []}
+        })
+        .to_string(),
+    )])
+    .await;
+    let response = authenticate::send(
+        &reqwest::Client::new(),
+        stub.url.clone(),
+        "karenina@example.com".to_string(),
+        "password".to_string(),
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.access_token, Some("access".to_string()));
+    assert_eq!(response.client_token, Some("client".to_string()));
+    assert_eq!(response.available_profiles.unwrap()[0].name, "Karenina");
+    assert_eq!(response.error, None);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["agent"]["name"], "Minecraft");
+    assert_eq!(body["username"], "karenina@example.com");
+    assert_eq!(body["password"], "password");
+    assert_eq!(body["requestUser"], true);
+}
+
+#[tokio::test]
+async fn test_authenticate_forbidden() {
+    let stub = HttpStub::start(vec![(
+        "POST",
+        "/api/yggdrasil/authserver/authenticate",
+        403,
+        forbidden("输入的邮箱与密码不匹配"),
+    )])
+    .await;
+    let response = authenticate::send(
+        &reqwest::Client::new(),
+        stub.url.clone(),
+        "karenina@example.com".to_string(),
+        "xxx".to_string(),
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.access_token, None);
+    assert_eq!(response.available_profiles, None);
+    assert_eq!(
+        response.error,
+        Some("ForbiddenOperationException".to_string())
+    );
+    assert_eq!(
+        response.error_message,
+        Some("输入的邮箱与密码不匹配".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_refresh() {
+    let stub = HttpStub::start(vec![(
+        "POST",
+        "/api/yggdrasil/authserver/refresh",
+        200,
+        json!({
+            "accessToken": "access2",
+            "clientToken": "client",
+            "selectedProfile": {"id": ID, "name": "Karenina"},
+            "user": {"id": "user", "properties": []}
+        })
+        .to_string(),
+    )])
+    .await;
+    let response = refresh::send(
+        &reqwest::Client::new(),
+        stub.url.clone(),
+        "access".to_string(),
+        "client".to_string(),
+        true,
+        "Karenina".to_string(),
+        ID.to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.access_token, Some("access2".to_string()));
+    assert_eq!(response.selected_profile.unwrap().id, ID);
+
+    let body: Value = serde_json::from_str(&stub.requests()[0].body).unwrap();
+    assert_eq!(body["accessToken"], "access");
+    assert_eq!(body["selectedProfile"]["name"], "Karenina");
+}
+
+#[tokio::test]
+async fn test_refresh_forbidden() {
+    let stub = HttpStub::start(vec![(
+        "POST",
+        "/api/yggdrasil/authserver/refresh",
+        403,
+        forbidden("无效的 AccessToken，请重新登录"),
+    )])
+    .await;
+    let response = refresh::send(
+        &reqwest::Client::new(),
+        stub.url.clone(),
+        "xxx".to_string(),
+        "xxx".to_string(),
+        true,
+        "xxx".to_string(),
+        "xxx".to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.selected_profile, None);
+    assert_eq!(
+        response.error_message,
+        Some("无效的 AccessToken，请重新登录".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_profile() {
+    let stub = HttpStub::start(vec![(
+        "GET",
+        "/api/yggdrasil/sessionserver/session/minecraft/profile/",
+        200,
+        json!({
+            "id": ID,
+            "name": "Karenina",
+            "properties": [{"name": "textures", "value": "e30=", "signature": "c2ln"}]
+        })
+        .to_string(),
+    )])
+    .await;
+    let response = profile::send(&reqwest::Client::new(), stub.url.clone(), ID.to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.name, "Karenina");
+    assert_eq!(response.properties[0].signature, Some("c2ln".to_string()));
+    assert_eq!(
+        stub.requests()[0].path,
+        format!(
+            "/api/yggdrasil/sessionserver/session/minecraft/profile/{}?unsigned=false",
+            ID
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_profile_unknown() {
+    // unknown profiles have no content
+    let stub = HttpStub::start(vec![]).await;
+    let response = profile::send(&reqwest::Client::new(), stub.url.clone(), ID.to_string()).await;
+    assert!(response.is_err());
+}